@@ -73,6 +73,53 @@ impl Display for SemId {
 }
 
 pub const SEM_ID_TAG: [u8; 32] = *b"urn:ubideco:strict-types:typ:v01";
+pub const BACK_REF_TAG: [u8; 32] = *b"urn:ubideco:strict-types:ref:v01";
+pub const REC_GROUP_TAG: [u8; 32] = *b"urn:ubideco:strict-types:grp:v01";
+
+impl SemId {
+    /// Marker used instead of the semantic id of a named type whenever it is referenced from
+    /// inside its own recursion group, i.e. by a type which is itself (directly or indirectly)
+    /// referenced by it. Since such ids can't be computed before each other, the reference
+    /// commits to the fully qualified type name only; the ids of the group types commit to the
+    /// definitions of the whole group instead (see [`SemId::grouped`]).
+    pub fn back_ref(lib: &LibName, name: &TypeName) -> SemId {
+        let tag = sha2::Sha256::new_with_prefix(BACK_REF_TAG).finalize();
+        let mut hasher = sha2::Sha256::new();
+        hasher.commit_consume(tag);
+        hasher.commit_consume(tag);
+        lib.sem_commit(&mut hasher);
+        name.sem_commit(&mut hasher);
+        SemId::from_byte_array(hasher.finalize())
+    }
+
+    /// Computes id of a recursion group from the ids of its named types, computed with the
+    /// back-reference markers and provided in the order of the type names.
+    pub fn recursion_group(members: impl IntoIterator<Item = SemId>) -> SemId {
+        let members = members.into_iter().collect::<Vec<_>>();
+        let tag = sha2::Sha256::new_with_prefix(REC_GROUP_TAG).finalize();
+        let mut hasher = sha2::Sha256::new();
+        hasher.commit_consume(tag);
+        hasher.commit_consume(tag);
+        hasher.commit_consume((members.len() as u16).to_le_bytes());
+        for id in members {
+            id.sem_commit(&mut hasher);
+        }
+        SemId::from_byte_array(hasher.finalize())
+    }
+
+    /// Semantic id of a type from the recursion group `group`, where `self` is the id computed
+    /// from the type definition using the back-reference markers. This makes the type commit to
+    /// the definitions of all other types of the group, which it references by names only.
+    pub fn grouped(self, group: SemId) -> SemId {
+        let tag = sha2::Sha256::new_with_prefix(REC_GROUP_TAG).finalize();
+        let mut hasher = sha2::Sha256::new();
+        hasher.commit_consume(tag);
+        hasher.commit_consume(tag);
+        group.sem_commit(&mut hasher);
+        self.sem_commit(&mut hasher);
+        SemId::from_byte_array(hasher.finalize())
+    }
+}
 
 impl TypeRef for SemId {
     fn is_unicode_char(&self) -> bool { Self::unicode_char() == *self }
@@ -72,7 +72,7 @@ impl Path {
 
     pub fn with(step: Step) -> Path { Path(small_vec!(step)) }
 
-    pub fn iter(&self) -> std::slice::Iter<Step> { self.0.iter() }
+    pub fn iter(&self) -> std::slice::Iter<'_, Step> { self.0.iter() }
 }
 
 impl<'path> IntoIterator for &'path Path {
@@ -103,27 +103,37 @@ impl<'ty, Ref: TypeRef> PathError<'ty, Ref> {
 }
 
 impl<Ref: TypeRef> Ty<Ref> {
-    pub fn at_path(&self, path: &Path) -> Result<&Self, PathError<Ref>> {
+    /// Returns reference to the type component addressed by a single path step, if the step is
+    /// applicable to the type.
+    pub fn ty_at_step(&self, step: &Step) -> Option<&Ref> {
+        match (self, step) {
+            (Ty::Struct(fields), Step::NamedField(name)) => fields.ty_by_name(name),
+            (Ty::Union(variants), Step::Variant(name)) => variants.ty_by_name(name),
+            (Ty::Struct(fields), Step::UnnamedField(pos)) => fields.ty_by_pos(*pos),
+            (Ty::Tuple(fields), Step::UnnamedField(pos)) => fields.ty_by_pos(*pos),
+            (Ty::Union(variants), Step::UnnamedField(tag)) => variants.ty_by_tag(*tag),
+            (Ty::Array(ty, _), Step::Index) => Some(ty),
+            (Ty::List(ty, _), Step::List) => Some(ty),
+            (Ty::Set(ty, _), Step::Set) => Some(ty),
+            (Ty::Map(ty, _, _), Step::MapKey) => Some(ty),
+            (Ty::Map(_, ty, _), Step::MapValue) => Some(ty),
+            (_, _) => None,
+        }
+    }
+
+    /// Follows the path through the types which are inlined into this type.
+    ///
+    /// Type references which are not inline types (like semantic ids) can't be followed; use
+    /// [`crate::TypeSystem::ty_at_path`] to resolve paths through a type system.
+    pub fn at_path(&self, path: &Path) -> Result<&Self, PathError<'_, Ref>> {
         let mut ty = self;
-        let mut path = path.clone();
         let mut path_so_far = Path::new();
-        while let Some(step) = path.pop() {
-            let res = match (self, &step) {
-                (Ty::Struct(fields), Step::NamedField(name)) => fields.ty_by_name(name),
-                (Ty::Union(variants), Step::Variant(name)) => variants.ty_by_name(name),
-                (Ty::Struct(fields), Step::UnnamedField(tag)) => fields.ty_by_pos(*tag),
-                (Ty::Union(variants), Step::UnnamedField(tag)) => variants.ty_by_tag(*tag),
-                (Ty::Array(ty, _), Step::Index) => Some(ty),
-                (Ty::List(ty, _), Step::List) => Some(ty),
-                (Ty::Set(ty, _), Step::Set) => Some(ty),
-                (Ty::Map(ty, _, _), Step::MapKey) => Some(ty),
-                (Ty::Map(_, ty, _), Step::MapValue) => Some(ty),
-                (_, _) => None,
-            };
-            path_so_far.push(step).expect("confinement collection guarantees");
-            ty = res
+        for step in path {
+            path_so_far.push(step.clone()).expect("confinement collection guarantees");
+            ty = ty
+                .ty_at_step(step)
                 .and_then(|r| r.as_ty())
-                .ok_or_else(|| PathError::new(self, path_so_far.clone()))?
+                .ok_or_else(|| PathError::new(self, path_so_far.clone()))?;
         }
         Ok(ty)
     }
@@ -142,3 +152,46 @@ impl<Ref: TypeRef> Ty<Ref> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use encoding::Primitive;
+
+    use super::*;
+    use crate::ast::{Field, UnnamedFields};
+    use crate::TranspileRef;
+
+    #[test]
+    fn nested_path() {
+        let prim = |prim| TranspileRef::from(Ty::Primitive(prim));
+        let pair = Ty::Tuple(
+            UnnamedFields::try_from(vec![prim(Primitive::U8), prim(Primitive::U16)]).unwrap(),
+        );
+        let ty = Ty::<TranspileRef>::Struct(
+            vec![
+                Field {
+                    name: fname!("pair"),
+                    ty: pair.into(),
+                },
+                Field {
+                    name: fname!("other"),
+                    ty: prim(Primitive::U32),
+                },
+            ]
+            .try_into()
+            .unwrap(),
+        );
+
+        let mut path = Path::with(Step::NamedField(fname!("pair")));
+        path.push(Step::UnnamedField(1)).unwrap();
+        assert_eq!(ty.at_path(&path).unwrap(), &Ty::Primitive(Primitive::U16));
+        assert_eq!(
+            ty.at_path(&Path::with(Step::UnnamedField(1))).unwrap(),
+            &Ty::Primitive(Primitive::U32)
+        );
+
+        path.push(Step::Index).unwrap();
+        let err = ty.at_path(&path).unwrap_err();
+        assert_eq!(err.path, path);
+    }
+}
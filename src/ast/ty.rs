@@ -39,7 +39,7 @@ pub trait TypeRef:
     SemCommit + Clone + StrictEncode + StrictDecode + StrictDumb + Eq + Debug + Sized
 {
     fn as_ty(&self) -> Option<&Ty<Self>> { None }
-    fn type_refs(&self) -> Iter<Self> { Iter::from(self) }
+    fn type_refs(&self) -> Iter<'_, Self> { Iter::from(self) }
 
     fn is_compound(&self) -> bool { false }
     fn is_byte(&self) -> bool { false }
@@ -223,9 +223,9 @@ impl<Ref: TypeRef> Ty<Ref> {
         match self {
             Ty::Union(variants)
                 if variants.len() == 2
-                    && &variants.unwrap_first().name == &vname!("none")
+                    && variants.unwrap_first().name == vname!("none")
                     && variants.unwrap_first().tag == 0
-                    && &variants.unwrap_last().name == &vname!("some")
+                    && variants.unwrap_last().name == vname!("some")
                     && variants.unwrap_last().tag == 1 =>
             {
                 Some(variants.last_key_value().unwrap().1)
@@ -563,8 +563,8 @@ impl<'a, Ref: TypeRef> IntoIterator for &'a UnionVariants<Ref> {
 impl<Ref: TypeRef> UnionVariants<Ref> {
     pub fn into_inner(self) -> BTreeMap<Variant, Ref> { self.0.into_inner() }
 
-    pub fn unwrap_first(&self) -> &Variant { &self.0.first_key_value().unwrap().0 }
-    pub fn unwrap_last(&self) -> &Variant { &self.0.last_key_value().unwrap().0 }
+    pub fn unwrap_first(&self) -> &Variant { self.0.first_key_value().unwrap().0 }
+    pub fn unwrap_last(&self) -> &Variant { self.0.last_key_value().unwrap().0 }
 
     pub fn into_keys(self) -> std::collections::btree_map::IntoKeys<Variant, Ref> {
         self.0.into_inner().into_keys()
@@ -0,0 +1,462 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{Display, Write as _};
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::{env, process};
+
+use strict_encoding::{LibName, STRICT_TYPES_LIB};
+use strict_types::codegen::RustGen;
+use strict_types::stl::{std_stl, strict_types_stl};
+use strict_types::typify::{TypeSpec, TypedVal};
+use strict_types::{
+    LibResolver, StlFormat, StrictVal, StsFormat, SymbolicSys, SystemBuilder, TypeLib,
+};
+
+const USAGE: &str = "Usage: strict-types <COMMAND> [ARGS]
+
+Commands:
+  inspect <LIB>                       show id, dependencies and types of a library
+  convert <LIB> <OUTPUT>              convert library into the format given by the OUTPUT file
+                                      extension (stl, sty or sta)
+  link <LIBS> [OUTPUT]                build a type system out of libraries, printing it or
+                                      saving it into a file with sts or stsa extension
+  tree <LIBS> <TYPE>                  print type tree of a type in vesper (alias: vesper)
+  decode <LIBS> <TYPE> [INPUT]        decode strict-encoded data of a type and print them
+  encode <LIBS> <TYPE> [INPUT] [OUTPUT]
+                                      encode a value of a type into strict encoding
+  stl [DIR]                           write built-in libraries into DIR (defaults to `stl`)
+  rustgen <LIB> [OUTPUT]              generate Rust types for a library or for all libraries
+                                      of a type system file (sts or stsa)
+
+Options for decode and encode:
+  --format <FORMAT>                   text format of the values: ston (default), json, yaml
+                                      or toml
+
+Options for rustgen:
+  --extern <LIB>=<RUST_PATH>          use types of library LIB from the Rust module at
+                                      RUST_PATH instead of generating them; may be repeated
+
+<LIBS> is either a directory, from which all libraries in binary and armored formats are
+used, a library file, which is used together with its dependencies from the same
+directory, or a type system file (sts or stsa). <TYPE> is a fully qualified type name or a
+semantic type id. Data are read from the standard input and written to the standard output
+if INPUT or OUTPUT are not given or are `-`.";
+
+const STD_VERSION: &str = "0.1.0";
+const STD_HEADER: &str = "
+  Description: Strict types standard library
+  Author: Dr Maxim Orlovsky <orlovsky@ubideco.org>
+  Copyright (C) 2023-2024 UBIDECO Institute. All rights reserved.
+  License: Apache-2.0";
+
+const STRICT_TYPES_VERSION: &str = "0.2.0";
+const STRICT_TYPES_HEADER: &str = "
+  Description: Confined generalized algebraic data types (GADT)
+  Author: Dr Maxim Orlovsky <orlovsky@ubideco.org>
+  Copyright (C) 2023-2024 UBIDECO Institute. All rights reserved.
+  License: Apache-2.0";
+
+const VESPER_HEADER: &str = "{-
+  Description: Data type layout in Vesper language
+  Author: Dr Maxim Orlovsky <orlovsky@ubideco.org>
+  Copyright (C) 2024 UBIDECO Institute. All rights reserved.
+  License: Apache-2.0
+-}";
+
+/// Reasons for the command failure, defining the process exit code.
+enum Failure {
+    /// Invalid command line arguments; exit code 2.
+    Usage(String),
+    /// Command can't be completed; exit code 1.
+    Fatal(String),
+}
+
+impl Failure {
+    fn exit(self) -> ! {
+        match self {
+            Failure::Usage(msg) => {
+                eprintln!("Error: {msg}\n\n{USAGE}");
+                process::exit(2)
+            }
+            Failure::Fatal(msg) => {
+                eprintln!("Error: {msg}");
+                process::exit(1)
+            }
+        }
+    }
+}
+
+fn usage(msg: impl Display) -> Failure { Failure::Usage(msg.to_string()) }
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+enum ValFormat {
+    #[default]
+    Ston,
+    #[cfg(feature = "serde")]
+    Json,
+    #[cfg(feature = "serde")]
+    Yaml,
+    #[cfg(feature = "serde")]
+    Toml,
+}
+
+impl FromStr for ValFormat {
+    type Err = Failure;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ston" => Ok(ValFormat::Ston),
+            #[cfg(feature = "serde")]
+            "json" => Ok(ValFormat::Json),
+            #[cfg(feature = "serde")]
+            "yaml" => Ok(ValFormat::Yaml),
+            #[cfg(feature = "serde")]
+            "toml" => Ok(ValFormat::Toml),
+            other => Err(usage(format!("unsupported value format `{other}`"))),
+        }
+    }
+}
+
+fn main() {
+    if let Err(failure) = run(env::args().skip(1).collect()) {
+        failure.exit()
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), Failure> {
+    let mut format = None;
+    let mut gen = RustGen::new();
+    let mut has_externs = false;
+    let mut params = vec![];
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(());
+            }
+            "--format" => {
+                let name = args.next().ok_or_else(|| usage("missing `--format` value"))?;
+                format = Some(ValFormat::from_str(&name)?);
+            }
+            "--extern" => {
+                let spec = args.next().ok_or_else(|| usage("missing `--extern` value"))?;
+                let (lib, path) = spec
+                    .split_once('=')
+                    .ok_or_else(|| usage(format!("invalid `--extern` value `{spec}`")))?;
+                let lib = LibName::try_from(lib.to_owned())
+                    .map_err(|err| usage(format!("invalid library name `{lib}`: {err}")))?;
+                gen = gen.extern_lib(lib, path);
+                has_externs = true;
+            }
+            _ if arg.starts_with("--") => return Err(usage(format!("unknown option `{arg}`"))),
+            _ => params.push(arg),
+        }
+    }
+    let Some((command, params)) = params.split_first() else {
+        return Err(usage("no command given"));
+    };
+    let params = params.iter().map(String::as_str).collect::<Vec<_>>();
+    if format.is_some() && !matches!(command.as_str(), "decode" | "encode") {
+        return Err(usage(format!("command `{command}` doesn't accept `--format` option")));
+    }
+    if has_externs && command != "rustgen" {
+        return Err(usage(format!("command `{command}` doesn't accept `--extern` option")));
+    }
+    let format = format.unwrap_or_default();
+
+    match (command.as_str(), params.as_slice()) {
+        ("inspect", [lib]) => inspect(lib),
+        ("convert", [lib, output]) => convert(lib, output),
+        ("link", [libs]) => write_output(None, link(libs)?.to_string().as_bytes()),
+        ("link", [libs, output]) => link(libs)?.save(output).map_err(|err| {
+            Failure::Fatal(format!("unable to save type system to `{output}`. Details: {err}"))
+        }),
+        ("tree" | "vesper", [libs, spec]) => {
+            let sys = link(libs)?;
+            let spec = type_spec(spec)?;
+            let tree = sys
+                .type_tree(spec.clone())
+                .ok_or_else(|| Failure::Fatal(format!("unknown type `{spec}`")))?;
+            write_output(None, tree.to_string().as_bytes())
+        }
+        ("decode", [libs, spec, input @ ..]) if input.len() <= 1 => {
+            decode(libs, spec, input.first().copied(), format)
+        }
+        ("encode", [libs, spec, files @ ..]) if files.len() <= 2 => {
+            encode(libs, spec, files.first().copied(), files.get(1).copied(), format)
+        }
+        ("stl", dir) if dir.len() <= 1 => stl(dir.first().copied().unwrap_or("stl")),
+        ("rustgen", [input, output @ ..]) if output.len() <= 1 => {
+            rustgen(&gen, input, output.first().copied())
+        }
+        (
+            "inspect" | "convert" | "link" | "tree" | "vesper" | "decode" | "encode" | "stl"
+            | "rustgen",
+            _,
+        ) => Err(usage(format!("invalid number of arguments for `{command}`"))),
+        (command, _) => Err(usage(format!("unknown command `{command}`"))),
+    }
+}
+
+fn type_spec(s: &str) -> Result<TypeSpec, Failure> {
+    TypeSpec::from_str(s).map_err(|err| usage(format!("invalid type `{s}`: {err}")))
+}
+
+fn lib_format(path: &Path) -> Result<StlFormat, Failure> {
+    let ext = path.extension().ok_or_else(|| {
+        usage(format!("file name `{}` has no extension specifying library format", path.display()))
+    })?;
+    StlFormat::from_str(&ext.to_string_lossy()).map_err(usage)
+}
+
+fn lib_dir(path: &Path) -> &Path {
+    path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."))
+}
+
+fn resolver(dir: &Path) -> Result<LibResolver, Failure> {
+    LibResolver::from_dir(dir).map_err(|err| Failure::Fatal(err.to_string()))
+}
+
+fn load_lib(path: &Path) -> Result<(TypeLib, Option<LibResolver>), Failure> {
+    // Only libraries in source format need their dependencies to be loaded
+    let resolver = match lib_format(path)? {
+        StlFormat::Source => Some(resolver(lib_dir(path))?),
+        _ => None,
+    };
+    let libs = resolver.iter().flat_map(LibResolver::libs);
+    let lib = TypeLib::load_with(path, libs).map_err(|err| {
+        Failure::Fatal(format!("unable to load library `{}`. Details: {err}", path.display()))
+    })?;
+    Ok((lib, resolver))
+}
+
+fn inspect(path: &str) -> Result<(), Failure> {
+    let (lib, _) = load_lib(Path::new(path))?;
+    let mut text = format!("Id: {:+}\nName: {}\nDependencies:\n", lib.id(), lib.name);
+    for dep in &lib.dependencies {
+        writeln!(text, "  {dep}").expect("writing to string");
+    }
+    if lib.dependencies.is_empty() {
+        text.push_str("  -- none\n");
+    }
+    text.push_str("Types:\n");
+    let width = lib.types.keys().map(|name| name.len()).max().unwrap_or_default();
+    for (name, id) in lib.type_ids() {
+        writeln!(text, "  {name:width$}  {id}").expect("writing to string");
+    }
+    write_output(None, text.as_bytes())
+}
+
+fn convert(input: &str, output: &str) -> Result<(), Failure> {
+    let output = Path::new(output);
+    let format = lib_format(output)?;
+    let (lib, _) = load_lib(Path::new(input))?;
+    let write_err = |err: io::Error| {
+        Failure::Fatal(format!("unable to write to `{}`. Details: {err}", output.display()))
+    };
+    let file = File::create(output).map_err(write_err)?;
+    lib.write_to(format, file, None, None).map_err(write_err)
+}
+
+fn is_sys(path: &Path) -> bool {
+    path.extension().and_then(|ext| StsFormat::from_str(&ext.to_string_lossy()).ok()).is_some()
+}
+
+fn load_sys(path: &Path) -> Result<SymbolicSys, Failure> {
+    SymbolicSys::load(path).map_err(|err| {
+        Failure::Fatal(format!("unable to load type system `{}`. Details: {err}", path.display()))
+    })
+}
+
+fn link(path: &str) -> Result<SymbolicSys, Failure> {
+    let path = Path::new(path);
+    let import_err = |err: strict_types::typesys::Error| Failure::Fatal(err.to_string());
+    if is_sys(path) {
+        return load_sys(path);
+    }
+    let builder = if path.is_dir() {
+        resolver(path)?
+            .libs()
+            .cloned()
+            .try_fold(SystemBuilder::new(), SystemBuilder::import)
+            .map_err(import_err)?
+    } else {
+        let (lib, resolver) = load_lib(path)?;
+        let resolver = match resolver {
+            Some(resolver) => resolver,
+            None => self::resolver(lib_dir(path))?,
+        };
+        resolver.import(SystemBuilder::new(), lib).map_err(|err| Failure::Fatal(err.to_string()))?
+    };
+    builder.finalize().map_err(|errors| {
+        let errors = errors.iter().map(|err| format!("\n- {err}")).collect::<String>();
+        Failure::Fatal(format!("unable to link type system:{errors}"))
+    })
+}
+
+fn read_input(input: Option<&str>) -> Result<Vec<u8>, Failure> {
+    let mut data = vec![];
+    match input {
+        None | Some("-") => io::stdin().read_to_end(&mut data).map(|_| data),
+        Some(path) => fs::read(path),
+    }
+    .map_err(|err| Failure::Fatal(format!("unable to read input data. Details: {err}")))
+}
+
+fn write_output(output: Option<&str>, data: &[u8]) -> Result<(), Failure> {
+    match output {
+        None | Some("-") => io::stdout().write_all(data),
+        Some(path) => fs::write(path, data),
+    }
+    .map_err(|err| Failure::Fatal(format!("unable to write output data. Details: {err}")))
+}
+
+fn decode(libs: &str, spec: &str, input: Option<&str>, format: ValFormat) -> Result<(), Failure> {
+    let spec = type_spec(spec)?;
+    let sys = link(libs)?;
+    let data = read_input(input)?;
+    let typed = sys
+        .strict_deserialize_type(spec, &data)
+        .map_err(|err| Failure::Fatal(format!("unable to decode data. Details: {err}")))?;
+    #[cfg(feature = "serde")]
+    let export_err =
+        |err: &dyn Display| Failure::Fatal(format!("unable to export value. Details: {err}"));
+    let text = match format {
+        ValFormat::Ston => format!("{}\n", typed.as_val()),
+        #[cfg(feature = "serde")]
+        ValFormat::Json => {
+            let json = typed.to_json(sys.as_types()).map_err(|err| export_err(&err))?;
+            serde_json::to_string_pretty(&json).expect("JSON values are serializable") + "\n"
+        }
+        #[cfg(feature = "serde")]
+        ValFormat::Yaml => {
+            let yaml = typed.to_yaml(sys.as_types()).map_err(|err| export_err(&err))?;
+            serde_yaml::to_string(&yaml).map_err(|err| export_err(&err))?
+        }
+        #[cfg(feature = "serde")]
+        ValFormat::Toml => {
+            let toml = typed.to_toml(sys.as_types()).map_err(|err| export_err(&err))?;
+            toml::to_string_pretty(&toml).map_err(|err| export_err(&err))?
+        }
+    };
+    write_output(None, text.as_bytes())
+}
+
+fn encode(
+    libs: &str,
+    spec: &str,
+    input: Option<&str>,
+    output: Option<&str>,
+    format: ValFormat,
+) -> Result<(), Failure> {
+    let spec = type_spec(spec)?;
+    let sys = link(libs)?;
+    let data = read_input(input)?;
+    let text = String::from_utf8(data)
+        .map_err(|_| Failure::Fatal("input data are not a valid UTF-8 text".to_owned()))?;
+    let typed = typify(&sys, spec, &text, format)?;
+    let mut data = vec![];
+    sys.as_types()
+        .strict_write_type(&typed, &mut data)
+        .map_err(|err| Failure::Fatal(format!("unable to encode value. Details: {err}")))?;
+    write_output(output, &data)
+}
+
+fn typify(
+    sys: &SymbolicSys,
+    spec: TypeSpec,
+    text: &str,
+    format: ValFormat,
+) -> Result<TypedVal, Failure> {
+    let parse_err =
+        |err: &dyn Display| Failure::Fatal(format!("invalid input value. Details: {err}"));
+    let typed = match format {
+        ValFormat::Ston => {
+            let val = StrictVal::from_str(text.trim()).map_err(|err| parse_err(&err))?;
+            sys.typify(val, spec).map_err(|err| parse_err(&err))
+        }
+        #[cfg(feature = "serde")]
+        ValFormat::Json => {
+            let json = serde_json::from_str(text).map_err(|err| parse_err(&err))?;
+            sys.from_json(spec, &json).map_err(|err| parse_err(&err))
+        }
+        #[cfg(feature = "serde")]
+        ValFormat::Yaml => {
+            let yaml = serde_yaml::from_str(text).map_err(|err| parse_err(&err))?;
+            sys.from_yaml(spec, &yaml).map_err(|err| parse_err(&err))
+        }
+        #[cfg(feature = "serde")]
+        ValFormat::Toml => {
+            let toml = toml::from_str(text).map_err(|err| parse_err(&err))?;
+            sys.from_toml(spec, &toml).map_err(|err| parse_err(&err))
+        }
+    }?;
+    Ok(typed)
+}
+
+fn stl(dir: &str) -> Result<(), Failure> {
+    let write_err =
+        |err: io::Error| Failure::Fatal(format!("unable to write to `{dir}`. Details: {err}"));
+    let formats = [
+        StlFormat::Binary,
+        StlFormat::Source,
+        #[cfg(feature = "armor")]
+        StlFormat::Armored,
+    ];
+    let std = std_stl();
+    let st = strict_types_stl();
+    for format in formats {
+        std.serialize(format, Some(dir), STD_VERSION, Some(STD_HEADER)).map_err(write_err)?;
+        st.serialize(format, Some(dir), STRICT_TYPES_VERSION, Some(STRICT_TYPES_HEADER))
+            .map_err(write_err)?;
+    }
+
+    let sys = [st, std]
+        .into_iter()
+        .try_fold(SystemBuilder::new(), SystemBuilder::import)
+        .map_err(|err| Failure::Fatal(err.to_string()))?
+        .finalize()
+        .map_err(|_| Failure::Fatal("built-in libraries can't be linked".to_owned()))?;
+    let tree = sys.type_tree("StrictTypes.TypeLib").expect("built-in type is absent");
+    let vesper = format!("{VESPER_HEADER}\n\n{STRICT_TYPES_LIB} vesper lexicon=types\n\n{tree}");
+    fs::write(Path::new(dir).join("TypeLib.vesper"), vesper).map_err(write_err)
+}
+
+fn rustgen(gen: &RustGen, input: &str, output: Option<&str>) -> Result<(), Failure> {
+    let path = Path::new(input);
+    let code = if is_sys(path) {
+        gen.generate_sys(&load_sys(path)?)
+    } else {
+        let (lib, _) = load_lib(path)?;
+        let lib = lib
+            .to_symbolic()
+            .map_err(|err| Failure::Fatal(format!("invalid library `{input}`. Details: {err}")))?;
+        gen.generate_lib(&lib)
+    }
+    .map_err(|err| Failure::Fatal(format!("unable to generate Rust code. Details: {err}")))?;
+    write_output(output, code.as_bytes())
+}
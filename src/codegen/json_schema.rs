@@ -0,0 +1,376 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Export of strict types as [JSON Schema] (draft 2020-12), describing JSON representation of
+//! strict values:
+//! - unit type is represented as `null`;
+//! - integers and floats are JSON numbers, except numbers wider than 64 bits, which are decimal
+//!   strings;
+//! - enums are strings with the variant name, except boolean enums (having only `false` and `true`
+//!   variants), which are JSON booleans;
+//! - unions are JSON objects with a single property, named after the variant and containing the
+//!   variant value. Variants of the unit type are represented just by a string with the variant
+//!   name;
+//! - optionals are represented by `null` or the inner value;
+//! - newtypes are represented by the wrapped value;
+//! - structures are JSON objects, and tuples are JSON arrays;
+//! - ASCII and unicode strings are JSON strings; byte strings are strings containing either hex- or
+//!   base64-encoded data (see [`BytesEncoding`]);
+//! - arrays, lists and sets are JSON arrays;
+//! - maps with string-like keys are JSON objects; other maps are arrays of two-item arrays, each
+//!   containing a key and a value.
+//!
+//! Named types are put into `$defs` under their fully qualified names.
+//!
+//! [JSON Schema]: https://json-schema.org/draft/2020-12
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use encoding::{NumCls, Primitive, Sizing, VariantName};
+use serde_json::{json, Map, Value};
+
+use crate::ast::{EnumVariants, NamedFields, UnionVariants, UnnamedFields};
+use crate::typesys::TypeTree;
+use crate::{SemId, SymbolicSys, Ty, TypeRef};
+
+pub const JSON_SCHEMA_URI: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Encoding of byte strings and byte arrays in JSON.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default, Display)]
+#[display(lowercase)]
+pub enum BytesEncoding {
+    /// Hexadecimal encoding.
+    #[default]
+    Hex,
+    /// Standard base64 encoding with padding.
+    Base64,
+}
+
+impl BytesEncoding {
+    /// Length of a string encoding given number of bytes.
+    pub fn encoded_len(self, len: u64) -> u64 {
+        match self {
+            BytesEncoding::Hex => len.saturating_mul(2),
+            BytesEncoding::Base64 => (len.saturating_add(2) / 3).saturating_mul(4),
+        }
+    }
+}
+
+/// Generator of JSON Schema for strict types.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct JsonSchemaGen {
+    bytes: BytesEncoding,
+}
+
+impl JsonSchemaGen {
+    pub fn new() -> Self { Self::default() }
+
+    /// Sets encoding used for byte strings and byte arrays. Values matching the schema are
+    /// produced and consumed with the same encoding by [`TypedVal::to_json_with`] and
+    /// [`TypeSystem::from_json_with`].
+    ///
+    /// [`TypedVal::to_json_with`]: crate::typify::TypedVal::to_json_with
+    /// [`TypeSystem::from_json_with`]: crate::TypeSystem::from_json_with
+    pub fn bytes_encoding(mut self, encoding: BytesEncoding) -> Self {
+        self.bytes = encoding;
+        self
+    }
+
+    /// Generates JSON Schema for the root type of the type tree.
+    pub fn generate(&self, tree: &TypeTree) -> Value {
+        let mut writer = SchemaWriter {
+            bytes: self.bytes,
+            sys: tree.sys(),
+            defs: empty!(),
+        };
+        let root = writer.reference(tree.sem_id());
+
+        let mut schema = Map::new();
+        schema.insert(s!("$schema"), Value::from(JSON_SCHEMA_URI));
+        if let Some(fqn) = tree.sys().lookup(tree.sem_id()) {
+            schema.insert(s!("title"), Value::from(fqn.to_string()));
+        }
+        if let Value::Object(root) = root {
+            schema.extend(root);
+        }
+        if !writer.defs.is_empty() {
+            let defs = writer.defs.into_iter().collect::<Map<_, _>>();
+            schema.insert(s!("$defs"), Value::Object(defs));
+        }
+        Value::Object(schema)
+    }
+}
+
+impl TypeTree<'_> {
+    /// Generates JSON Schema for the root type of the type tree using default settings.
+    pub fn to_json_schema(&self) -> Value { JsonSchemaGen::new().generate(self) }
+}
+
+struct SchemaWriter<'sys> {
+    bytes: BytesEncoding,
+    sys: &'sys SymbolicSys,
+    defs: BTreeMap<String, Value>,
+}
+
+impl<'sys> SchemaWriter<'sys> {
+    fn ty(&self, sem_id: SemId) -> &'sys Ty<SemId> {
+        self.sys.get(sem_id).expect("inconsistent type tree")
+    }
+
+    /// Returns reference to a named type, adding it to `$defs`, or an inline schema for an unnamed
+    /// type.
+    fn reference(&mut self, sem_id: SemId) -> Value {
+        let Some(fqn) = self.sys.lookup(sem_id) else {
+            return self.schema(sem_id);
+        };
+        let name = fqn.to_string();
+        if !self.defs.contains_key(&name) {
+            // Reserving the name before writing the definition
+            self.defs.insert(name.clone(), Value::Null);
+            let schema = self.schema(sem_id);
+            self.defs.insert(name.clone(), schema);
+        }
+        json!({ "$ref": format!("#/$defs/{name}") })
+    }
+
+    fn schema(&mut self, sem_id: SemId) -> Value {
+        let ty = self.ty(sem_id);
+        match ty {
+            Ty::Primitive(prim) => primitive(*prim),
+            Ty::UnicodeChar => json!({ "type": "string", "minLength": 1, "maxLength": 1 }),
+            Ty::Enum(variants) if ty.is_char_enum() => {
+                let chars = variants.iter().map(|var| (var.tag as char).to_string());
+                json!({ "type": "string", "enum": chars.collect::<Vec<_>>() })
+            }
+            Ty::Enum(variants) => enumeration(variants),
+            Ty::Union(_) if ty.is_option() => {
+                let some = *ty.as_some().expect("checked for option");
+                json!({ "oneOf": [{ "type": "null" }, self.reference(some)] })
+            }
+            Ty::Union(variants) => self.union(variants),
+            Ty::Tuple(fields) if fields.len() == 1 => self.reference(fields[0]),
+            Ty::Tuple(fields) if self.sys.as_types().is_rstring(fields).unwrap_or_default() => {
+                self.rstring(fields)
+            }
+            Ty::Tuple(fields) => self.tuple(fields),
+            Ty::Struct(fields) => self.structure(fields),
+            Ty::Array(id, len) => {
+                let sizing = Sizing::fixed(*len as u64);
+                self.string(*id, sizing).unwrap_or_else(|| {
+                    let mut schema = json!({ "type": "array", "items": self.reference(*id) });
+                    with_sizing(&mut schema, "Items", sizing);
+                    schema
+                })
+            }
+            Ty::List(id, sizing) => self.string(*id, *sizing).unwrap_or_else(|| {
+                let mut schema = json!({ "type": "array", "items": self.reference(*id) });
+                with_sizing(&mut schema, "Items", *sizing);
+                schema
+            }),
+            Ty::Set(id, sizing) => {
+                let mut schema = json!({
+                    "type": "array",
+                    "items": self.reference(*id),
+                    "uniqueItems": true
+                });
+                with_sizing(&mut schema, "Items", *sizing);
+                schema
+            }
+            Ty::Map(key, val, sizing) if self.sys.as_types().is_string_like(*key) => {
+                let mut schema = json!({
+                    "type": "object",
+                    "propertyNames": self.reference(*key),
+                    "additionalProperties": self.reference(*val)
+                });
+                with_sizing(&mut schema, "Properties", *sizing);
+                schema
+            }
+            Ty::Map(key, val, sizing) => {
+                let pair = json!({
+                    "type": "array",
+                    "prefixItems": [self.reference(*key), self.reference(*val)],
+                    "items": false,
+                    "minItems": 2,
+                    "maxItems": 2
+                });
+                let mut schema = json!({ "type": "array", "items": pair });
+                with_sizing(&mut schema, "Items", *sizing);
+                schema
+            }
+        }
+    }
+
+    /// Returns schema for collections of bytes or characters, which are represented with strings.
+    fn string(&self, item: SemId, sizing: Sizing) -> Option<Value> {
+        let mut schema = json!({ "type": "string" });
+        if item.is_byte() {
+            let (encoding, pattern) = match self.bytes {
+                BytesEncoding::Hex => ("base16", "^([0-9a-fA-F]{2})*$"),
+                BytesEncoding::Base64 => {
+                    ("base64", "^([A-Za-z0-9+/]{4})*([A-Za-z0-9+/]{2}==|[A-Za-z0-9+/]{3}=)?$")
+                }
+            };
+            schema["contentEncoding"] = Value::from(encoding);
+            schema["pattern"] = Value::from(pattern);
+            let sizing =
+                Sizing::new(self.bytes.encoded_len(sizing.min), self.bytes.encoded_len(sizing.max));
+            with_sizing(&mut schema, "Length", sizing);
+            return Some(schema);
+        }
+        if item.is_unicode_char() {
+            with_sizing(&mut schema, "Length", sizing);
+            return Some(schema);
+        }
+        if let Ty::Enum(variants) = self.ty(item) {
+            if self.ty(item).is_char_enum() {
+                schema["pattern"] = Value::from(format!("^{}*$", char_class(variants)));
+                with_sizing(&mut schema, "Length", sizing);
+                return Some(schema);
+            }
+        }
+        None
+    }
+
+    fn rstring(&self, fields: &UnnamedFields<SemId>) -> Value {
+        let (rest, sizing) =
+            self.sys.as_types().rstring_sizing(fields).ok().flatten().expect("checked for rstring");
+        let (Ty::Enum(first), Ty::Enum(rest)) = (self.ty(fields[0]), self.ty(rest)) else {
+            unreachable!("checked for rstring");
+        };
+        let mut schema = json!({
+            "type": "string",
+            "pattern": format!("^{}{}*$", char_class(first), char_class(rest))
+        });
+        with_sizing(&mut schema, "Length", sizing);
+        schema
+    }
+
+    fn union(&mut self, variants: &UnionVariants<SemId>) -> Value {
+        let mut one_of = vec![];
+        for (var, id) in variants {
+            let name = var.name.to_string();
+            if self.ty(*id) == &Ty::UNIT {
+                one_of.push(json!({ "const": name }));
+                continue;
+            }
+            one_of.push(json!({
+                "type": "object",
+                "properties": { name.clone(): self.reference(*id) },
+                "required": [name],
+                "additionalProperties": false
+            }));
+        }
+        json!({ "oneOf": one_of })
+    }
+
+    fn tuple(&mut self, fields: &UnnamedFields<SemId>) -> Value {
+        let items = fields.iter().map(|id| self.reference(*id)).collect::<Vec<_>>();
+        json!({
+            "type": "array",
+            "prefixItems": items,
+            "items": false,
+            "minItems": fields.len(),
+            "maxItems": fields.len()
+        })
+    }
+
+    fn structure(&mut self, fields: &NamedFields<SemId>) -> Value {
+        let mut properties = Map::new();
+        for field in fields {
+            properties.insert(field.name.to_string(), self.reference(field.ty));
+        }
+        let required = fields.iter().map(|field| field.name.to_string()).collect::<Vec<_>>();
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false
+        })
+    }
+}
+
+fn primitive(prim: Primitive) -> Value {
+    if prim == Primitive::UNIT {
+        return json!({ "type": "null" });
+    }
+    if prim == Primitive::BYTE {
+        return json!({ "type": "integer", "minimum": 0, "maximum": u8::MAX });
+    }
+    let bits = prim.byte_size() as u32 * 8;
+    match prim.info().ty {
+        NumCls::Float if bits <= 64 => {
+            json!({ "oneOf": [{ "type": "number" }, { "enum": ["NaN", "+Inf", "-Inf"] }] })
+        }
+        NumCls::Unsigned if bits <= 64 => {
+            json!({ "type": "integer", "minimum": 0, "maximum": u64::MAX >> (64 - bits) })
+        }
+        NumCls::NonZero if bits <= 64 => {
+            json!({ "type": "integer", "minimum": 1, "maximum": u64::MAX >> (64 - bits) })
+        }
+        NumCls::Signed if bits <= 64 => json!({
+            "type": "integer",
+            "minimum": i64::MIN >> (64 - bits),
+            "maximum": i64::MAX >> (64 - bits)
+        }),
+        // Numbers which can't be represented by JSON numbers without the loss of precision
+        NumCls::Float => json!({ "type": "string" }),
+        NumCls::Unsigned | NumCls::NonZero => json!({ "type": "string", "pattern": "^[0-9]+$" }),
+        NumCls::Signed => json!({ "type": "string", "pattern": "^-?[0-9]+$" }),
+    }
+}
+
+fn enumeration(variants: &EnumVariants) -> Value {
+    if variants.len() == 2
+        && variants.name_by_tag(0).map(VariantName::as_str) == Some("false")
+        && variants.name_by_tag(1).map(VariantName::as_str) == Some("true")
+    {
+        return json!({ "type": "boolean" });
+    }
+    let names = variants.iter().map(|var| var.name.to_string()).collect::<Vec<_>>();
+    json!({ "type": "string", "enum": names })
+}
+
+/// Constructs regular expression character class matching characters of a character enum.
+fn char_class(variants: &EnumVariants) -> String {
+    let mut class = s!("[");
+    for var in variants {
+        let c = var.tag as char;
+        match c {
+            '\\' | ']' | '[' | '^' | '-' => {
+                class.push('\\');
+                class.push(c);
+            }
+            c if c.is_ascii_graphic() || c == ' ' => class.push(c),
+            c => write!(class, "\\u{:04x}", c as u32).expect("writing to string"),
+        }
+    }
+    class.push(']');
+    class
+}
+
+fn with_sizing(schema: &mut Value, suffix: &str, sizing: Sizing) {
+    if sizing.min > 0 {
+        schema[format!("min{suffix}")] = Value::from(sizing.min);
+    }
+    schema[format!("max{suffix}")] = Value::from(sizing.max);
+}
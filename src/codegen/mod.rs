@@ -20,22 +20,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use strict_types::{parse_args, stl};
+//! Generation of source code in other languages from strict type definitions.
 
-fn main() {
-    let (format, dir) = parse_args();
-    stl::std_stl()
-        .serialize(
-            format,
-            dir,
-            "0.1.0",
-            Some(
-                "
-  Description: Strict types standard library
-  Author: Dr Maxim Orlovsky <orlovsky@ubideco.org>
-  Copyright (C) 2023-2024 UBIDECO Institute. All rights reserved.
-  License: Apache-2.0",
-            ),
-        )
-        .expect("unable to write to the file");
-}
+mod rust;
+#[cfg(feature = "serde")]
+mod json_schema;
+
+#[cfg(feature = "serde")]
+pub use json_schema::{BytesEncoding, JsonSchemaGen, JSON_SCHEMA_URI};
+pub use rust::{RustGen, RustGenError};
@@ -0,0 +1,1046 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rust code generator producing type definitions with strict encoding derives, which transpile
+//! back into the same strict types.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Write};
+
+use encoding::{LibName, Primitive, Sizing, TypeName};
+
+use crate::ast::{NamedFields, UnnamedFields};
+use crate::typesys::{Resolved, SymbolicSys, TypeFqn, TypeSource, UnknownType};
+use crate::{Dependency, SemId, SymbolicLib, Ty, TypeRef};
+
+const U8: u64 = u8::MAX as u64;
+const U16: u64 = u16::MAX as u64;
+const U24: u64 = 0xFFFFFF;
+const U32: u64 = u32::MAX as u64;
+const U64: u64 = u64::MAX;
+
+const KEYWORDS: [&str; 52] = [
+    "abstract", "alloc", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "core", "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen",
+    "if", "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv",
+    "pub", "ref", "return", "self", "static", "std", "struct", "super", "trait", "true", "try",
+    "type", "typeof", "unsafe", "unsized", "use", "virtual", "where",
+];
+
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum RustGenError {
+    #[from]
+    #[display(inner)]
+    UnknownType(UnknownType),
+
+    /// library `{0}` is used by other libraries, but is not known to the type system.
+    UnknownLib(LibName),
+
+    /// type `{0}` uses `{1}`, which has no Rust representation supported by strict encoding
+    /// derive macros.
+    Unrepresentable(TypeFqn, String),
+
+    /// type `{0}` uses `{1}` for which a dumb value can't be constructed, since it requires
+    /// several distinct items.
+    NoDumb(TypeFqn, String),
+}
+
+/// Rust code generator.
+///
+/// Produces Rust source with type definitions using strict encoding derive macros. Transpiling
+/// the generated types with [`crate::LibBuilder`] (the generated code provides a function doing
+/// that) results in the same type library with the same id.
+///
+/// Types from other libraries are referenced as `super::<lib_name>::<TypeName>`, i.e. they are
+/// expected to be generated into sibling modules named after the library in snake case, unless a
+/// Rust path for the library is provided with [`RustGen::extern_lib`].
+///
+/// The generated code requires `strict_encoding` macros to be imported into the crate root with
+/// `#[macro_use] extern crate strict_encoding;`.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct RustGen {
+    extern_libs: BTreeMap<LibName, String>,
+}
+
+impl RustGen {
+    pub fn new() -> Self { Self::default() }
+
+    /// Uses existing Rust types located at `path` for the types from library `lib`, instead of
+    /// generating them (or expecting them to be generated in a sibling module).
+    pub fn extern_lib(mut self, lib: impl Into<LibName>, path: impl ToString) -> Self {
+        self.extern_libs.insert(lib.into(), path.to_string());
+        self
+    }
+
+    /// Generates Rust code for all types of the library.
+    pub fn generate_lib(&self, lib: &SymbolicLib) -> Result<String, RustGenError> {
+        let types = lib.types().iter().map(|(name, ty)| (name.clone(), ty)).collect();
+        let module = Module::new(self, lib, lib.name().clone(), types);
+        let body = module.generate(lib.dependencies().iter().cloned().collect())?;
+
+        let mut out = String::new();
+        writeln!(out, "// Rust types for the `{}` strict type library.", lib.name()).ok();
+        if let Ok(compiled) = lib.clone().compile() {
+            writeln!(out, "//\n// Library id: {}", compiled.id()).ok();
+        }
+        out.push_str(HEADER);
+        out.push_str(&body);
+        Ok(out)
+    }
+
+    /// Generates Rust code for all named types of the type system, putting types from each
+    /// library into a separate module.
+    pub fn generate_sys(&self, sys: &SymbolicSys) -> Result<String, RustGenError> {
+        let mut libs = BTreeMap::<LibName, BTreeMap<TypeName, &Ty<SemId>>>::new();
+        for sym in sys.as_symbols().symbols() {
+            let Some(fqn) = &sym.fqn else { continue };
+            if self.extern_libs.contains_key(&fqn.lib) {
+                continue;
+            }
+            let ty = sys.as_types().get(sym.id).ok_or(UnknownType(sym.id))?;
+            libs.entry(fqn.lib.clone()).or_default().insert(fqn.name.clone(), ty);
+        }
+
+        let mut out = String::new();
+        writeln!(out, "// Rust types for the strict type system {}.", sys.id()).ok();
+        out.push_str(HEADER);
+        for (lib, types) in libs {
+            let mut used = BTreeSet::new();
+            for ty in types.values() {
+                used_libs(sys, ty, &mut used)?;
+            }
+            used.remove(&lib);
+            let dependencies = used
+                .into_iter()
+                .map(|name| {
+                    sys.as_symbols()
+                        .libs()
+                        .iter()
+                        .find(|dep| dep.name == name)
+                        .cloned()
+                        .ok_or(RustGenError::UnknownLib(name))
+                })
+                .collect::<Result<_, _>>()?;
+
+            let module = Module::new(self, sys, lib.clone(), types);
+            let body = module.generate(dependencies)?;
+            writeln!(out, "\npub mod {} {{", module_name(&lib)).ok();
+            for line in body.trim_start_matches('\n').lines() {
+                if line.is_empty() {
+                    out.push('\n');
+                } else {
+                    writeln!(out, "    {line}").ok();
+                }
+            }
+            out.push_str("}\n");
+        }
+        Ok(out)
+    }
+}
+
+const HEADER: &str = "//
+// Generated by the strict types Rust code generator; do not edit manually.
+//
+// The code requires `strict_encoding` macros to be imported into the crate root with
+// `#[macro_use] extern crate strict_encoding;`. Dumb value expressions in field attributes
+// trigger false `unused_braces` warnings, which should be allowed.
+";
+
+fn used_libs(
+    sys: &SymbolicSys,
+    ty: &Ty<SemId>,
+    libs: &mut BTreeSet<LibName>,
+) -> Result<(), RustGenError> {
+    for (r, _) in ty.type_refs() {
+        match sys.resolve_ref(r)? {
+            Resolved::Named(fqn) => {
+                libs.insert(fqn.lib);
+            }
+            Resolved::Inline(ty) => used_libs(sys, ty, libs)?,
+        }
+    }
+    Ok(())
+}
+
+/// Way of constructing a dumb value for a type.
+enum DumbValue {
+    /// Type implements `Default`, and thus `StrictDumb`.
+    Default,
+    /// Type implements `StrictDumb` only.
+    Derived,
+    /// Dumb value must be constructed with the expression.
+    Expr(String),
+}
+
+impl DumbValue {
+    fn expr(self) -> String {
+        match self {
+            DumbValue::Default | DumbValue::Derived => s!("strict_dumb!()"),
+            DumbValue::Expr(expr) => expr,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Leaf {
+    Prim(Primitive),
+    Char,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Collection {
+    String,
+    Blob,
+    Vec,
+    Set,
+    Map,
+}
+
+impl Collection {
+    fn alias_suffix(self) -> &'static str {
+        match self {
+            Collection::String => "String",
+            Collection::Blob => "Blob",
+            Collection::Vec => "Vec",
+            Collection::Set => "OrdSet",
+            Collection::Map => "OrdMap",
+        }
+    }
+}
+
+struct Module<'a, Ref: TypeRef, S: TypeSource<Ref>> {
+    gen: &'a RustGen,
+    src: &'a S,
+    lib: LibName,
+    types: BTreeMap<TypeName, &'a Ty<Ref>>,
+    local: BTreeSet<String>,
+    imports: BTreeMap<&'static str, BTreeSet<&'static str>>,
+}
+
+impl<'a, Ref: TypeRef + Display, S: TypeSource<Ref>> Module<'a, Ref, S> {
+    fn new(
+        gen: &'a RustGen,
+        src: &'a S,
+        lib: LibName,
+        types: BTreeMap<TypeName, &'a Ty<Ref>>,
+    ) -> Self {
+        let local = types.keys().map(|name| type_ident(name).0).collect();
+        Module {
+            gen,
+            src,
+            lib,
+            types,
+            local,
+            imports: empty!(),
+        }
+    }
+
+    fn lib_const(&self) -> String {
+        format!("LIB_NAME_{}", snake_case(self.lib.as_str()).to_uppercase())
+    }
+
+    fn generate(mut self, dependencies: Vec<Dependency>) -> Result<String, RustGenError> {
+        for item in ["StrictDecode", "StrictDumb", "StrictEncode", "StrictType"] {
+            self.import("strict_encoding", item);
+        }
+
+        let mut body = String::new();
+        let types = self.types.clone();
+        for (name, ty) in &types {
+            body.push('\n');
+            self.write_type(&mut body, name, ty)?;
+        }
+
+        let lib_const = self.lib_const();
+        writeln!(
+            body,
+            "\n/// Compiles `{}` type library from the types defined in this module.",
+            self.lib
+        )
+        .ok();
+        writeln!(
+            body,
+            "pub fn {}_stl() -> strict_types::TypeLib {{",
+            snake_case(self.lib.as_str())
+        )
+        .ok();
+        if dependencies.is_empty() {
+            writeln!(body, "    strict_types::LibBuilder::new({lib_const}, None)").ok();
+        } else {
+            writeln!(body, "    strict_types::LibBuilder::new({lib_const}, [").ok();
+            for dep in dependencies {
+                writeln!(
+                    body,
+                    "        strict_types::Dependency::with(\"{}\".parse().expect(\"valid library \
+                     id\"), \"{}\".into()),",
+                    dep.id, dep.name
+                )
+                .ok();
+            }
+            body.push_str("    ])\n");
+        }
+        for name in types.keys() {
+            writeln!(body, "    .transpile::<{}>()", type_ident(name).0).ok();
+        }
+        body.push_str("    .compile()\n");
+        writeln!(body, "    .expect(\"invalid `{}` type library\")", self.lib).ok();
+        body.push_str("}\n");
+
+        let mut out = s!("\n");
+        for (module, items) in &self.imports {
+            let items = items.iter().copied().collect::<Vec<_>>();
+            if items.len() == 1 {
+                writeln!(out, "use {module}::{};", items[0]).ok();
+            } else {
+                writeln!(out, "use {module}::{{{}}};", items.join(", ")).ok();
+            }
+        }
+        writeln!(out, "\npub const {lib_const}: &str = \"{}\";", self.lib).ok();
+        out.push_str(&body);
+        Ok(out)
+    }
+
+    fn import(&mut self, module: &'static str, item: &'static str) -> String {
+        if self.local.contains(item) {
+            return format!("{module}::{item}");
+        }
+        self.imports.entry(module).or_default().insert(item);
+        item.to_owned()
+    }
+
+    fn prelude(&self, item: &'static str, path: &'static str) -> String {
+        if self.local.contains(item) {
+            format!("{path}::{item}")
+        } else {
+            item.to_owned()
+        }
+    }
+
+    fn type_path(&self, fqn: &TypeFqn) -> String {
+        let ident = type_ident(&fqn.name).0;
+        if fqn.lib == self.lib {
+            ident
+        } else if let Some(path) = self.gen.extern_libs.get(&fqn.lib) {
+            format!("{path}::{ident}")
+        } else {
+            format!("super::{}::{ident}", module_name(&fqn.lib))
+        }
+    }
+
+    fn unrepresentable(ctx: &TypeFqn, ty: &impl Display) -> RustGenError {
+        RustGenError::Unrepresentable(ctx.clone(), ty.to_string())
+    }
+
+    fn derives(&self, ty: &'a Ty<Ref>) -> Result<&'static str, RustGenError> {
+        if self.has_float(ty, &mut empty!())? {
+            Ok("#[derive(Clone, PartialEq, Debug)]")
+        } else {
+            Ok("#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]")
+        }
+    }
+
+    fn has_float(
+        &self,
+        ty: &'a Ty<Ref>,
+        visited: &mut BTreeSet<TypeFqn>,
+    ) -> Result<bool, RustGenError> {
+        if let Ty::Primitive(prim) = ty {
+            return Ok(matches!(
+                *prim,
+                Primitive::F16
+                    | Primitive::F16B
+                    | Primitive::F32
+                    | Primitive::F64
+                    | Primitive::F80
+                    | Primitive::F128
+                    | Primitive::F256
+            ));
+        }
+        for (r, _) in ty.type_refs() {
+            let found = match self.src.resolve_ref(r)? {
+                Resolved::Named(fqn) if visited.contains(&fqn) => false,
+                Resolved::Named(fqn) => match self.src.named_ty(&fqn) {
+                    Some(ty) => {
+                        visited.insert(fqn);
+                        self.has_float(ty, visited)?
+                    }
+                    None => false,
+                },
+                Resolved::Inline(ty) => self.has_float(ty, visited)?,
+            };
+            if found {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn prim_expr(&mut self, prim: Primitive) -> Option<String> {
+        let (module, item) = match prim {
+            Primitive::UNIT => return Some(s!("()")),
+            Primitive::U8 => return Some(s!("u8")),
+            Primitive::U16 => return Some(s!("u16")),
+            Primitive::U32 => return Some(s!("u32")),
+            Primitive::U64 => return Some(s!("u64")),
+            Primitive::U128 => return Some(s!("u128")),
+            Primitive::I8 => return Some(s!("i8")),
+            Primitive::I16 => return Some(s!("i16")),
+            Primitive::I32 => return Some(s!("i32")),
+            Primitive::I64 => return Some(s!("i64")),
+            Primitive::I128 => return Some(s!("i128")),
+            Primitive::BYTE => ("strict_encoding", "Byte"),
+            Primitive::U24 => ("amplify::num", "u24"),
+            Primitive::U40 => ("amplify::num", "u40"),
+            Primitive::U48 => ("amplify::num", "u48"),
+            Primitive::U56 => ("amplify::num", "u56"),
+            Primitive::U256 => ("amplify::num", "u256"),
+            Primitive::U512 => ("amplify::num", "u512"),
+            Primitive::U1024 => ("amplify::num", "u1024"),
+            Primitive::I256 => ("amplify::num", "i256"),
+            Primitive::I512 => ("amplify::num", "i512"),
+            Primitive::I1024 => ("amplify::num", "i1024"),
+            Primitive::N8 => ("core::num", "NonZeroU8"),
+            Primitive::N16 => ("core::num", "NonZeroU16"),
+            Primitive::F16 => return Some(self.float("Half")),
+            Primitive::F32 => return Some(self.float("Single")),
+            Primitive::F64 => return Some(self.float("Double")),
+            Primitive::F80 => return Some(self.float("X87DoubleExtended")),
+            Primitive::F128 => return Some(self.float("Quad")),
+            Primitive::F256 => return Some(self.float("Oct")),
+            _ => return None,
+        };
+        Some(self.import(module, item))
+    }
+
+    fn float(&mut self, name: &str) -> String {
+        format!("{}::{name}", self.import("amplify::num::apfloat", "ieee"))
+    }
+
+    fn size(&mut self, size: u64) -> String {
+        let item = match size {
+            U8 => "U8",
+            U16 => "U16",
+            U24 => "U24",
+            U32 => "U32",
+            U64 => "U64",
+            _ => return size.to_string(),
+        };
+        self.import("amplify::confinement", item)
+    }
+
+    fn collection(&mut self, kind: Collection, items: &[String], sizing: Sizing) -> String {
+        let suffix = kind.alias_suffix();
+        let params = items.join(", ");
+        let alias = match (sizing.min, sizing.max) {
+            (0, U8) => Some("Tiny"),
+            (0, U16) => Some("Small"),
+            (0, U24) => Some("Medium"),
+            (0, U32) => Some("Large"),
+            (1, _) => Some("NonEmpty"),
+            _ => None,
+        };
+        if let Some(prefix) = alias {
+            let name = format!("{prefix}{suffix}");
+            let name: &'static str = ALIASES
+                .iter()
+                .find(|alias| **alias == name)
+                .expect("all collection aliases are listed");
+            let alias = self.import("amplify::confinement", name);
+            let mut params = params;
+            if prefix == "NonEmpty" && sizing.max != U64 {
+                if !params.is_empty() {
+                    params.push_str(", ");
+                }
+                params.push_str(&self.size(sizing.max));
+            }
+            return if params.is_empty() { alias } else { format!("{alias}<{params}>") };
+        }
+
+        let inner = match kind {
+            Collection::String => s!("String"),
+            Collection::Blob => s!("Vec<u8>"),
+            Collection::Vec => format!("Vec<{params}>"),
+            Collection::Set => {
+                format!("{}<{params}>", self.import("::std::collections", "BTreeSet"))
+            }
+            Collection::Map => {
+                format!("{}<{params}>", self.import("::std::collections", "BTreeMap"))
+            }
+        };
+        let confined = self.import("amplify::confinement", "Confined");
+        let max = self.size(sizing.max);
+        format!("{confined}<{inner}, {}, {max}>", sizing.min)
+    }
+
+    fn ty_expr(&mut self, ctx: &TypeFqn, r: &'a Ref) -> Result<String, RustGenError> {
+        match self.src.resolve_ref(r)? {
+            Resolved::Named(fqn) => Ok(self.type_path(&fqn)),
+            Resolved::Inline(ty) => self.inline_expr(ctx, ty),
+        }
+    }
+
+    fn inline_prim(&self, r: &'a Ref) -> Result<Option<Leaf>, RustGenError> {
+        Ok(match self.src.resolve_ref(r)? {
+            Resolved::Inline(Ty::Primitive(prim)) => Some(Leaf::Prim(*prim)),
+            Resolved::Inline(Ty::UnicodeChar) => Some(Leaf::Char),
+            _ => None,
+        })
+    }
+
+    /// Detects restricted string type, returning its first and rest char types together with the
+    /// string size limits.
+    fn rstring(
+        &self,
+        fields: &'a UnnamedFields<Ref>,
+    ) -> Result<Option<(TypeFqn, TypeFqn, Sizing)>, RustGenError> {
+        if fields.len() != 2 {
+            return Ok(None);
+        }
+        let Resolved::Named(first) = self.src.resolve_ref(&fields[0])? else {
+            return Ok(None);
+        };
+        let Resolved::Inline(Ty::List(rest, sizing)) = self.src.resolve_ref(&fields[1])? else {
+            return Ok(None);
+        };
+        let Resolved::Named(rest) = self.src.resolve_ref(rest)? else {
+            return Ok(None);
+        };
+        Ok(Some((first, rest, Sizing::new(sizing.min + 1, sizing.max.saturating_add(1)))))
+    }
+
+    fn inline_expr(&mut self, ctx: &TypeFqn, ty: &'a Ty<Ref>) -> Result<String, RustGenError> {
+        Ok(match ty {
+            Ty::Primitive(prim) => {
+                self.prim_expr(*prim).ok_or_else(|| Self::unrepresentable(ctx, ty))?
+            }
+            Ty::Union(_) => {
+                let some = ty
+                    .as_some()
+                    .map(|some| self.src.resolve_ref(some))
+                    .transpose()?
+                    .and_then(|some| match some {
+                        Resolved::Inline(Ty::Tuple(fields)) if fields.len() == 1 => {
+                            Some(&fields[0])
+                        }
+                        _ => None,
+                    })
+                    .ok_or_else(|| Self::unrepresentable(ctx, ty))?;
+                format!(
+                    "{}<{}>",
+                    self.prelude("Option", "::core::option"),
+                    self.ty_expr(ctx, some)?
+                )
+            }
+            Ty::Tuple(fields) => {
+                if let Some((first, rest, sizing)) = self.rstring(fields)? {
+                    let rstring = self.import("strict_encoding", "RString");
+                    let min = self.size(sizing.min);
+                    let max = self.size(sizing.max);
+                    format!(
+                        "{rstring}<{}, {}, {min}, {max}>",
+                        self.type_path(&first),
+                        self.type_path(&rest)
+                    )
+                } else if (2..=3).contains(&fields.len()) {
+                    let items = fields
+                        .iter()
+                        .map(|r| self.ty_expr(ctx, r))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    format!("({})", items.join(", "))
+                } else {
+                    return Err(Self::unrepresentable(ctx, ty));
+                }
+            }
+            Ty::Array(r, len) => match self.inline_prim(r)? {
+                Some(Leaf::Prim(Primitive::BYTE)) => format!("[u8; {len}]"),
+                Some(Leaf::Prim(Primitive::U8) | Leaf::Char) => {
+                    return Err(Self::unrepresentable(ctx, ty))
+                }
+                _ => format!("[{}; {len}]", self.ty_expr(ctx, r)?),
+            },
+            Ty::List(r, sizing) => match self.inline_prim(r)? {
+                Some(Leaf::Char) => self.collection(Collection::String, &[], *sizing),
+                Some(Leaf::Prim(Primitive::BYTE)) => {
+                    self.collection(Collection::Blob, &[], *sizing)
+                }
+                Some(Leaf::Prim(Primitive::U8)) => return Err(Self::unrepresentable(ctx, ty)),
+                _ => {
+                    let item = self.ty_expr(ctx, r)?;
+                    self.collection(Collection::Vec, &[item], *sizing)
+                }
+            },
+            Ty::Set(r, sizing) => {
+                let item = self.ty_expr(ctx, r)?;
+                self.collection(Collection::Set, &[item], *sizing)
+            }
+            Ty::Map(k, v, sizing) => {
+                let key = self.ty_expr(ctx, k)?;
+                let val = self.ty_expr(ctx, v)?;
+                self.collection(Collection::Map, &[key, val], *sizing)
+            }
+            Ty::UnicodeChar | Ty::Enum(_) | Ty::Struct(_) => {
+                return Err(Self::unrepresentable(ctx, ty))
+            }
+        })
+    }
+
+    fn dumb(&mut self, ctx: &TypeFqn, r: &'a Ref) -> Result<DumbValue, RustGenError> {
+        match self.src.resolve_ref(r)? {
+            Resolved::Named(_) => Ok(DumbValue::Derived),
+            Resolved::Inline(ty) => self.inline_dumb(ctx, ty),
+        }
+    }
+
+    fn inline_dumb(&mut self, ctx: &TypeFqn, ty: &'a Ty<Ref>) -> Result<DumbValue, RustGenError> {
+        Ok(match ty {
+            Ty::Primitive(prim @ (Primitive::N8 | Primitive::N16)) => {
+                let ty = self.prim_expr(*prim).expect("non-zero primitives are supported");
+                DumbValue::Expr(format!("{ty}::MIN"))
+            }
+            Ty::Tuple(fields) if self.rstring(fields)?.is_some() => DumbValue::Derived,
+            Ty::Tuple(fields) => {
+                let mut items = Vec::with_capacity(fields.len());
+                let mut default = true;
+                for r in fields.iter() {
+                    let dumb = self.dumb(ctx, r)?;
+                    default &= matches!(dumb, DumbValue::Default);
+                    items.push(dumb.expr());
+                }
+                if default {
+                    DumbValue::Default
+                } else {
+                    DumbValue::Expr(format!("({})", items.join(", ")))
+                }
+            }
+            Ty::Array(r, len) => match self.dumb(ctx, r)? {
+                DumbValue::Default if *len <= 32 => DumbValue::Default,
+                dumb => DumbValue::Expr(format!("[{}; {len}]", dumb.expr())),
+            },
+            Ty::List(_, sizing) | Ty::Set(_, sizing) | Ty::Map(_, _, sizing) if sizing.min == 0 => {
+                DumbValue::Default
+            }
+            Ty::List(r, sizing) if self.inline_prim(r)? == Some(Leaf::Char) => {
+                let confined = self.import("amplify::confinement", "Confined");
+                DumbValue::Expr(format!(
+                    "{confined}::try_from(\" \".repeat({})).expect(\"dumb value\")",
+                    sizing.min
+                ))
+            }
+            Ty::Set(..) | Ty::Map(..) if sizing_min(ty) > 1 => {
+                return Err(RustGenError::NoDumb(ctx.clone(), ty.to_string()))
+            }
+            Ty::List(r, sizing) | Ty::Set(r, sizing) => {
+                let item = self.dumb(ctx, r)?.expr();
+                let confined = self.import("amplify::confinement", "Confined");
+                DumbValue::Expr(format!(
+                    "{confined}::try_from_iter((0..{}).map(|_| {item})).expect(\"dumb value\")",
+                    sizing.min
+                ))
+            }
+            Ty::Map(k, v, sizing) => {
+                let key = self.dumb(ctx, k)?.expr();
+                let val = self.dumb(ctx, v)?.expr();
+                let confined = self.import("amplify::confinement", "Confined");
+                DumbValue::Expr(format!(
+                    "{confined}::try_from_iter((0..{}).map(|_| ({key}, {val}))).expect(\"dumb \
+                     value\")",
+                    sizing.min
+                ))
+            }
+            _ => DumbValue::Default,
+        })
+    }
+
+    fn write_type(
+        &mut self,
+        out: &mut String,
+        name: &TypeName,
+        ty: &'a Ty<Ref>,
+    ) -> Result<(), RustGenError> {
+        let fqn = TypeFqn::with(self.lib.clone(), name.clone());
+        let (ident, renamed) = type_ident(name);
+        let mut attrs = format!("lib = {}", self.lib_const());
+        if renamed {
+            write!(attrs, ", rename = \"{name}\"").ok();
+        }
+
+        match ty {
+            Ty::Enum(variants) => {
+                out.push_str(
+                    "#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]\n",
+                );
+                out.push_str("#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]\n");
+                writeln!(out, "#[strict_type({attrs}, tags = repr, into_u8, try_from_u8)]").ok();
+                writeln!(out, "#[repr(u8)]\npub enum {ident} {{").ok();
+                let mut variants = variants.iter().collect::<Vec<_>>();
+                variants.sort_by_key(|variant| variant.tag);
+                let mut used = BTreeSet::new();
+                for (no, variant) in variants.into_iter().enumerate() {
+                    let (ident, renamed) =
+                        variant_ident(variant.name.as_str(), variant.tag, &mut used);
+                    let mut attrs = vec![];
+                    if no == 0 {
+                        attrs.push(s!("dumb"));
+                    }
+                    if renamed {
+                        attrs.push(format!("rename = \"{}\"", variant.name));
+                    }
+                    write_attrs(out, "    ", &attrs);
+                    writeln!(out, "    {ident} = {},", variant.tag).ok();
+                }
+                out.push_str("}\n");
+            }
+            Ty::Union(variants) => {
+                let derives = self.derives(ty)?;
+                let mut body = String::new();
+                let mut used = BTreeSet::new();
+                // Unit variants are preferred as dumb values; otherwise the first variant is used
+                let mut unit_dumb = false;
+                for r in variants.values() {
+                    if let Resolved::Inline(Ty::Primitive(Primitive::UNIT)) =
+                        self.src.resolve_ref(r)?
+                    {
+                        unit_dumb = true;
+                    }
+                }
+                let mut dumb_expr = None;
+                for (variant, r) in variants.iter() {
+                    let (ident, renamed) =
+                        variant_ident(variant.name.as_str(), variant.tag, &mut used);
+                    let mut attrs = vec![format!("tag = {}", variant.tag)];
+                    if renamed {
+                        attrs.push(format!("rename = \"{}\"", variant.name));
+                    }
+                    let Resolved::Inline(var_ty) = self.src.resolve_ref(r)? else {
+                        return Err(Self::unrepresentable(&fqn, r));
+                    };
+                    match var_ty {
+                        Ty::Primitive(Primitive::UNIT) => {
+                            if unit_dumb {
+                                attrs.push(s!("dumb"));
+                                unit_dumb = false;
+                            }
+                            write_attrs(&mut body, "    ", &attrs);
+                            writeln!(body, "    {ident},").ok();
+                        }
+                        Ty::Tuple(fields) => {
+                            let fields = self.unnamed_fields(&fqn, fields, true)?;
+                            if !unit_dumb && body.is_empty() {
+                                let items = fields.iter().map(|_| "strict_dumb!()");
+                                dumb_expr = Some(format!(
+                                    "Self::{ident}({})",
+                                    items.collect::<Vec<_>>().join(", ")
+                                ));
+                            }
+                            write_attrs(&mut body, "    ", &attrs);
+                            let fields = fields.into_iter().map(|(ty, _)| ty).collect::<Vec<_>>();
+                            writeln!(body, "    {ident}({}),", fields.join(", ")).ok();
+                        }
+                        Ty::Struct(fields) => {
+                            let fields = self.named_fields(&fqn, fields, true)?;
+                            if !unit_dumb && body.is_empty() {
+                                let items = fields
+                                    .iter()
+                                    .map(|(name, ..)| format!("{name}: strict_dumb!()"));
+                                dumb_expr = Some(format!(
+                                    "Self::{ident} {{ {} }}",
+                                    items.collect::<Vec<_>>().join(", ")
+                                ));
+                            }
+                            write_attrs(&mut body, "    ", &attrs);
+                            writeln!(body, "    {ident} {{").ok();
+                            for (name, ty, attrs) in fields {
+                                write_attrs(&mut body, "        ", &attrs);
+                                writeln!(body, "        {name}: {ty},").ok();
+                            }
+                            body.push_str("    },\n");
+                        }
+                        _ => return Err(Self::unrepresentable(&fqn, var_ty)),
+                    }
+                }
+                if let Some(expr) = dumb_expr {
+                    write!(attrs, ", dumb = {expr}").ok();
+                }
+                writeln!(out, "{derives}").ok();
+                out.push_str("#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]\n");
+                writeln!(out, "#[strict_type({attrs}, tags = custom)]").ok();
+                writeln!(out, "pub enum {ident} {{").ok();
+                out.push_str(&body);
+                out.push_str("}\n");
+            }
+            Ty::Struct(fields) => {
+                let derives = self.derives(ty)?;
+                let fields = self.named_fields(&fqn, fields, false)?;
+                writeln!(out, "{derives}").ok();
+                out.push_str("#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]\n");
+                writeln!(out, "#[strict_type({attrs})]\npub struct {ident} {{").ok();
+                for (name, ty, attrs) in fields {
+                    write_attrs(out, "    ", &attrs);
+                    writeln!(out, "    pub {name}: {ty},").ok();
+                }
+                out.push_str("}\n");
+            }
+            Ty::Tuple(fields) => {
+                let derives = self.derives(ty)?;
+                let fields = self.unnamed_fields(&fqn, fields, false)?;
+                writeln!(out, "{derives}").ok();
+                out.push_str("#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]\n");
+                writeln!(out, "#[strict_type({attrs})]").ok();
+                if fields.iter().all(|(_, attrs)| attrs.is_empty()) {
+                    let fields =
+                        fields.into_iter().map(|(ty, _)| format!("pub {ty}")).collect::<Vec<_>>();
+                    writeln!(out, "pub struct {ident}({});", fields.join(", ")).ok();
+                } else {
+                    writeln!(out, "pub struct {ident}(").ok();
+                    for (ty, attrs) in fields {
+                        write_attrs(out, "    ", &attrs);
+                        writeln!(out, "    pub {ty},").ok();
+                    }
+                    out.push_str(");\n");
+                }
+            }
+            _ => return Err(Self::unrepresentable(&fqn, ty)),
+        }
+        Ok(())
+    }
+
+    /// Produces field type and field attributes. Fields in union variants must not require dumb
+    /// value expressions, since strict encoding derive macros do not support them.
+    fn field(
+        &mut self,
+        ctx: &TypeFqn,
+        r: &'a Ref,
+        in_union: bool,
+    ) -> Result<(String, Option<String>), RustGenError> {
+        let ty = self.ty_expr(ctx, r)?;
+        match self.dumb(ctx, r)? {
+            DumbValue::Expr(_) if in_union => Err(Self::unrepresentable(ctx, r)),
+            DumbValue::Expr(expr) => Ok((ty, Some(format!("dumb = {{ {expr} }}")))),
+            DumbValue::Default | DumbValue::Derived => Ok((ty, None)),
+        }
+    }
+
+    fn named_fields(
+        &mut self,
+        ctx: &TypeFqn,
+        fields: &'a NamedFields<Ref>,
+        in_union: bool,
+    ) -> Result<Vec<(String, String, Vec<String>)>, RustGenError> {
+        let mut used = BTreeSet::new();
+        let mut res = Vec::with_capacity(fields.len());
+        for field in fields.iter() {
+            let (ident, renamed) = field_ident(field.name.as_str(), &mut used);
+            let (ty, dumb) = self.field(ctx, &field.ty, in_union)?;
+            let mut attrs = vec![];
+            if renamed {
+                attrs.push(format!("rename = \"{}\"", field.name));
+            }
+            attrs.extend(dumb);
+            res.push((ident, ty, attrs));
+        }
+        Ok(res)
+    }
+
+    fn unnamed_fields(
+        &mut self,
+        ctx: &TypeFqn,
+        fields: &'a UnnamedFields<Ref>,
+        in_union: bool,
+    ) -> Result<Vec<(String, Vec<String>)>, RustGenError> {
+        fields
+            .iter()
+            .map(|r| {
+                let (ty, dumb) = self.field(ctx, r, in_union)?;
+                Ok((ty, dumb.into_iter().collect()))
+            })
+            .collect()
+    }
+}
+
+const ALIASES: [&str; 25] = [
+    "TinyString",
+    "SmallString",
+    "MediumString",
+    "LargeString",
+    "NonEmptyString",
+    "TinyBlob",
+    "SmallBlob",
+    "MediumBlob",
+    "LargeBlob",
+    "NonEmptyBlob",
+    "TinyVec",
+    "SmallVec",
+    "MediumVec",
+    "LargeVec",
+    "NonEmptyVec",
+    "TinyOrdSet",
+    "SmallOrdSet",
+    "MediumOrdSet",
+    "LargeOrdSet",
+    "NonEmptyOrdSet",
+    "TinyOrdMap",
+    "SmallOrdMap",
+    "MediumOrdMap",
+    "LargeOrdMap",
+    "NonEmptyOrdMap",
+];
+
+fn sizing_min<Ref: TypeRef>(ty: &Ty<Ref>) -> u64 {
+    match ty {
+        Ty::List(_, sizing) | Ty::Set(_, sizing) | Ty::Map(_, _, sizing) => sizing.min,
+        _ => 0,
+    }
+}
+
+fn write_attrs(out: &mut String, indent: &str, attrs: &[String]) {
+    if !attrs.is_empty() {
+        writeln!(out, "{indent}#[strict_type({})]", attrs.join(", ")).ok();
+    }
+}
+
+/// Checks whether the name is a sequence of lowercase words in camel case, which is converted to
+/// the same name by strict encoding derive macros from both snake case and pascal case.
+fn is_simple_camel(name: &str) -> bool {
+    let mut prev_upper = true;
+    for (pos, c) in name.chars().enumerate() {
+        match c {
+            'a'..='z' => prev_upper = false,
+            'A'..='Z' if pos > 0 && !prev_upper => prev_upper = true,
+            _ => return false,
+        }
+    }
+    !name.is_empty() && !prev_upper
+}
+
+fn snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut s = String::with_capacity(name.len() + 4);
+    for (pos, c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() {
+            let prev = pos.checked_sub(1).map(|p| chars[p]);
+            let next = chars.get(pos + 1);
+            let boundary = match prev {
+                Some(p) if p.is_ascii_lowercase() || p.is_ascii_digit() => true,
+                Some(p) if p.is_ascii_uppercase() => next.is_some_and(|n| n.is_ascii_lowercase()),
+                _ => false,
+            };
+            if boundary {
+                s.push('_');
+            }
+            s.push(c.to_ascii_lowercase());
+        } else {
+            s.push(*c);
+        }
+    }
+    s
+}
+
+fn module_name(lib: &LibName) -> String {
+    let name = snake_case(lib.as_str());
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+
+/// Returns Rust identifier for the type, together with a flag indicating that the type requires
+/// renaming in strict type derivation.
+fn type_ident(name: &TypeName) -> (String, bool) {
+    match name.as_str() {
+        "Self" | "_" => (format!("{name}_"), true),
+        name => (name.to_owned(), false),
+    }
+}
+
+fn field_ident(name: &str, used: &mut BTreeSet<String>) -> (String, bool) {
+    let mut ident = snake_case(name);
+    let mut renamed = !is_simple_camel(name);
+    if KEYWORDS.contains(&ident.as_str()) || ident.chars().all(|c| c == '_') {
+        ident.push('_');
+        renamed = true;
+    }
+    while !used.insert(ident.clone()) {
+        ident.push('_');
+        renamed = true;
+    }
+    (ident, renamed)
+}
+
+fn variant_ident(name: &str, tag: u8, used: &mut BTreeSet<String>) -> (String, bool) {
+    let mut ident = String::with_capacity(name.len());
+    for word in name.split('_') {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            ident.push(first.to_ascii_uppercase());
+            ident.extend(chars);
+        }
+    }
+    let mut renamed = !is_simple_camel(name);
+    if !ident.starts_with(|c: char| c.is_ascii_alphabetic()) || ident == "Self" {
+        ident.insert(0, 'V');
+        renamed = true;
+    }
+    if !used.insert(ident.clone()) {
+        ident = format!("{ident}{tag}");
+        used.insert(ident.clone());
+        renamed = true;
+    }
+    (ident, renamed)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn generate(src: &str) -> Result<String, RustGenError> {
+        let lib = SymbolicLib::from_source(src, None).unwrap();
+        RustGen::new().generate_lib(&lib)
+    }
+
+    #[test]
+    fn unrepresentable() {
+        let fqn = TypeFqn::with(libname!("Test"), tn!("Some"));
+        let err = generate("typelib Test\ndata Some : ()\n").unwrap_err();
+        assert_eq!(err, RustGenError::Unrepresentable(fqn.clone(), s!("()")));
+        let err = generate("typelib Test\ndata Some : data [U8 ^ 4]\n").unwrap_err();
+        assert_eq!(err, RustGenError::Unrepresentable(fqn.clone(), s!("[U8 ^ 4]")));
+        let err = generate("typelib Test\ndata Some : data {U16 ^ 2..4}\n").unwrap_err();
+        assert_eq!(err, RustGenError::NoDumb(fqn, s!("{U16 ^ 2..0x4}")));
+    }
+
+    #[test]
+    fn idents() {
+        let mut used = empty!();
+        assert_eq!(field_ident("semId", &mut used), (s!("sem_id"), false));
+        assert_eq!(field_ident("type", &mut used), (s!("type_"), true));
+        assert_eq!(field_ident("sem_id", &mut used), (s!("sem_id_"), true));
+        let mut used = empty!();
+        assert_eq!(variant_ident("unnamedField", 0, &mut used), (s!("UnnamedField"), false));
+        assert_eq!(variant_ident("_A", 1, &mut used), (s!("A"), true));
+        assert_eq!(variant_ident("a", 2, &mut used), (s!("A2"), true));
+        assert_eq!(variant_ident("_0", 3, &mut used), (s!("V0"), true));
+        assert_eq!(snake_case("StrictTypes"), "strict_types");
+        assert_eq!(snake_case("RGBContract"), "rgb_contract");
+    }
+}
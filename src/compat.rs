@@ -0,0 +1,752 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Analysis of compatibility between different versions of strict types.
+//!
+//! Each change to a type changes its semantic id, but not every change affects the way the data
+//! are serialized. The analyzer compares two versions of types and classifies each change
+//! according to whether data serialized with the old version can be read using the new one.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
+use std::marker::PhantomData;
+
+use encoding::{FieldName, Primitive, Sizing, Variant, VariantName};
+
+use crate::ast::{NamedFields, Path, Step, UnnamedFields};
+use crate::typesys::{Resolved, SymbolicSys, TypeFqn, TypeSource, UnknownType};
+use crate::value::encode::SizingExt;
+use crate::{Cls, SemId, SymbolicLib, Ty, TypeRef, TypeSystem};
+
+/// Level of compatibility between the old and the new version of a type.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[display(lowercase)]
+pub enum Compat {
+    /// Only names were changed; the data layout is the same, but the data may be interpreted by
+    /// software differently.
+    Semantic,
+
+    /// Data serialized with the old version of the type can be read with the new version.
+    Compatible,
+
+    /// Data serialized with the old version of the type may be not readable with the new version,
+    /// or may be read incorrectly.
+    Breaking,
+}
+
+/// Single change between the old and the new version of a type.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Change {
+    /// A new named type was added.
+    TypeAdded,
+    /// Named type was removed.
+    TypeRemoved,
+    /// Reference to a named type was replaced with a reference to a type with different name (or
+    /// an inline type) having compatible layout.
+    TypeRenamed {
+        old: Option<TypeFqn>,
+        new: Option<TypeFqn>,
+    },
+    /// Type defined in an external library has changed; its compatibility must be checked by
+    /// analyzing that library.
+    ExternChanged(TypeFqn),
+    /// Type kind was changed, for instance a list was replaced with a set.
+    KindChanged { old: Cls, new: Cls },
+    /// Primitive type was replaced with a different one.
+    PrimitiveChanged { old: Primitive, new: Primitive },
+    /// A new enum or union variant was added.
+    VariantAdded(Variant),
+    /// Enum or union variant was removed.
+    VariantRemoved(Variant),
+    /// Enum or union variant was renamed, keeping the same tag.
+    VariantRenamed {
+        tag: u8,
+        old: VariantName,
+        new: VariantName,
+    },
+    /// Enum or union variant tag was changed.
+    VariantRetagged { name: VariantName, old: u8, new: u8 },
+    /// A new field was added to a structure or a tuple.
+    FieldAdded { pos: u8, name: Option<FieldName> },
+    /// A field was removed from a structure or a tuple.
+    FieldRemoved { pos: u8, name: Option<FieldName> },
+    /// Structure field was renamed.
+    FieldRenamed {
+        pos: u8,
+        old: FieldName,
+        new: FieldName,
+    },
+    /// Structure fields were reordered.
+    FieldsReordered,
+    /// Length of an array was changed.
+    ArrayLenChanged { old: u16, new: u16 },
+    /// Limits on the number of collection items were changed.
+    SizingChanged { old: Sizing, new: Sizing },
+}
+
+impl Change {
+    /// Classifies the change.
+    pub fn compat(&self) -> Compat {
+        match self {
+            Change::TypeRenamed { .. }
+            | Change::VariantRenamed { .. }
+            | Change::FieldRenamed { .. } => Compat::Semantic,
+            Change::KindChanged {
+                old: Cls::Struct | Cls::Tuple,
+                new: Cls::Struct | Cls::Tuple,
+            } => Compat::Semantic,
+            Change::TypeAdded | Change::VariantAdded(_) => Compat::Compatible,
+            // Collection length is serialized with a number of bytes depending on the maximal
+            // collection size
+            Change::SizingChanged { old, new }
+                if new.min <= old.min
+                    && new.max >= old.max
+                    && new.byte_size() == old.byte_size() =>
+            {
+                Compat::Compatible
+            }
+            Change::TypeRemoved
+            | Change::ExternChanged(_)
+            | Change::KindChanged { .. }
+            | Change::PrimitiveChanged { .. }
+            | Change::VariantRemoved(_)
+            | Change::VariantRetagged { .. }
+            | Change::FieldAdded { .. }
+            | Change::FieldRemoved { .. }
+            | Change::FieldsReordered
+            | Change::ArrayLenChanged { .. }
+            | Change::SizingChanged { .. } => Compat::Breaking,
+        }
+    }
+}
+
+struct DisplayName<'a>(&'a Option<TypeFqn>);
+
+impl Display for DisplayName<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(fqn) => write!(f, "`{fqn}`"),
+            None => f.write_str("inline type"),
+        }
+    }
+}
+
+struct DisplaySizing<'a>(&'a Sizing);
+
+impl Display for DisplaySizing<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{:#x}", self.0.min, self.0.max)
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::TypeAdded => f.write_str("type added"),
+            Change::TypeRemoved => f.write_str("type removed"),
+            Change::TypeRenamed { old, new } => {
+                write!(f, "type {} replaced with {}", DisplayName(old), DisplayName(new))
+            }
+            Change::ExternChanged(fqn) => write!(f, "external type `{fqn}` changed"),
+            Change::KindChanged { old, new } => write!(f, "{old} changed to {new}"),
+            Change::PrimitiveChanged { old, new } => write!(f, "{old} changed to {new}"),
+            Change::VariantAdded(var) => write!(f, "variant {var} added"),
+            Change::VariantRemoved(var) => write!(f, "variant {var} removed"),
+            Change::VariantRenamed { tag, old, new } => {
+                write!(f, "variant #{tag} renamed from `{old}` to `{new}`")
+            }
+            Change::VariantRetagged { name, old, new } => {
+                write!(f, "variant `{name}` tag changed from {old} to {new}")
+            }
+            Change::FieldAdded {
+                pos,
+                name: Some(name),
+            } => {
+                write!(f, "field `{name}` added at position {pos}")
+            }
+            Change::FieldAdded { pos, name: None } => write!(f, "field added at position {pos}"),
+            Change::FieldRemoved {
+                pos,
+                name: Some(name),
+            } => {
+                write!(f, "field `{name}` removed from position {pos}")
+            }
+            Change::FieldRemoved { pos, name: None } => {
+                write!(f, "field removed from position {pos}")
+            }
+            Change::FieldRenamed { pos, old, new } => {
+                write!(f, "field #{pos} renamed from `{old}` to `{new}`")
+            }
+            Change::FieldsReordered => f.write_str("fields reordered"),
+            Change::ArrayLenChanged { old, new } => {
+                write!(f, "array length changed from {old} to {new}")
+            }
+            Change::SizingChanged { old, new } => write!(
+                f,
+                "size limits changed from {} to {}",
+                DisplaySizing(old),
+                DisplaySizing(new)
+            ),
+        }
+    }
+}
+
+/// Change located within a type.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct CompatChange {
+    /// Named type containing the change. Absent for changes within unnamed types.
+    pub ty: Option<TypeFqn>,
+    /// Path to the changed type within the named type.
+    pub path: Path,
+    pub change: Change,
+}
+
+impl Display for CompatChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.change.compat())?;
+        if let Some(fqn) = &self.ty {
+            Display::fmt(fqn, f)?;
+        }
+        write!(f, "{}: {}", self.path, self.change)
+    }
+}
+
+/// Report on changes between two versions of types.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct CompatReport {
+    changes: Vec<CompatChange>,
+}
+
+impl CompatReport {
+    /// Compares all named types between two versions of a type library.
+    pub fn libs(old: &SymbolicLib, new: &SymbolicLib) -> Result<Self, UnknownType> {
+        let mut cmp = Comparator::new(old, new);
+        let old_types = old.types().iter().map(|(name, ty)| (name.clone(), ty));
+        let new_types = new.types().iter().map(|(name, ty)| (name.clone(), ty));
+        cmp.cmp_named(
+            old_types.collect(),
+            new_types.collect(),
+            |name| TypeFqn::with(old.name().clone(), name.clone()),
+            |name| TypeFqn::with(new.name().clone(), name.clone()),
+        )?;
+        Ok(cmp.report)
+    }
+
+    /// Compares all named types between two versions of a type system.
+    pub fn systems(old: &SymbolicSys, new: &SymbolicSys) -> Result<Self, UnknownType> {
+        fn named(sys: &SymbolicSys) -> Result<BTreeMap<TypeFqn, &Ty<SemId>>, UnknownType> {
+            let mut types = BTreeMap::new();
+            for sym in sys.as_symbols().symbols() {
+                if let Some(fqn) = &sym.fqn {
+                    let ty = sys.as_types().get(sym.id).ok_or(UnknownType(sym.id))?;
+                    types.insert(fqn.clone(), ty);
+                }
+            }
+            Ok(types)
+        }
+
+        let mut cmp = Comparator::new(old, new);
+        cmp.cmp_named(named(old)?, named(new)?, TypeFqn::clone, TypeFqn::clone)?;
+        Ok(cmp.report)
+    }
+
+    /// Compares two types from different type systems.
+    pub fn types(
+        old: &TypeSystem,
+        old_id: SemId,
+        new: &TypeSystem,
+        new_id: SemId,
+    ) -> Result<Self, UnknownType> {
+        let mut cmp = Comparator::new(old, new);
+        cmp.cmp_refs(&old_id, &new_id, &Path::new())?;
+        Ok(cmp.report)
+    }
+
+    pub fn changes(&self) -> &[CompatChange] { &self.changes }
+
+    /// Returns the worst compatibility level among all changes, or `None` if there were no
+    /// changes.
+    pub fn compat(&self) -> Option<Compat> { self.changes.iter().map(|c| c.change.compat()).max() }
+
+    /// Checks whether data serialized with the old types can be read with the new types.
+    pub fn is_wire_compatible(&self) -> bool { self.compat() < Some(Compat::Breaking) }
+
+    pub fn breaking(&self) -> impl Iterator<Item = &CompatChange> {
+        self.changes.iter().filter(|c| c.change.compat() == Compat::Breaking)
+    }
+
+    pub fn wire_compatible(&self) -> impl Iterator<Item = &CompatChange> {
+        self.changes.iter().filter(|c| c.change.compat() < Compat::Breaking)
+    }
+}
+
+impl Display for CompatReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+fn at(path: &Path, step: impl Into<Step>) -> Path {
+    let mut path = path.clone();
+    path.push(step.into()).expect("type nesting is limited");
+    path
+}
+
+struct Comparator<'a, Ref: TypeRef, S: TypeSource<Ref>> {
+    old: &'a S,
+    new: &'a S,
+    /// Named type which is currently compared.
+    ty: Option<TypeFqn>,
+    /// Pairs of differently named types which were already compared.
+    visited: BTreeSet<(TypeFqn, TypeFqn)>,
+    /// Pairs of unnamed types which are being compared. Types of a bare type system are all
+    /// unnamed, thus recursive types have to be detected by their references.
+    inline: Vec<(&'a Ref, &'a Ref)>,
+    report: CompatReport,
+    _phantom: PhantomData<Ref>,
+}
+
+impl<'a, Ref: TypeRef + 'a, S: TypeSource<Ref>> Comparator<'a, Ref, S> {
+    fn new(old: &'a S, new: &'a S) -> Self {
+        Comparator {
+            old,
+            new,
+            ty: None,
+            visited: empty!(),
+            inline: empty!(),
+            report: empty!(),
+            _phantom: default!(),
+        }
+    }
+
+    fn push(&mut self, path: &Path, change: Change) {
+        self.report.changes.push(CompatChange {
+            ty: self.ty.clone(),
+            path: path.clone(),
+            change,
+        });
+    }
+
+    fn cmp_named<K: Ord>(
+        &mut self,
+        old: BTreeMap<K, &'a Ty<Ref>>,
+        new: BTreeMap<K, &'a Ty<Ref>>,
+        old_fqn: impl Fn(&K) -> TypeFqn,
+        new_fqn: impl Fn(&K) -> TypeFqn,
+    ) -> Result<(), UnknownType> {
+        let root = Path::new();
+        for (key, old_ty) in &old {
+            match new.get(key) {
+                Some(new_ty) => {
+                    self.ty = Some(new_fqn(key));
+                    self.cmp_tys(old_ty, new_ty, &root)?;
+                }
+                None => {
+                    self.ty = Some(old_fqn(key));
+                    self.push(&root, Change::TypeRemoved);
+                }
+            }
+        }
+        for key in new.keys().filter(|key| !old.contains_key(key)) {
+            self.ty = Some(new_fqn(key));
+            self.push(&root, Change::TypeAdded);
+        }
+        Ok(())
+    }
+
+    fn definition(
+        src: &'a S,
+        resolved: Resolved<'a, Ref>,
+    ) -> (Option<TypeFqn>, Option<&'a Ty<Ref>>) {
+        match resolved {
+            Resolved::Named(fqn) => {
+                let ty = src.named_ty(&fqn);
+                (Some(fqn), ty)
+            }
+            Resolved::Inline(ty) => (None, Some(ty)),
+        }
+    }
+
+    fn cmp_refs(&mut self, old: &'a Ref, new: &'a Ref, path: &Path) -> Result<(), UnknownType> {
+        let (old_name, old_ty) = Self::definition(self.old, self.old.resolve_ref(old)?);
+        let (new_name, new_ty) = Self::definition(self.new, self.new.resolve_ref(new)?);
+
+        if let (Some(old_fqn), Some(new_fqn)) = (&old_name, &new_name) {
+            if old_fqn == new_fqn {
+                // Named types are compared on their own; external types are opaque and may be
+                // compared only by their ids
+                match (self.old.extern_id(old), self.new.extern_id(new)) {
+                    (Some(old_id), Some(new_id)) if old_id != new_id => {
+                        self.push(path, Change::ExternChanged(new_fqn.clone()))
+                    }
+                    _ => {}
+                }
+                return Ok(());
+            }
+            if !self.visited.insert((old_fqn.clone(), new_fqn.clone())) {
+                return Ok(());
+            }
+        }
+
+        let (Some(old_ty), Some(new_ty)) = (old_ty, new_ty) else {
+            let fqn = new_name.or(old_name).expect("inline types are always known");
+            self.push(path, Change::ExternChanged(fqn));
+            return Ok(());
+        };
+        let inline = old_name.is_none() && new_name.is_none();
+        if inline {
+            if self.inline.contains(&(old, new)) {
+                return Ok(());
+            }
+            self.inline.push((old, new));
+        }
+        if old_name != new_name {
+            self.push(path, Change::TypeRenamed {
+                old: old_name,
+                new: new_name,
+            });
+        }
+        let res = self.cmp_tys(old_ty, new_ty, path);
+        if inline {
+            self.inline.pop();
+        }
+        res
+    }
+
+    fn cmp_tys(
+        &mut self,
+        old: &'a Ty<Ref>,
+        new: &'a Ty<Ref>,
+        path: &Path,
+    ) -> Result<(), UnknownType> {
+        match (old, new) {
+            (Ty::Primitive(old), Ty::Primitive(new)) => {
+                if old != new {
+                    self.push(path, Change::PrimitiveChanged {
+                        old: *old,
+                        new: *new,
+                    });
+                }
+            }
+            (Ty::UnicodeChar, Ty::UnicodeChar) => {}
+            (Ty::Enum(old), Ty::Enum(new)) => {
+                self.cmp_variants(old.iter(), new.iter(), path);
+            }
+            (Ty::Union(old_vars), Ty::Union(new_vars)) => {
+                for (old_tag, new_var) in self.cmp_variants(old_vars.keys(), new_vars.keys(), path)
+                {
+                    let old_ty = old_vars.ty_by_tag(old_tag).expect("variant exists");
+                    let new_ty = new_vars.ty_by_tag(new_var.tag).expect("variant exists");
+                    self.cmp_refs(old_ty, new_ty, &at(path, new_var.name))?;
+                }
+            }
+            (Ty::Struct(old), Ty::Struct(new)) => self.cmp_named_fields(old, new, path)?,
+            (Ty::Tuple(old), Ty::Tuple(new)) => self.cmp_unnamed_fields(old, new, path)?,
+            (Ty::Struct(old_fields), Ty::Tuple(new_fields)) => {
+                self.push(path, Change::KindChanged {
+                    old: old.cls(),
+                    new: new.cls(),
+                });
+                let old_fields = old_fields.iter().map(|field| &field.ty);
+                self.cmp_positional(old_fields, new_fields.iter(), path)?;
+            }
+            (Ty::Tuple(old_fields), Ty::Struct(new_fields)) => {
+                self.push(path, Change::KindChanged {
+                    old: old.cls(),
+                    new: new.cls(),
+                });
+                let new_fields = new_fields.iter().map(|field| &field.ty);
+                self.cmp_positional(old_fields.iter(), new_fields, path)?;
+            }
+            (Ty::Array(old, old_len), Ty::Array(new, new_len)) => {
+                if old_len != new_len {
+                    self.push(path, Change::ArrayLenChanged {
+                        old: *old_len,
+                        new: *new_len,
+                    });
+                }
+                self.cmp_refs(old, new, &at(path, Step::Index))?;
+            }
+            (Ty::List(old, old_sizing), Ty::List(new, new_sizing)) => {
+                self.cmp_sizing(old_sizing, new_sizing, path);
+                self.cmp_refs(old, new, &at(path, Step::List))?;
+            }
+            (Ty::Set(old, old_sizing), Ty::Set(new, new_sizing)) => {
+                self.cmp_sizing(old_sizing, new_sizing, path);
+                self.cmp_refs(old, new, &at(path, Step::Set))?;
+            }
+            (Ty::Map(old_key, old_val, old_sizing), Ty::Map(new_key, new_val, new_sizing)) => {
+                self.cmp_sizing(old_sizing, new_sizing, path);
+                self.cmp_refs(old_key, new_key, &at(path, Step::MapKey))?;
+                self.cmp_refs(old_val, new_val, &at(path, Step::MapValue))?;
+            }
+            (old, new) => self.push(path, Change::KindChanged {
+                old: old.cls(),
+                new: new.cls(),
+            }),
+        }
+        Ok(())
+    }
+
+    fn cmp_sizing(&mut self, old: &Sizing, new: &Sizing, path: &Path) {
+        if old != new {
+            self.push(path, Change::SizingChanged {
+                old: *old,
+                new: *new,
+            });
+        }
+    }
+
+    /// Compares enum or union variants, returning pairs of old variant tags and matching new
+    /// variants.
+    fn cmp_variants<'v>(
+        &mut self,
+        old: impl Iterator<Item = &'v Variant>,
+        new: impl Iterator<Item = &'v Variant>,
+        path: &Path,
+    ) -> Vec<(u8, Variant)> {
+        let old = old.collect::<Vec<_>>();
+        let new = new.collect::<Vec<_>>();
+        let mut matched = vec![];
+        for var in &old {
+            if let Some(new_var) = new.iter().find(|v| v.name == var.name) {
+                if new_var.tag != var.tag {
+                    self.push(path, Change::VariantRetagged {
+                        name: var.name.clone(),
+                        old: var.tag,
+                        new: new_var.tag,
+                    });
+                }
+                matched.push((var.tag, (*new_var).clone()));
+            } else if let Some(new_var) =
+                new.iter().find(|v| v.tag == var.tag && !old.iter().any(|o| o.name == v.name))
+            {
+                self.push(path, Change::VariantRenamed {
+                    tag: var.tag,
+                    old: var.name.clone(),
+                    new: new_var.name.clone(),
+                });
+                matched.push((var.tag, (*new_var).clone()));
+            } else {
+                self.push(path, Change::VariantRemoved((*var).clone()));
+            }
+        }
+        for var in new {
+            if !matched.iter().any(|(_, v)| v.name == var.name) {
+                self.push(path, Change::VariantAdded(var.clone()));
+            }
+        }
+        matched
+    }
+
+    fn cmp_named_fields(
+        &mut self,
+        old: &'a NamedFields<Ref>,
+        new: &'a NamedFields<Ref>,
+        path: &Path,
+    ) -> Result<(), UnknownType> {
+        let old_names = old.iter().map(|field| &field.name).collect::<BTreeSet<_>>();
+        let new_names = new.iter().map(|field| &field.name).collect::<BTreeSet<_>>();
+        if old_names == new_names && old.len() == new.len() {
+            if old.iter().zip(new.iter()).any(|(o, n)| o.name != n.name) {
+                self.push(path, Change::FieldsReordered);
+            }
+            for field in old.iter() {
+                let new_ty = new.ty_by_name(&field.name).expect("same field names");
+                self.cmp_refs(&field.ty, new_ty, &at(path, field.name.clone()))?;
+            }
+            return Ok(());
+        }
+
+        for (pos, (o, n)) in old.iter().zip(new.iter()).enumerate() {
+            if o.name != n.name {
+                self.push(path, Change::FieldRenamed {
+                    pos: pos as u8,
+                    old: o.name.clone(),
+                    new: n.name.clone(),
+                });
+            }
+            self.cmp_refs(&o.ty, &n.ty, &at(path, n.name.clone()))?;
+        }
+        for (pos, field) in old.iter().enumerate().skip(new.len()) {
+            self.push(path, Change::FieldRemoved {
+                pos: pos as u8,
+                name: Some(field.name.clone()),
+            });
+        }
+        for (pos, field) in new.iter().enumerate().skip(old.len()) {
+            self.push(path, Change::FieldAdded {
+                pos: pos as u8,
+                name: Some(field.name.clone()),
+            });
+        }
+        Ok(())
+    }
+
+    fn cmp_unnamed_fields(
+        &mut self,
+        old: &'a UnnamedFields<Ref>,
+        new: &'a UnnamedFields<Ref>,
+        path: &Path,
+    ) -> Result<(), UnknownType> {
+        self.cmp_positional(old.iter(), new.iter(), path)
+    }
+
+    fn cmp_positional(
+        &mut self,
+        old: impl ExactSizeIterator<Item = &'a Ref>,
+        new: impl ExactSizeIterator<Item = &'a Ref>,
+        path: &Path,
+    ) -> Result<(), UnknownType> {
+        let (old_len, new_len) = (old.len(), new.len());
+        for (pos, (o, n)) in old.zip(new).enumerate() {
+            self.cmp_refs(o, n, &at(path, pos as u8))?;
+        }
+        for pos in new_len..old_len {
+            self.push(path, Change::FieldRemoved {
+                pos: pos as u8,
+                name: None,
+            });
+        }
+        for pos in old_len..new_len {
+            self.push(path, Change::FieldAdded {
+                pos: pos as u8,
+                name: None,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn compare(old: &str, new: &str) -> CompatReport {
+        let old = SymbolicLib::from_source(&format!("typelib Test\n{old}\n"), None).unwrap();
+        let new = SymbolicLib::from_source(&format!("typelib Test\n{new}\n"), None).unwrap();
+        CompatReport::libs(&old, &new).unwrap()
+    }
+
+    fn single(report: &CompatReport) -> (&Change, Compat) {
+        assert_eq!(report.changes().len(), 1, "{report}");
+        let change = &report.changes()[0].change;
+        (change, change.compat())
+    }
+
+    #[test]
+    fn unchanged() {
+        let src = "data Some : a U8, b [U16 ^ ..0xff]\ndata Other : x Some, y (U8, U16)";
+        let report = compare(src, src);
+        assert!(report.changes().is_empty());
+        assert_eq!(report.compat(), None);
+        assert!(report.is_wire_compatible());
+    }
+
+    #[test]
+    fn variant_added() {
+        let report =
+            compare("data Some : a (U8) | b (U16)", "data Some : a (U8) | b (U16) | c (U32)");
+        let (change, compat) = single(&report);
+        assert_eq!(change, &Change::VariantAdded(Variant::named(2, vname!("c"))));
+        assert_eq!(compat, Compat::Compatible);
+        assert!(report.is_wire_compatible());
+    }
+
+    #[test]
+    fn variant_retagged() {
+        let report = compare("data Some : a | b", "data Some : a#1 | b#0");
+        assert_eq!(report.changes().len(), 2);
+        assert_eq!(report.compat(), Some(Compat::Breaking));
+    }
+
+    #[test]
+    fn field_renamed() {
+        let report = compare("data Some : a U8, b U16", "data Some : a U8, c U16");
+        let (change, compat) = single(&report);
+        assert_eq!(change, &Change::FieldRenamed {
+            pos: 1,
+            old: fname!("b"),
+            new: fname!("c")
+        });
+        assert_eq!(compat, Compat::Semantic);
+        assert_eq!(report.to_string(), "semantic: Test.Some: field #1 renamed from `b` to `c`\n");
+    }
+
+    #[test]
+    fn field_reordered() {
+        let report = compare("data Some : a U8, b U16", "data Some : b U16, a U8");
+        let (change, compat) = single(&report);
+        assert_eq!(change, &Change::FieldsReordered);
+        assert_eq!(compat, Compat::Breaking);
+    }
+
+    #[test]
+    fn sizing_widened() {
+        let report = compare("data Some : [U8 ^ 1..0x10]", "data Some : [U8 ^ ..0xff]");
+        let (_, compat) = single(&report);
+        assert_eq!(compat, Compat::Compatible);
+
+        let report = compare("data Some : [U8 ^ ..0xff]", "data Some : [U8 ^ ..0x100]");
+        let (_, compat) = single(&report);
+        assert_eq!(compat, Compat::Breaking);
+
+        let report = compare("data Some : [U8 ^ ..0xff]", "data Some : [U8 ^ ..0x10]");
+        let (_, compat) = single(&report);
+        assert_eq!(compat, Compat::Breaking);
+    }
+
+    #[test]
+    fn primitive_changed() {
+        let report = compare("data Some : a U8, b (U16)", "data Some : a U8, b (I16)");
+        let (change, compat) = single(&report);
+        assert_eq!(change, &Change::PrimitiveChanged {
+            old: Primitive::U16,
+            new: Primitive::I16
+        });
+        assert_eq!(compat, Compat::Breaking);
+        assert_eq!(report.changes()[0].path.to_string(), ".b.0");
+    }
+
+    #[test]
+    fn type_renamed() {
+        let report = compare(
+            "data Inner : U8\ndata Some : a Inner",
+            "data Renamed : U8\ndata Some : a Renamed",
+        );
+        assert_eq!(report.compat(), Some(Compat::Breaking));
+        let changes = report.changes().iter().map(|c| &c.change).collect::<Vec<_>>();
+        assert_eq!(changes, vec![
+            &Change::TypeRemoved,
+            &Change::TypeRenamed {
+                old: Some(TypeFqn::with(libname!("Test"), tn!("Inner"))),
+                new: Some(TypeFqn::with(libname!("Test"), tn!("Renamed"))),
+            },
+            &Change::TypeAdded,
+        ]);
+        assert_eq!(report.breaking().count(), 1);
+        assert_eq!(report.wire_compatible().count(), 2);
+    }
+}
@@ -0,0 +1,310 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use amplify::{ByteArray, Bytes32};
+use baid64::{Baid64ParseError, DisplayBaid64, FromBaid64Str};
+use sha2::{Digest, Sha256};
+use strict_encoding::STRICT_TYPES_LIB;
+
+use crate::ast::SemCommit;
+use crate::typesys::UnknownType;
+use crate::{Cls, CommitConsume, SemId, Ty, TypeSystem};
+
+pub const LAYOUT_ID_TAG: [u8; 32] = *b"urn:ubideco:strict-types:lay:v01";
+pub const LAYOUT_REF_TAG: [u8; 32] = *b"urn:ubideco:strict-types:lrf:v01";
+
+/// Structural type id, which commits only to the type layout, ignoring type names and
+/// field/variant names.
+///
+/// Types having the same layout id are serialized in the same way, and data serialized with one
+/// of them can be deserialized using another. Since names are ignored, structures are equivalent
+/// to tuples with the same fields, and types with a single field are equivalent to the type of
+/// that field.
+#[derive(Wrapper, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, From)]
+#[wrapper(Deref, BorrowSlice, Hex, Index, RangeOps)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = STRICT_TYPES_LIB)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", transparent)
+)]
+pub struct LayoutId(
+    #[from]
+    #[from([u8; 32])]
+    Bytes32,
+);
+
+impl DisplayBaid64 for LayoutId {
+    const HRI: &'static str = "layout";
+    const CHUNKING: bool = true;
+    const PREFIX: bool = true;
+    const EMBED_CHECKSUM: bool = false;
+    const MNEMONIC: bool = true;
+    fn to_baid64_payload(&self) -> [u8; 32] { self.to_byte_array() }
+}
+impl FromBaid64Str for LayoutId {}
+impl FromStr for LayoutId {
+    type Err = Baid64ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Self::from_baid64_str(s) }
+}
+impl Display for LayoutId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { self.fmt_baid64(f) }
+}
+
+impl LayoutId {
+    /// Layout of a recursive reference to a type, which layout is being computed `distance`
+    /// levels above. Since the distance doesn't depend on the type names, the recursive types of
+    /// the same shape have the same layout.
+    fn back_ref(distance: usize) -> LayoutId {
+        let tag = Sha256::new_with_prefix(LAYOUT_REF_TAG).finalize();
+        let mut hasher = Sha256::new();
+        hasher.commit_consume(tag);
+        hasher.commit_consume(tag);
+        hasher.commit_consume((distance as u64).to_le_bytes());
+        LayoutId::from_byte_array(hasher.finalize())
+    }
+}
+
+/// Types which layout is being computed, with the levels at which they are located.
+#[derive(Default)]
+struct LayoutStack {
+    types: Vec<(SemId, usize)>,
+    level: usize,
+}
+
+impl SemCommit for LayoutId {
+    fn sem_commit(&self, hasher: &mut impl CommitConsume) {
+        hasher.commit_consume(self.as_slice());
+    }
+}
+
+impl TypeSystem {
+    /// Computes layout id of a type.
+    pub fn layout_id(&self, sem_id: SemId) -> Result<LayoutId, UnknownType> {
+        self.layout_id_cached(sem_id, &mut empty!(), &mut default!()).map(|(id, _)| id)
+    }
+
+    /// Computes layout ids of all types in the type system.
+    pub fn layout_ids(&self) -> Result<BTreeMap<SemId, LayoutId>, UnknownType> {
+        let mut cache = BTreeMap::new();
+        for sem_id in self.keys() {
+            self.layout_id_cached(*sem_id, &mut cache, &mut default!())?;
+        }
+        Ok(cache)
+    }
+
+    /// Groups all types in the type system by their layout.
+    pub fn layouts(&self) -> Result<BTreeMap<LayoutId, BTreeSet<SemId>>, UnknownType> {
+        let mut layouts = BTreeMap::<LayoutId, BTreeSet<SemId>>::new();
+        for (sem_id, layout_id) in self.layout_ids()? {
+            layouts.entry(layout_id).or_default().insert(sem_id);
+        }
+        Ok(layouts)
+    }
+
+    /// Finds all types in the type system having the provided layout.
+    pub fn types_with_layout(&self, layout_id: LayoutId) -> Result<BTreeSet<SemId>, UnknownType> {
+        Ok(self
+            .layout_ids()?
+            .into_iter()
+            .filter(|(_, id)| *id == layout_id)
+            .map(|(sem_id, _)| sem_id)
+            .collect())
+    }
+
+    /// Finds all types in the type system having the same layout as the given type, including
+    /// the type itself.
+    pub fn same_layout(&self, sem_id: SemId) -> Result<BTreeSet<SemId>, UnknownType> {
+        let layout_id = self.layout_id(sem_id)?;
+        self.types_with_layout(layout_id)
+    }
+
+    /// Computes layout id of a type, returning also the lowest level of the recursive types
+    /// referenced from it. Layouts referencing types above their own level depend on the path
+    /// they were reached from and thus can't be cached.
+    fn layout_id_cached(
+        &self,
+        sem_id: SemId,
+        cache: &mut BTreeMap<SemId, LayoutId>,
+        stack: &mut LayoutStack,
+    ) -> Result<(LayoutId, usize), UnknownType> {
+        if let Some(id) = cache.get(&sem_id) {
+            return Ok((*id, usize::MAX));
+        }
+        if let Some((_, level)) = stack.types.iter().find(|(id, _)| *id == sem_id) {
+            return Ok((LayoutId::back_ref(stack.level - level), *level));
+        }
+        let ty = self.get(sem_id).ok_or(UnknownType(sem_id))?;
+
+        let level = stack.level;
+        stack.types.push((sem_id, level));
+        let res = self.layout_id_ty(ty, cache, stack);
+        stack.types.pop();
+        let (id, refs) = res?;
+        if refs >= level {
+            cache.insert(sem_id, id);
+        }
+        Ok((id, refs))
+    }
+
+    fn layout_id_ty(
+        &self,
+        ty: &Ty<SemId>,
+        cache: &mut BTreeMap<SemId, LayoutId>,
+        stack: &mut LayoutStack,
+    ) -> Result<(LayoutId, usize), UnknownType> {
+        // Types with a single field are serialized in the same way as the field itself
+        let single = match ty {
+            Ty::Tuple(fields) if fields.len() == 1 => fields.first().copied(),
+            Ty::Struct(fields) if fields.len() == 1 => fields.first().map(|field| field.ty),
+            _ => None,
+        };
+        if let Some(inner) = single {
+            return self.layout_id_cached(inner, cache, stack);
+        }
+
+        let tag = Sha256::new_with_prefix(LAYOUT_ID_TAG).finalize();
+        let mut hasher = Sha256::new();
+        hasher.commit_consume(tag);
+        hasher.commit_consume(tag);
+        match ty.cls() {
+            Cls::Struct => Cls::Tuple,
+            cls => cls,
+        }
+        .sem_commit(&mut hasher);
+
+        stack.level += 1;
+        let mut refs = usize::MAX;
+        let mut commit = |sem_id: SemId, hasher: &mut Sha256| -> Result<(), UnknownType> {
+            let (id, level) = self.layout_id_cached(sem_id, cache, stack)?;
+            refs = refs.min(level);
+            id.sem_commit(hasher);
+            Ok(())
+        };
+        match ty {
+            Ty::Primitive(prim) => hasher.commit_consume([prim.into_code()]),
+            Ty::UnicodeChar => {}
+            Ty::Enum(vars) => {
+                for var in vars {
+                    hasher.commit_consume([var.tag]);
+                }
+            }
+            Ty::Union(vars) => {
+                for (var, ty) in vars {
+                    hasher.commit_consume([var.tag]);
+                    commit(*ty, &mut hasher)?;
+                }
+            }
+            Ty::Tuple(fields) => {
+                for ty in fields {
+                    commit(*ty, &mut hasher)?;
+                }
+            }
+            Ty::Struct(fields) => {
+                for field in fields {
+                    commit(field.ty, &mut hasher)?;
+                }
+            }
+            Ty::Array(ty, len) => {
+                commit(*ty, &mut hasher)?;
+                hasher.commit_consume(len.to_le_bytes());
+            }
+            Ty::List(ty, sizing) | Ty::Set(ty, sizing) => {
+                commit(*ty, &mut hasher)?;
+                sizing.sem_commit(&mut hasher);
+            }
+            Ty::Map(key, ty, sizing) => {
+                commit(*key, &mut hasher)?;
+                commit(*ty, &mut hasher)?;
+                sizing.sem_commit(&mut hasher);
+            }
+        }
+        stack.level -= 1;
+
+        Ok((LayoutId::from_byte_array(hasher.finalize()), refs))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::typesys::TypeFqn;
+    use crate::{SymbolicLib, SymbolicSys, SystemBuilder};
+
+    fn sys() -> SymbolicSys {
+        let src = "typelib Test
+data Named : x U8, y U16
+data Unnamed : (U8, U16)
+data Other : a U8, b U32
+data Wrapper : (Named)
+data NamedList : [Named ^ ..0xff]
+data UnnamedList : [Unnamed ^ ..0xff]
+data Kind : a | b
+data Other2 : some | other
+data List1 : value U8, next [List1 ^ ..0xff]
+data List2 : item U8, tail [List2 ^ ..0xff]
+data List3 : value U8, next [Named ^ ..0xff]
+data Expr1 : neg (Expr1) | lit U8
+data Expr2 : neg (Expr2) | lit U8
+";
+        let lib = SymbolicLib::from_source(src, None).unwrap().compile().unwrap();
+        SystemBuilder::new().import(lib).unwrap().finalize().unwrap()
+    }
+
+    #[test]
+    fn same_layout() {
+        let sys = sys();
+        let id = |name: &'static str| sys.to_sem_id(TypeFqn::with(libname!("Test"), tn!(name)));
+        let types = sys.as_types();
+        let layout = |name: &'static str| types.layout_id(id(name).unwrap()).unwrap();
+
+        assert_ne!(id("Named"), id("Unnamed"));
+        assert_eq!(layout("Named"), layout("Unnamed"));
+        assert_eq!(layout("Named"), layout("Wrapper"));
+        assert_eq!(layout("NamedList"), layout("UnnamedList"));
+        assert_eq!(layout("Kind"), layout("Other2"));
+        assert_ne!(layout("Named"), layout("Other"));
+        assert_ne!(layout("Named"), layout("NamedList"));
+
+        let same = types.same_layout(id("Named").unwrap()).unwrap();
+        assert!(same.contains(&id("Unnamed").unwrap()));
+        assert!(same.contains(&id("Wrapper").unwrap()));
+        assert!(!same.contains(&id("Other").unwrap()));
+        for sem_id in &same {
+            assert_eq!(types.layout_id(*sem_id).unwrap(), layout("Named"));
+        }
+
+        assert_eq!(layout("List1"), layout("List2"));
+        assert_ne!(layout("List1"), layout("List3"));
+        assert_eq!(layout("Expr1"), layout("Expr2"));
+        assert_ne!(layout("Expr1"), layout("Kind"));
+
+        let layouts = types.layouts().unwrap();
+        assert_eq!(layouts[&layout("Named")], same);
+        assert_eq!(layouts.values().map(BTreeSet::len).sum::<usize>(), types.len());
+    }
+}
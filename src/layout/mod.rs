@@ -23,5 +23,9 @@
 pub mod vesper;
 mod translate;
 mod memory;
+mod id;
+mod size;
 
+pub use id::LayoutId;
 pub use memory::MemoryLayout;
+pub use size::Size;
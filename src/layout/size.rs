@@ -0,0 +1,185 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+
+use crate::typesys::UnknownType;
+use crate::value::encode::SizingExt;
+use crate::{SemId, Ty, TypeSystem};
+
+/// Measure of a type size in bytes
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Display)]
+pub enum Size {
+    /// Type has a fixed size known at compile time
+    #[display(inner)]
+    Fixed(usize),
+
+    /// Type has variable size
+    #[display("variable")]
+    Variable,
+}
+
+impl PartialOrd for Size {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for Size {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Size::Variable, Size::Variable) => Ordering::Equal,
+            (Size::Variable, _) => Ordering::Greater,
+            (_, Size::Variable) => Ordering::Less,
+            (Size::Fixed(a), Size::Fixed(b)) => a.cmp(b),
+        }
+    }
+}
+
+impl Add for Size {
+    type Output = Size;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        match (self, rhs) {
+            (Size::Fixed(a), Size::Fixed(b)) => Size::Fixed(a.saturating_add(b)),
+            _ => Size::Variable,
+        }
+    }
+}
+
+impl AddAssign for Size {
+    fn add_assign(&mut self, rhs: Self) { *self = *self + rhs; }
+}
+
+impl Sum for Size {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        let mut acc = Size::Fixed(0);
+        for item in iter {
+            acc += item;
+        }
+        acc
+    }
+}
+
+impl Size {
+    pub fn is_fixed(self) -> bool { matches!(self, Size::Fixed(_)) }
+
+    pub fn fixed(self) -> Option<usize> {
+        match self {
+            Size::Fixed(len) => Some(len),
+            Size::Variable => None,
+        }
+    }
+
+    fn times(self, count: usize) -> Size {
+        match self {
+            Size::Fixed(len) => Size::Fixed(len.saturating_mul(count)),
+            Size::Variable => Size::Variable,
+        }
+    }
+}
+
+impl TypeSystem {
+    /// Computes size of the serialized data of a type, if it doesn't depend on
+    /// the value. This is the case for types which don't contain unicode
+    /// characters, collections with variable number of items or unions with
+    /// variants of different size. Recursive types are always considered to
+    /// have a variable size.
+    pub fn static_size(&self, sem_id: SemId) -> Result<Size, UnknownType> {
+        self.static_size_inner(sem_id, &mut vec![])
+    }
+
+    fn static_size_inner(
+        &self,
+        sem_id: SemId,
+        parents: &mut Vec<SemId>,
+    ) -> Result<Size, UnknownType> {
+        if parents.contains(&sem_id) {
+            return Ok(Size::Variable);
+        }
+        let ty = self.find(sem_id).ok_or(UnknownType(sem_id))?;
+        parents.push(sem_id);
+        let size = self.static_size_ty(ty, parents);
+        parents.pop();
+        size
+    }
+
+    fn static_size_ty(
+        &self,
+        ty: &Ty<SemId>,
+        parents: &mut Vec<SemId>,
+    ) -> Result<Size, UnknownType> {
+        let mut static_size = |sem_id: SemId| self.static_size_inner(sem_id, parents);
+        Ok(match ty {
+            Ty::Primitive(prim) => Size::Fixed(prim.byte_size() as usize),
+            Ty::Enum(_) => Size::Fixed(1),
+            Ty::UnicodeChar => Size::Variable,
+            Ty::Union(variants) => {
+                let mut sizes = variants.values().map(|ty| static_size(*ty));
+                let first = sizes.next().transpose()?.unwrap_or(Size::Fixed(0));
+                let mut size = first;
+                for other in sizes {
+                    if other? != first {
+                        size = Size::Variable;
+                    }
+                }
+                Size::Fixed(1) + size
+            }
+            Ty::Tuple(fields) => {
+                fields.iter().map(|ty| static_size(*ty)).sum::<Result<Size, _>>()?
+            }
+            Ty::Struct(fields) => {
+                fields.iter().map(|field| static_size(field.ty)).sum::<Result<Size, _>>()?
+            }
+            Ty::Array(ty, len) => static_size(*ty)?.times(*len as usize),
+            Ty::List(ty, sizing) | Ty::Set(ty, sizing) if sizing.min == sizing.max => {
+                Size::Fixed(sizing.byte_size()) + static_size(*ty)?.times(sizing.min as usize)
+            }
+            Ty::Map(key, ty, sizing) if sizing.min == sizing.max => {
+                let entry = static_size(*key)? + static_size(*ty)?;
+                Size::Fixed(sizing.byte_size()) + entry.times(sizing.min as usize)
+            }
+            Ty::List(..) | Ty::Set(..) | Ty::Map(..) => Size::Variable,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::value::test_helpers::*;
+
+    #[test]
+    fn static_size() {
+        let sys = test_system();
+        let size =
+            |name: &'static str| sys.as_types().static_size(sys.to_sem_id(name).unwrap()).unwrap();
+        assert_eq!(size("TestLib.Precision"), Size::Fixed(1));
+        assert_eq!(size("TestLib.Amounts"), Size::Fixed(3 + 16 + 32 + 32 + 64 + 128));
+        assert_eq!(size("TestLib.Reading"), Size::Fixed(2 + 2 + 4 + 8 + 10 + 16 + 32));
+        assert_eq!(size("TestLib.Nominal"), Size::Variable);
+        assert_eq!(size("TestLib.Tree"), Size::Variable);
+        assert_eq!(size("TestLib.Expr"), Size::Variable);
+        assert!(Size::Fixed(u16::MAX as usize) < Size::Variable);
+        assert_eq!([Size::Fixed(1), Size::Fixed(2)].into_iter().sum::<Size>(), Size::Fixed(3));
+    }
+}
@@ -35,6 +35,7 @@ impl TypeInfo {
             fqn,
             item,
             nested,
+            back_ref,
             ..
         } = self;
 
@@ -142,6 +143,9 @@ impl TypeInfo {
         if let Some(ItemCase::UnionVariant(ref pos, _)) = item {
             attributes.push(Attr::Tag(*pos));
         }
+        if *back_ref {
+            attributes.push(Attr::Recursive);
+        }
 
         TypeVesper {
             subject,
@@ -98,6 +98,7 @@ pub enum Attr {
     EnumVariant(u8, Ident),
     Len(u16),
     LenRange(LenRange),
+    Recursive,
 }
 
 impl Attribute for Attr {
@@ -116,6 +117,7 @@ impl Attribute for Attr {
             Attr::AsciiFirst(_) => Some(ident!("first")),
             Attr::AsciiRest(_) => Some(ident!("rest")),
             Attr::EnumVariant(_, name) => Some(name.clone()),
+            Attr::Recursive => None,
         }
     }
 
@@ -131,6 +133,7 @@ impl Attribute for Attr {
             Attr::AsciiFirst(name) => AttrVal::Ident(name.clone()),
             Attr::AsciiRest(name) => AttrVal::Ident(name.clone()),
             Attr::EnumVariant(pos, _) => AttrVal::Expr(AttrExpr::Tag(*pos)),
+            Attr::Recursive => AttrVal::Ident(ident!("recursive")),
         }
     }
 }
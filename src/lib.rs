@@ -57,15 +57,25 @@ pub mod typesys;
 pub mod value;
 pub mod stl;
 pub mod layout;
+pub mod codegen;
+pub mod compat;
 
 pub use ast::{Cls, PrimitiveRef, SemId, Translate, Ty, TypeRef};
+pub use layout::{LayoutId, Size};
 pub use typelib::{
-    CompileError, Dependency, LibBuilder, LibRef, SymbolRef, SymbolicLib, TranspileError,
-    TranspileRef, TypeLib, TypeLibId,
+    CompileError, Dependency, LibBuilder, LibRef, LibResolver, LoadError, SymbolRef, SymbolicLib,
+    TranspileError, TranspileRef, TypeLib, TypeLibId,
+};
+pub use typesys::{SymbolicSys, SysFileError, SystemBuilder, TypeSymbol, TypeSysId, TypeSystem};
+#[allow(deprecated)]
+pub use util::parse_args;
+pub use util::{
+    ArgsError, BuildFragment, PreFragment, SemVer, StlFormat, StsFormat, UnknownFormat, Urn,
+};
+pub use value::{
+    decode, diff, encode, random, ston, typify, visit, Change, KeyStep, Patch, Path, PathError,
+    PathParseError, Step, StrictVal,
 };
-pub use typesys::{SymbolicSys, SystemBuilder, TypeSymbol, TypeSysId, TypeSystem};
-pub use util::{parse_args, BuildFragment, PreFragment, SemVer, StlFormat, UnknownFormat, Urn};
-pub use value::{decode, ston, typify, KeyStep, Path, PathError, Step, StrictVal};
 
 pub trait CommitConsume {
     fn commit_consume(&mut self, data: impl AsRef<[u8]>);
@@ -84,10 +84,22 @@ impl From<TranspileError> for CompileError {
 pub struct NestedContext {
     pub top_name: TypeName,
     pub index: TypeIndex,
+    /// Back-reference markers for the types from the recursion group of the compiled type.
+    pub back_refs: TypeIndex,
     pub extern_types: ExternTypes,
     pub stack: Vec<String>,
 }
 
+impl NestedContext {
+    fn resolve(&self, name: &TypeName) -> Result<SemId, CompileError> {
+        self.back_refs
+            .get(name)
+            .or_else(|| self.index.get(name))
+            .copied()
+            .ok_or(CompileError::Continue)
+    }
+}
+
 impl Translate<LibRef> for TranspileRef {
     type Context = ();
     type Builder = NestedContext;
@@ -105,10 +117,7 @@ impl Translate<LibRef> for TranspileRef {
                 builder.stack.pop();
                 res
             }
-            TranspileRef::Named(name) => {
-                let id = builder.index.get(&name).ok_or(CompileError::Continue)?;
-                Ok(LibRef::Named(*id))
-            }
+            TranspileRef::Named(name) => builder.resolve(&name).map(LibRef::Named),
             TranspileRef::Extern(ext) => Ok(LibRef::Extern(ext.into())),
         }
     }
@@ -131,10 +140,7 @@ impl Translate<InlineRef> for TranspileRef {
                 builder.stack.pop();
                 res
             }
-            TranspileRef::Named(name) => {
-                let id = builder.index.get(&name).ok_or(CompileError::Continue)?;
-                Ok(InlineRef::Named(*id))
-            }
+            TranspileRef::Named(name) => builder.resolve(&name).map(InlineRef::Named),
             TranspileRef::Extern(ext) => Ok(InlineRef::Extern(ext.into())),
         }
     }
@@ -157,10 +163,7 @@ impl Translate<InlineRef1> for TranspileRef {
                 builder.stack.pop();
                 res
             }
-            TranspileRef::Named(name) => {
-                let id = builder.index.get(&name).ok_or(CompileError::Continue)?;
-                Ok(InlineRef1::Named(*id))
-            }
+            TranspileRef::Named(name) => builder.resolve(&name).map(InlineRef1::Named),
             TranspileRef::Extern(ext) => Ok(InlineRef1::Extern(ext.into())),
         }
     }
@@ -182,10 +185,7 @@ impl Translate<InlineRef2> for TranspileRef {
                 let name = path.pop().unwrap_or_else(|| s!("<unnamed>"));
                 Err(CompileError::NestedInline(builder.top_name.clone(), path.join("."), name))
             }
-            TranspileRef::Named(name) => {
-                let id = builder.index.get(&name).ok_or(CompileError::Continue)?;
-                Ok(InlineRef2::Named(*id))
-            }
+            TranspileRef::Named(name) => builder.resolve(&name).map(InlineRef2::Named),
             TranspileRef::Extern(ext) => Ok(InlineRef2::Extern(ext.into())),
         }
     }
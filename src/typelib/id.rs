@@ -82,8 +82,7 @@ impl SemCommit for TypeLib {
             dep.sem_commit(hasher);
         }
         hasher.commit_consume(self.types.len_u16().to_le_bytes());
-        for (name, ty) in &self.types {
-            let sem_id = ty.sem_id_named(name);
+        for sem_id in self.type_ids().values() {
             sem_id.sem_commit(hasher);
         }
     }
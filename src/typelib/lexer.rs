@@ -0,0 +1,266 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Lexer for the strict type library source (`.sty`) files.
+
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use super::parse::{ParseError, ParseErrorKind};
+
+/// Location of a token or a syntax construct within the source text.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default, Display)]
+#[display("{line}:{col}")]
+pub struct Span {
+    /// Byte offset of the first character.
+    pub start: usize,
+    /// Byte offset right after the last character.
+    pub end: usize,
+    /// Line number, starting from 1.
+    pub line: u32,
+    /// Column number (in characters), starting from 1.
+    pub col: u32,
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Display)]
+pub enum Token {
+    #[display("identifier `{0}`")]
+    Ident(String),
+    #[display("number `{0}`")]
+    Number(u64),
+    #[display("`:`")]
+    Colon,
+    #[display("`,`")]
+    Comma,
+    #[display("`|`")]
+    Pipe,
+    #[display("`#`")]
+    Hash,
+    #[display("`^`")]
+    Caret,
+    #[display("`.`")]
+    Dot,
+    #[display("`..`")]
+    Range,
+    #[display("`->`")]
+    Arrow,
+    #[display("`-`")]
+    Dash,
+    #[display("`?`")]
+    Question,
+    #[display("`@`")]
+    At,
+    #[display("`(`")]
+    LParen,
+    #[display("`)`")]
+    RParen,
+    #[display("`[`")]
+    LBracket,
+    #[display("`]`")]
+    RBracket,
+    #[display("`{{`")]
+    LBrace,
+    #[display("`}}`")]
+    RBrace,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Lexeme {
+    pub token: Token,
+    pub span: Span,
+    /// Whether the token is the first one on its line.
+    pub line_start: bool,
+}
+
+struct Lexer<'src> {
+    source: &'src str,
+    chars: Peekable<CharIndices<'src>>,
+    line: u32,
+    col: u32,
+    line_start: bool,
+}
+
+pub fn tokenize(source: &str) -> Result<Vec<Lexeme>, ParseError> {
+    let mut lexer = Lexer {
+        source,
+        chars: source.char_indices().peekable(),
+        line: 1,
+        col: 1,
+        line_start: true,
+    };
+    let mut lexemes = vec![];
+    while let Some(lexeme) = lexer.next_lexeme()? {
+        lexemes.push(lexeme);
+    }
+    Ok(lexemes)
+}
+
+impl Lexer<'_> {
+    fn bump(&mut self) -> Option<(usize, char)> {
+        let (pos, c) = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.col = 1;
+            self.line_start = true;
+        } else {
+            self.col += 1;
+        }
+        Some((pos, c))
+    }
+
+    fn peek(&mut self) -> Option<char> { self.chars.peek().map(|(_, c)| *c) }
+
+    fn peek2(&self) -> Option<char> {
+        let mut iter = self.chars.clone();
+        iter.next();
+        iter.next().map(|(_, c)| c)
+    }
+
+    fn offset(&mut self) -> usize {
+        self.chars.peek().map(|(pos, _)| *pos).unwrap_or(self.source.len())
+    }
+
+    fn span_from(&mut self, start: usize, line: u32, col: u32) -> Span {
+        Span {
+            start,
+            end: self.offset(),
+            line,
+            col,
+        }
+    }
+
+    fn skip_trivia(&mut self) -> Result<(), ParseError> {
+        loop {
+            match (self.peek(), self.peek2()) {
+                (Some(c), _) if c.is_whitespace() => {
+                    self.bump();
+                }
+                (Some('-'), Some('-')) => {
+                    while let Some(c) = self.peek() {
+                        if c == '\n' {
+                            break;
+                        }
+                        self.bump();
+                    }
+                }
+                (Some('{'), Some('-')) => {
+                    let (line, col) = (self.line, self.col);
+                    let start = self.offset();
+                    self.bump();
+                    self.bump();
+                    loop {
+                        match (self.peek(), self.peek2()) {
+                            (Some('-'), Some('}')) => {
+                                self.bump();
+                                self.bump();
+                                break;
+                            }
+                            (Some(_), _) => {
+                                self.bump();
+                            }
+                            (None, _) => {
+                                return Err(ParseError::new(
+                                    self.span_from(start, line, col),
+                                    ParseErrorKind::UnterminatedComment,
+                                ))
+                            }
+                        }
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn next_lexeme(&mut self) -> Result<Option<Lexeme>, ParseError> {
+        self.skip_trivia()?;
+        let (line, col) = (self.line, self.col);
+        let line_start = self.line_start;
+        let Some((start, c)) = self.bump() else {
+            return Ok(None);
+        };
+        self.line_start = false;
+
+        let token = match c {
+            ':' => Token::Colon,
+            ',' => Token::Comma,
+            '|' => Token::Pipe,
+            '#' => Token::Hash,
+            '^' => Token::Caret,
+            '?' => Token::Question,
+            '@' => Token::At,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            '{' => Token::LBrace,
+            '}' => Token::RBrace,
+            '.' if self.peek() == Some('.') => {
+                self.bump();
+                Token::Range
+            }
+            '.' => Token::Dot,
+            '-' if self.peek() == Some('>') => {
+                self.bump();
+                Token::Arrow
+            }
+            '-' => Token::Dash,
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                while matches!(self.peek(), Some(c) if c.is_ascii_alphanumeric() || c == '_') {
+                    self.bump();
+                }
+                let end = self.offset();
+                Token::Ident(self.source[start..end].to_owned())
+            }
+            c if c.is_ascii_digit() => {
+                while matches!(self.peek(), Some(c) if c.is_ascii_alphanumeric() || c == '_') {
+                    self.bump();
+                }
+                let end = self.offset();
+                let literal = &self.source[start..end];
+                let parsed = match literal.strip_prefix("0x") {
+                    Some(hex) => u64::from_str_radix(hex, 16),
+                    None => literal.parse(),
+                };
+                let no = parsed.map_err(|_| {
+                    ParseError::new(
+                        self.span_from(start, line, col),
+                        ParseErrorKind::InvalidNumber(literal.to_owned()),
+                    )
+                })?;
+                Token::Number(no)
+            }
+            other => {
+                return Err(ParseError::new(
+                    self.span_from(start, line, col),
+                    ParseErrorKind::UnexpectedChar(other),
+                ))
+            }
+        };
+
+        Ok(Some(Lexeme {
+            token,
+            span: self.span_from(start, line, col),
+            line_start,
+        }))
+    }
+}
@@ -0,0 +1,296 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::str::FromStr;
+use std::{fs, io};
+
+use amplify::confinement::{Confined, U24 as U24MAX};
+use encoding::{DecodeError, DeserializeError, LibName, StrictDeserialize};
+
+use crate::typelib::{CompileError, ParseError};
+use crate::typesys::{self, SystemBuilder};
+use crate::{Dependency, StlFormat, SymbolicLib, TypeLib, TypeLibId, UnknownFormat};
+
+#[derive(Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum LoadError {
+    /// file name `{0}` has no extension specifying library format.
+    NoExtension(String),
+
+    #[from]
+    #[display(inner)]
+    UnknownFormat(UnknownFormat),
+
+    #[from]
+    #[display(inner)]
+    Io(io::Error),
+
+    /// invalid binary library data. Details: {0}
+    #[from]
+    Deserialize(DeserializeError),
+
+    #[cfg(feature = "armor")]
+    #[from]
+    #[display(inner)]
+    Armor(armor::StrictArmorError),
+
+    /// invalid library source. Details: {0}
+    #[from]
+    Parse(ParseError),
+
+    /// library source can't be compiled. Details: {0}
+    #[from]
+    Compile(CompileError),
+
+    /// the file states invalid library id `{0}`.
+    InvalidId(String),
+
+    /// the file states library id `{expected}`, while the actual library id is `{actual}`.
+    IdMismatch {
+        expected: TypeLibId,
+        actual: TypeLibId,
+    },
+}
+
+impl TypeLib {
+    /// Loads a library from a file, detecting the file format from the file extension.
+    ///
+    /// For the armored and source formats the id stated in the file is checked against the id of
+    /// the loaded library. Libraries in source format can be loaded only if they have no
+    /// dependencies; use [`TypeLib::load_with`] otherwise.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LoadError> { Self::load_with(path, None) }
+
+    /// Loads a library from a file, detecting the file format from the file extension.
+    ///
+    /// Libraries provided in `libs` are used to resolve dependencies of a library in source format
+    /// and are ignored for other formats.
+    pub fn load_with<'lib>(
+        path: impl AsRef<Path>,
+        libs: impl IntoIterator<Item = &'lib TypeLib>,
+    ) -> Result<Self, LoadError> {
+        let path = path.as_ref();
+        let ext = path
+            .extension()
+            .ok_or_else(|| LoadError::NoExtension(path.display().to_string()))?
+            .to_string_lossy();
+        match StlFormat::from_str(&ext)? {
+            StlFormat::Binary => {
+                // Reading the file first to report file system errors as such
+                let data = Confined::try_from(fs::read(path)?)
+                    .map_err(|err| DeserializeError::from(DecodeError::from(err)))?;
+                Ok(TypeLib::from_strict_serialized::<U24MAX>(data)?)
+            }
+            #[cfg(feature = "armor")]
+            StlFormat::Armored => Self::from_armored(&fs::read_to_string(path)?),
+            StlFormat::Source => Self::from_source(&fs::read_to_string(path)?, libs),
+        }
+    }
+
+    #[cfg(feature = "armor")]
+    fn from_armored(s: &str) -> Result<Self, LoadError> {
+        use armor::{AsciiArmor, StrictArmorError};
+
+        TypeLib::from_ascii_armored_str(s.trim()).map_err(|err| match err {
+            StrictArmorError::MismatchedId { expected, actual } => LoadError::IdMismatch {
+                expected: expected.parse().expect("armor ids are checked when parsed"),
+                actual: actual.parse().expect("library ids are always valid"),
+            },
+            err => err.into(),
+        })
+    }
+
+    fn from_source<'lib>(
+        s: &str,
+        libs: impl IntoIterator<Item = &'lib TypeLib>,
+    ) -> Result<Self, LoadError> {
+        let lib = SymbolicLib::from_source(s, libs)?.compile()?;
+        // Library id is stated in the header comment produced by `TypeLib::serialize`
+        let stated =
+            s.strip_prefix("{-").and_then(|s| s.split_once("-}")).and_then(|(header, _)| {
+                header.lines().find_map(|line| line.trim().strip_prefix("Id:").map(str::trim))
+            });
+        if let Some(stated) = stated {
+            let expected =
+                TypeLibId::from_str(stated).map_err(|_| LoadError::InvalidId(stated.to_owned()))?;
+            if expected != lib.id() {
+                return Err(LoadError::IdMismatch {
+                    expected,
+                    actual: lib.id(),
+                });
+            }
+        }
+        Ok(lib)
+    }
+}
+
+#[derive(Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum ResolveError {
+    /// unable to load library from `{0}`. Details: {1}
+    Load(String, LoadError),
+
+    /// dependency {dep} of library `{lib}` is not found.
+    Absent { lib: LibName, dep: Dependency },
+
+    #[display(inner)]
+    Import(typesys::Error),
+}
+
+/// Collection of compiled libraries, resolving library dependencies.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct LibResolver {
+    libs: BTreeMap<TypeLibId, TypeLib>,
+}
+
+impl LibResolver {
+    pub fn new() -> Self { Self::default() }
+
+    /// Loads all libraries in binary and armored formats from a directory. Libraries in source
+    /// format are skipped, since they require their dependencies for compilation.
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Self, ResolveError> {
+        let dir = dir.as_ref();
+        let load_err = |err: io::Error| ResolveError::Load(dir.display().to_string(), err.into());
+
+        let mut resolver = Self::new();
+        for entry in fs::read_dir(dir).map_err(load_err)? {
+            let path = entry.map_err(load_err)?.path();
+            let format =
+                path.extension().and_then(|ext| StlFormat::from_str(&ext.to_string_lossy()).ok());
+            if !path.is_file() || matches!(format, None | Some(StlFormat::Source)) {
+                continue;
+            }
+            let lib = TypeLib::load(&path)
+                .map_err(|err| ResolveError::Load(path.display().to_string(), err))?;
+            resolver.add(lib);
+        }
+        Ok(resolver)
+    }
+
+    pub fn add(&mut self, lib: TypeLib) { self.libs.insert(lib.id(), lib); }
+
+    pub fn get(&self, id: TypeLibId) -> Option<&TypeLib> { self.libs.get(&id) }
+
+    /// Iterates over all known libraries, ordered by their ids.
+    pub fn libs(&self) -> impl Iterator<Item = &TypeLib> { self.libs.values() }
+
+    /// Lists all direct and indirect dependencies of the library, such that each library goes
+    /// after all of its own dependencies.
+    pub fn resolve(&self, lib: &TypeLib) -> Result<Vec<&TypeLib>, ResolveError> {
+        let mut resolved = vec![];
+        self.resolve_inner(lib, &mut resolved, &mut empty!())?;
+        Ok(resolved)
+    }
+
+    fn resolve_inner<'lib>(
+        &'lib self,
+        lib: &TypeLib,
+        resolved: &mut Vec<&'lib TypeLib>,
+        visited: &mut BTreeSet<TypeLibId>,
+    ) -> Result<(), ResolveError> {
+        for dep in &lib.dependencies {
+            if !visited.insert(dep.id) {
+                continue;
+            }
+            let dep_lib = self.get(dep.id).ok_or_else(|| ResolveError::Absent {
+                lib: lib.name.clone(),
+                dep: dep.clone(),
+            })?;
+            self.resolve_inner(dep_lib, resolved, visited)?;
+            resolved.push(dep_lib);
+        }
+        Ok(())
+    }
+
+    /// Imports the library together with all its dependencies into a type system builder.
+    pub fn import(
+        &self,
+        mut builder: SystemBuilder,
+        lib: TypeLib,
+    ) -> Result<SystemBuilder, ResolveError> {
+        for dep in self.resolve(&lib)? {
+            builder = builder.import(dep.clone()).map_err(ResolveError::Import)?;
+        }
+        builder.import(lib).map_err(ResolveError::Import)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::stl::{std_stl, strict_types_stl};
+
+    #[test]
+    fn load() {
+        let std = std_stl();
+        let lib = strict_types_stl();
+        assert_eq!(TypeLib::load("stl/Std@0.1.0.stl").unwrap(), std);
+        assert_eq!(TypeLib::load("stl/Std@0.1.0.sty").unwrap(), std);
+        assert_eq!(TypeLib::load("stl/StrictTypes@0.2.0.stl").unwrap(), lib);
+        assert_eq!(TypeLib::load_with("stl/StrictTypes@0.2.0.sty", [&std]).unwrap(), lib);
+        #[cfg(feature = "armor")]
+        assert_eq!(TypeLib::load("stl/StrictTypes@0.2.0.sta").unwrap(), lib);
+
+        assert!(matches!(TypeLib::load("stl/TypeLib.vesper"), Err(LoadError::UnknownFormat(_))));
+        assert!(matches!(TypeLib::load("stl/StrictTypes@0.2.0.sty"), Err(LoadError::Parse(_))));
+    }
+
+    #[test]
+    fn id_mismatch() {
+        let std = std_stl();
+        let lib = strict_types_stl();
+        let src = fs::read_to_string("stl/Std@0.1.0.sty")
+            .unwrap()
+            .replace(&std.id().to_string(), &lib.id().to_string());
+        assert!(matches!(
+            TypeLib::from_source(&src, None),
+            Err(LoadError::IdMismatch { expected, actual }) if expected == lib.id() && actual == std.id()
+        ));
+
+        #[cfg(feature = "armor")]
+        {
+            let armored = fs::read_to_string("stl/Std@0.1.0.sta")
+                .unwrap()
+                .replace(&std.id().to_string(), &lib.id().to_string());
+            assert!(matches!(
+                TypeLib::from_armored(&armored),
+                Err(LoadError::IdMismatch { expected, actual }) if expected == lib.id() && actual == std.id()
+            ));
+        }
+    }
+
+    #[test]
+    fn resolve() {
+        let std = std_stl();
+        let lib = strict_types_stl();
+        let resolver = LibResolver::from_dir("stl").unwrap();
+        assert_eq!(resolver.resolve(&lib).unwrap(), vec![&std]);
+        assert!(resolver.resolve(&std).unwrap().is_empty());
+        resolver.import(SystemBuilder::new(), lib.clone()).unwrap().finalize().unwrap();
+
+        let err = LibResolver::new().resolve(&lib).unwrap_err();
+        assert!(
+            matches!(err, ResolveError::Absent { lib: name, dep } if name == lib.name && dep.id == std.id())
+        );
+    }
+}
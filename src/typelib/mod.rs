@@ -27,12 +27,18 @@ mod serialize;
 mod transpile;
 mod symbolic;
 mod translate;
+mod lexer;
+mod parse;
+mod load;
 
 pub(crate) use compile::NestedContext;
 #[allow(deprecated)]
 pub use compile::TranslateError;
 pub use compile::{CompileError, TypeIndex};
 pub use id::TypeLibId;
+pub use lexer::Span;
+pub use load::{LibResolver, LoadError, ResolveError};
+pub use parse::{ParseError, ParseErrorKind};
 pub use symbolic::{ExternTypes, SymbolRef, SymbolicLib, TranspileError, TranspileRef};
 use translate::SymbolContext;
 pub use translate::SymbolError;
@@ -42,5 +48,6 @@ pub use type_lib::{
     Dependency, ExternRef, InlineRef, InlineRef1, InlineRef2, LibRef, LibSubref, TypeLib,
 };
 
+#[allow(deprecated)]
 #[deprecated(since = "1.3.0", note = "import from the crate root")]
 pub use super::parse_args;
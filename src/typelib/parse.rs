@@ -0,0 +1,1024 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parser for the strict type library source (`.sty`) files, reconstructing
+//! [`SymbolicLib`] from the text produced by its `Display` implementation.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use amplify::confinement::Confined;
+use baid64::DisplayBaid64;
+use encoding::{FieldName, InvalidRString, LibName, Primitive, Sizing, TypeName, Variant};
+use strict_encoding::VariantName;
+
+use super::lexer::{tokenize, Lexeme, Span, Token};
+use crate::ast::{EnumVariants, Field, NamedFields, UnionVariants, UnnamedFields};
+use crate::{Dependency, SemId, SymbolRef, SymbolicLib, TranspileRef, Ty, TypeLib, TypeLibId};
+
+const PRIMITIVES: [Primitive; 39] = [
+    Primitive::BYTE,
+    Primitive::U8,
+    Primitive::U16,
+    Primitive::U24,
+    Primitive::U32,
+    Primitive::U40,
+    Primitive::U48,
+    Primitive::U56,
+    Primitive::U64,
+    Primitive::U128,
+    Primitive::U160,
+    Primitive::U256,
+    Primitive::U512,
+    Primitive::U1024,
+    Primitive::I8,
+    Primitive::I16,
+    Primitive::I24,
+    Primitive::I32,
+    Primitive::I40,
+    Primitive::I48,
+    Primitive::I56,
+    Primitive::I64,
+    Primitive::I128,
+    Primitive::I256,
+    Primitive::I512,
+    Primitive::I1024,
+    Primitive::N8,
+    Primitive::N16,
+    Primitive::N24,
+    Primitive::N32,
+    Primitive::N48,
+    Primitive::N64,
+    Primitive::N128,
+    Primitive::F16B,
+    Primitive::F16,
+    Primitive::F32,
+    Primitive::F64,
+    Primitive::F80,
+    Primitive::F128,
+];
+
+/// Error parsing strict type library source.
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display("{span}: {kind}")]
+pub struct ParseError {
+    /// Position in the source text where the error was detected.
+    pub span: Span,
+    /// Details of the error.
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    pub fn new(span: Span, kind: ParseErrorKind) -> Self { ParseError { span, kind } }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Display)]
+#[display(doc_comments)]
+pub enum ParseErrorKind {
+    /// unexpected character `{0}`.
+    UnexpectedChar(char),
+
+    /// block comment is not terminated.
+    UnterminatedComment,
+
+    /// invalid number literal `{0}`.
+    InvalidNumber(String),
+
+    /// number {0} is too large for {1}.
+    NumberOverflow(u64, &'static str),
+
+    /// unexpected {found} while expecting {expected}.
+    Unexpected {
+        found: Token,
+        expected: &'static str,
+    },
+
+    /// unexpected end of file while expecting {0}.
+    UnexpectedEof(&'static str),
+
+    /// invalid name: {0}
+    InvalidName(InvalidRString),
+
+    /// type `{0}` is defined more than once.
+    RepeatedType(TypeName),
+
+    /// library `{0}` is imported more than once.
+    RepeatedImport(LibName),
+
+    /// field `{0}` is used more than once.
+    RepeatedField(FieldName),
+
+    /// variant `{0}` is used more than once.
+    RepeatedVariant(VariantName),
+
+    /// tag {0} is used by more than one variant.
+    RepeatedTag(u8),
+
+    /// library `{0}` is not known; please provide it as a dependency.
+    UnknownLib(LibName),
+
+    /// library `{0}` is used without being imported.
+    NotImported(LibName),
+
+    /// library `{name}` is imported as `{expected}`, but the provided library
+    /// has mnemonic `{found}`.
+    LibMismatch {
+        name: LibName,
+        expected: String,
+        found: String,
+    },
+
+    /// type `{ty}` is not defined in library `{lib}`.
+    UnknownExtern { lib: LibName, ty: TypeName },
+
+    /// unknown type `{0}`.
+    UnknownType(String),
+
+    /// type `{name}` has mnemonic `{found}`, which doesn't match the declared
+    /// `{expected}`.
+    MnemonicMismatch {
+        name: TypeName,
+        expected: String,
+        found: String,
+    },
+
+    /// invalid sizing: minimum {min} exceeds maximum {max}.
+    InvalidSizing { min: u64, max: u64 },
+
+    /// type definition mixes `,` and `|` separators.
+    MixedSeparators,
+
+    /// variants with and without associated types can't be mixed.
+    MixedVariants,
+
+    /// named and unnamed fields can't be mixed.
+    MixedFields,
+
+    /// union or enum variant must have a name.
+    UnnamedVariant,
+
+    /// variant tag can't be used outside of unions and enums.
+    MisplacedTag,
+
+    /// unit type can't be used as a field type.
+    UnitField,
+
+    /// type definition contains too many {0}.
+    TooMany(&'static str),
+}
+
+#[derive(Clone, Debug)]
+enum ElementTy {
+    Unit,
+    Inline(Ty<TranspileRef>),
+    Field(FieldName, TranspileRef),
+    Plain(TranspileRef),
+}
+
+#[derive(Clone, Debug)]
+struct Element {
+    span: Span,
+    name: Option<String>,
+    tag: Option<u8>,
+    ty: Option<ElementTy>,
+}
+
+impl ElementTy {
+    fn into_variant_ty(self) -> TranspileRef {
+        match self {
+            ElementTy::Unit => TranspileRef::unit(),
+            ElementTy::Inline(ty) => ty.into(),
+            ElementTy::Field(name, ty) => {
+                Ty::Struct(NamedFields::try_from(vec![Field { name, ty }]).expect("single field"))
+                    .into()
+            }
+            ElementTy::Plain(ty) => {
+                Ty::Tuple(UnnamedFields::try_from(vec![ty]).expect("single field")).into()
+            }
+        }
+    }
+
+    fn into_field_ty(self) -> Result<TranspileRef, ParseErrorKind> {
+        match self {
+            ElementTy::Unit => Err(ParseErrorKind::UnitField),
+            ElementTy::Inline(ty) => Ok(ty.into()),
+            ElementTy::Field(..) => Err(ParseErrorKind::MixedFields),
+            ElementTy::Plain(ty) => Ok(ty),
+        }
+    }
+}
+
+struct Parser<'lib> {
+    lexemes: Vec<Lexeme>,
+    pos: usize,
+    eof: Span,
+    libs: BTreeMap<LibName, &'lib TypeLib>,
+    imports: BTreeMap<LibName, (TypeLibId, &'lib TypeLib)>,
+    local: BTreeSet<TypeName>,
+    extern_types: BTreeMap<LibName, BTreeMap<SemId, TypeName>>,
+}
+
+impl SymbolicLib {
+    /// Parses library source in the `.sty` format.
+    ///
+    /// Since the source references its dependencies and external types by
+    /// mnemonics only, all libraries imported by the source must be provided
+    /// via `libs` argument.
+    pub fn from_source<'lib>(
+        source: &str,
+        libs: impl IntoIterator<Item = &'lib TypeLib>,
+    ) -> Result<SymbolicLib, ParseError> {
+        let lexemes = tokenize(source)?;
+        let eof = lexemes
+            .last()
+            .map(|lexeme| Span {
+                start: lexeme.span.end,
+                ..lexeme.span
+            })
+            .unwrap_or_default();
+        let parser = Parser {
+            lexemes,
+            pos: 0,
+            eof,
+            libs: libs.into_iter().map(|lib| (lib.name.clone(), lib)).collect(),
+            imports: empty!(),
+            local: empty!(),
+            extern_types: empty!(),
+        };
+        parser.parse_lib()
+    }
+}
+
+fn parse_name<T>(name: String, span: Span) -> Result<T, ParseError>
+where T: TryFrom<String, Error = InvalidRString> {
+    T::try_from(name).map_err(|err| ParseError::new(span, ParseErrorKind::InvalidName(err)))
+}
+
+/// Returns the other possible interpretation of a type, which has the same
+/// textual representation: a newtype wrapping the type, or - if the type is
+/// itself a newtype over an inline type - the wrapped type.
+fn alt_newtype(ty: &Ty<TranspileRef>) -> Ty<TranspileRef> {
+    match ty {
+        Ty::Tuple(fields) if fields.len() == 1 => {
+            if let TranspileRef::Embedded(inner) = &fields[0] {
+                return inner.as_ref().clone();
+            }
+        }
+        _ => {}
+    }
+    Ty::Tuple(UnnamedFields::try_from(vec![ty.clone().into()]).expect("single field"))
+}
+
+/// Maximal number of ambiguous fixed-size collections within a single type definition, for
+/// which all the interpretations are tried when matching the type mnemonic.
+const MAX_AMBIGUOUS: u32 = 10;
+
+/// Fixed-size lists are displayed exactly as arrays of the same length. Returns the type with
+/// the interpretation of the ambiguous collections flipped for those which position (in the
+/// order of traversal, counted with `pos`) is set in `mask`.
+fn flip_fixed(ty: &Ty<TranspileRef>, mask: u32, pos: &mut u32) -> Ty<TranspileRef> {
+    let mut flip_ref = |r: &TranspileRef| match r {
+        TranspileRef::Embedded(ty) => TranspileRef::from(flip_fixed(ty, mask, pos)),
+        r => r.clone(),
+    };
+    let ty = match ty {
+        Ty::Union(variants) => {
+            let variants = variants.iter().map(|(var, r)| (var.clone(), flip_ref(r)));
+            Ty::Union(UnionVariants::try_from(variants.collect::<BTreeMap<_, _>>()).expect("same"))
+        }
+        Ty::Tuple(fields) => {
+            let fields = fields.iter().map(flip_ref).collect::<Vec<_>>();
+            Ty::Tuple(UnnamedFields::try_from(fields).expect("same size"))
+        }
+        Ty::Struct(fields) => {
+            let fields = fields
+                .iter()
+                .map(|field| Field {
+                    name: field.name.clone(),
+                    ty: flip_ref(&field.ty),
+                })
+                .collect::<Vec<_>>();
+            Ty::Struct(NamedFields::try_from(fields).expect("same size"))
+        }
+        Ty::Array(r, len) => Ty::Array(flip_ref(r), *len),
+        Ty::List(r, sizing) => Ty::List(flip_ref(r), *sizing),
+        Ty::Set(r, sizing) => Ty::Set(flip_ref(r), *sizing),
+        Ty::Map(key, r, sizing) => Ty::Map(flip_ref(key), flip_ref(r), *sizing),
+        ty @ (Ty::Primitive(_) | Ty::UnicodeChar | Ty::Enum(_)) => ty.clone(),
+    };
+    let ambiguous = match &ty {
+        Ty::Array(..) => true,
+        Ty::List(_, sizing) => sizing.min == sizing.max && sizing.max <= u16::MAX as u64,
+        _ => false,
+    };
+    if !ambiguous {
+        return ty;
+    }
+    let flip = *pos < u32::BITS && mask & (1 << *pos) != 0;
+    *pos += 1;
+    match ty {
+        Ty::Array(r, len) if flip => Ty::List(r, Sizing::fixed(len as u64)),
+        Ty::List(r, sizing) if flip => Ty::Array(r, sizing.max as u16),
+        ty => ty,
+    }
+}
+
+fn option(ty: TranspileRef) -> TranspileRef {
+    let some = Ty::Tuple(UnnamedFields::try_from(vec![ty]).expect("single field"));
+    let variants = bmap! {
+        Variant::none() => TranspileRef::unit(),
+        Variant::some() => TranspileRef::from(some)
+    };
+    Ty::Union(UnionVariants::try_from(variants).expect("two variants")).into()
+}
+
+impl<'lib> Parser<'lib> {
+    fn peek(&self) -> Option<&Lexeme> { self.lexemes.get(self.pos) }
+
+    fn peek_nth(&self, n: usize) -> Option<&Lexeme> { self.lexemes.get(self.pos + n) }
+
+    fn is_token(&self, token: &Token) -> bool { self.peek().map(|l| &l.token) == Some(token) }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Lexeme { token: Token::Ident(ident), .. }) if ident == keyword)
+    }
+
+    /// Checks whether the `n`-th lexeme from the current position may start a
+    /// type which continues the current line.
+    fn is_type_start(&self, n: usize) -> bool {
+        matches!(
+            self.peek_nth(n),
+            Some(Lexeme {
+                token: Token::Ident(_) | Token::LParen | Token::LBracket | Token::LBrace,
+                line_start: false,
+                ..
+            })
+        )
+    }
+
+    /// Checks whether the current position ends a compound type definition.
+    fn is_end(&self, top: bool) -> bool {
+        match self.peek() {
+            None => true,
+            Some(Lexeme {
+                token: Token::RParen,
+                ..
+            }) => !top,
+            Some(Lexeme {
+                token: Token::At,
+                line_start: true,
+                ..
+            }) => top,
+            Some(Lexeme {
+                token: Token::Ident(ident),
+                line_start: true,
+                ..
+            }) => top && ident == "data",
+            Some(_) => false,
+        }
+    }
+
+    fn span(&self) -> Span { self.peek().map(|l| l.span).unwrap_or(self.eof) }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.is_token(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn next(&mut self, expected: &'static str) -> Result<Lexeme, ParseError> {
+        let lexeme = self
+            .peek()
+            .cloned()
+            .ok_or_else(|| ParseError::new(self.eof, ParseErrorKind::UnexpectedEof(expected)))?;
+        self.pos += 1;
+        Ok(lexeme)
+    }
+
+    fn expect(&mut self, token: Token, expected: &'static str) -> Result<Span, ParseError> {
+        let lexeme = self.next(expected)?;
+        if lexeme.token != token {
+            return Err(unexpected(lexeme, expected));
+        }
+        Ok(lexeme.span)
+    }
+
+    fn ident(&mut self, expected: &'static str) -> Result<(String, Span), ParseError> {
+        match self.next(expected)? {
+            Lexeme {
+                token: Token::Ident(ident),
+                span,
+                ..
+            } => Ok((ident, span)),
+            lexeme => Err(unexpected(lexeme, expected)),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &'static str) -> Result<Span, ParseError> {
+        let (ident, span) = self.ident(keyword)?;
+        if ident != keyword {
+            return Err(ParseError::new(span, ParseErrorKind::Unexpected {
+                found: Token::Ident(ident),
+                expected: keyword,
+            }));
+        }
+        Ok(span)
+    }
+
+    fn number(&mut self, expected: &'static str) -> Result<(u64, Span), ParseError> {
+        match self.next(expected)? {
+            Lexeme {
+                token: Token::Number(no),
+                span,
+                ..
+            } => Ok((no, span)),
+            lexeme => Err(unexpected(lexeme, expected)),
+        }
+    }
+
+    fn mnemonic(&mut self) -> Result<String, ParseError> {
+        let (mut mnemonic, _) = self.ident("mnemonic")?;
+        while self.eat(&Token::Dash) {
+            let (word, _) = self.ident("mnemonic word")?;
+            mnemonic.push('-');
+            mnemonic.push_str(&word);
+        }
+        Ok(mnemonic)
+    }
+
+    fn parse_lib(mut self) -> Result<SymbolicLib, ParseError> {
+        if self.eat(&Token::At) {
+            self.keyword("context")?;
+        }
+        self.keyword("typelib")?;
+        let (name, span) = self.ident("library name")?;
+        let name: LibName = parse_name(name, span)?;
+
+        let mut dependencies = BTreeSet::new();
+        while self.is_keyword("import") {
+            self.pos += 1;
+            let (lib_name, span) = self.ident("library name")?;
+            let lib_name: LibName = parse_name(lib_name, span)?;
+            self.expect(Token::Hash, "`#`")?;
+            let mnemonic = self.mnemonic()?;
+            let lib = *self.libs.get(&lib_name).ok_or_else(|| {
+                ParseError::new(span, ParseErrorKind::UnknownLib(lib_name.clone()))
+            })?;
+            let id = lib.id();
+            let found = id.to_baid64_mnemonic();
+            if found != mnemonic {
+                return Err(ParseError::new(span, ParseErrorKind::LibMismatch {
+                    name: lib_name,
+                    expected: mnemonic,
+                    found,
+                }));
+            }
+            if self.imports.insert(lib_name.clone(), (id, lib)).is_some() {
+                return Err(ParseError::new(span, ParseErrorKind::RepeatedImport(lib_name)));
+            }
+            dependencies.insert(Dependency::with(id, lib_name.clone()));
+
+            while self.is_keyword("use") {
+                self.pos += 1;
+                let (ty_name, span) = self.ident("type name")?;
+                let ty_name: TypeName = parse_name(ty_name, span)?;
+                self.expect(Token::Hash, "`#`")?;
+                let expected = self.mnemonic()?;
+                let TranspileRef::Extern(symbol) =
+                    self.resolve_extern(lib_name.clone(), ty_name.clone(), span)?
+                else {
+                    unreachable!("extern resolution always returns extern reference")
+                };
+                let found = symbol.sem_id.to_baid64_mnemonic();
+                if found != expected {
+                    return Err(ParseError::new(span, ParseErrorKind::MnemonicMismatch {
+                        name: ty_name,
+                        expected,
+                        found,
+                    }));
+                }
+            }
+        }
+
+        for (no, lexeme) in self.lexemes.iter().enumerate().skip(self.pos) {
+            if !lexeme.line_start || lexeme.token != Token::Ident(s!("data")) {
+                continue;
+            }
+            if let Some(Lexeme {
+                token: Token::Ident(name),
+                span,
+                ..
+            }) = self.lexemes.get(no + 1)
+            {
+                let name: TypeName = parse_name(name.clone(), *span)?;
+                if !self.local.insert(name.clone()) {
+                    return Err(ParseError::new(*span, ParseErrorKind::RepeatedType(name)));
+                }
+            }
+        }
+
+        let mut types = BTreeMap::new();
+        while self.peek().is_some() {
+            let mut mnemonic = None;
+            if self.eat(&Token::At) {
+                self.keyword("mnemonic")?;
+                self.expect(Token::LParen, "`(`")?;
+                mnemonic = Some(self.mnemonic()?);
+                self.expect(Token::RParen, "`)`")?;
+            }
+            self.keyword("data")?;
+            let (ty_name, span) = self.ident("type name")?;
+            let ty_name: TypeName = parse_name(ty_name, span)?;
+            self.expect(Token::Colon, "`:`")?;
+            let mut ty = self.parse_compound(true)?;
+            if let Some(expected) = mnemonic {
+                let found = ty.sem_id_named(&ty_name).to_baid64_mnemonic();
+                if found != expected {
+                    // Newtypes wrapping inline types are displayed exactly as the wrapped
+                    // types themselves, and fixed-size lists - as arrays, so we use the mnemonic
+                    // to resolve the ambiguity.
+                    let mut ambiguous = 0;
+                    flip_fixed(&ty, 0, &mut ambiguous);
+                    let masks = if ambiguous <= MAX_AMBIGUOUS { 1 << ambiguous } else { 1 };
+                    let alt = (0..masks)
+                        .map(|mask| flip_fixed(&ty, mask, &mut 0))
+                        .flat_map(|ty| [alt_newtype(&ty), ty])
+                        .find(|alt| alt.sem_id_named(&ty_name).to_baid64_mnemonic() == expected);
+                    let Some(alt) = alt else {
+                        return Err(ParseError::new(span, ParseErrorKind::MnemonicMismatch {
+                            name: ty_name,
+                            expected,
+                            found,
+                        }));
+                    };
+                    ty = alt;
+                }
+            }
+            types.insert(ty_name, ty);
+        }
+
+        let too_many = |what| ParseError::new(self.eof, ParseErrorKind::TooMany(what));
+        let dependencies =
+            Confined::try_from(dependencies).map_err(|_| too_many("dependencies"))?;
+        let types = Confined::try_from(types).map_err(|_| too_many("types"))?;
+        let extern_types = Confined::try_from(
+            self.extern_types
+                .into_iter()
+                .map(|(lib, index)| {
+                    Confined::try_from(index)
+                        .map(|index| (lib, index))
+                        .map_err(|_| too_many("external types"))
+                })
+                .collect::<Result<BTreeMap<_, _>, _>>()?,
+        )
+        .map_err(|_| too_many("dependencies"))?;
+
+        Ok(SymbolicLib {
+            name,
+            dependencies,
+            extern_types,
+            types,
+        })
+    }
+
+    fn resolve_extern(
+        &mut self,
+        lib_name: LibName,
+        ty_name: TypeName,
+        span: Span,
+    ) -> Result<TranspileRef, ParseError> {
+        let (lib_id, lib) = self
+            .imports
+            .get(&lib_name)
+            .ok_or_else(|| ParseError::new(span, ParseErrorKind::NotImported(lib_name.clone())))?;
+        let sem_id = *lib.type_ids().get(&ty_name).ok_or_else(|| {
+            ParseError::new(span, ParseErrorKind::UnknownExtern {
+                lib: lib_name.clone(),
+                ty: ty_name.clone(),
+            })
+        })?;
+        let symbol = SymbolRef::with(lib_name.clone(), ty_name.clone(), *lib_id, sem_id);
+        self.extern_types.entry(lib_name).or_default().insert(sem_id, ty_name);
+        Ok(TranspileRef::Extern(symbol))
+    }
+
+    fn resolve_name(&self, name: &str) -> Option<TranspileRef> {
+        if let Ok(ty_name) = TypeName::try_from(name.to_owned()) {
+            if self.local.contains(&ty_name) {
+                return Some(TranspileRef::Named(ty_name));
+            }
+        }
+        if name == "Unicode" {
+            return Some(Ty::UNICODE.into());
+        }
+        PRIMITIVES
+            .into_iter()
+            .find(|prim| prim.to_string() == name)
+            .map(|prim| Ty::Primitive(prim).into())
+    }
+
+    /// Parses a list of elements separated by either `,` or `|`, which may
+    /// represent an enum, union, tuple or a structure.
+    fn parse_compound(&mut self, top: bool) -> Result<Ty<TranspileRef>, ParseError> {
+        let span = self.span();
+        let mut elements = vec![self.parse_element()?];
+        let mut separator = None;
+        while !self.is_end(top) {
+            let lexeme = self.next("separator")?;
+            match (&lexeme.token, &separator) {
+                (Token::Comma | Token::Pipe, None) => separator = Some(lexeme.token),
+                (token, Some(sep)) if token == sep => {}
+                (Token::Comma | Token::Pipe, Some(_)) => {
+                    return Err(ParseError::new(lexeme.span, ParseErrorKind::MixedSeparators))
+                }
+                _ => return Err(unexpected(lexeme, "`,`, `|` or end of type definition")),
+            }
+            elements.push(self.parse_element()?);
+        }
+
+        match separator {
+            Some(Token::Pipe) => self.build_variants(elements, span),
+            Some(_) => self.build_fields(elements, span),
+            None => {
+                let element = elements.pop().expect("at least one element");
+                match element {
+                    Element { tag: Some(_), .. } => self.build_variants(vec![element], span),
+                    Element {
+                        name: Some(_),
+                        ty: Some(_),
+                        ..
+                    } => self.build_fields(vec![element], span),
+                    Element {
+                        name: Some(ref name),
+                        ty: None,
+                        ..
+                    } if self.resolve_name(name).is_none() => {
+                        self.build_variants(vec![element], span)
+                    }
+                    Element {
+                        ty: Some(ElementTy::Plain(TranspileRef::Embedded(ref ty))),
+                        ..
+                    } if **ty == Ty::UNIT => Ok(Ty::UNIT),
+                    _ => self.build_fields(vec![element], span),
+                }
+            }
+        }
+    }
+
+    fn parse_element(&mut self) -> Result<Element, ParseError> {
+        let span = self.span();
+        let lexeme = self.peek().cloned();
+        let Some(Lexeme {
+            token: Token::Ident(name),
+            ..
+        }) = lexeme
+        else {
+            return Ok(Element {
+                span,
+                name: None,
+                tag: None,
+                ty: Some(ElementTy::Plain(self.parse_type()?)),
+            });
+        };
+
+        match self.peek_nth(1).map(|l| &l.token) {
+            Some(Token::Hash) => {
+                self.pos += 2;
+                let (tag, tag_span) = self.number("variant tag")?;
+                let tag = u8::try_from(tag).map_err(|_| {
+                    ParseError::new(tag_span, ParseErrorKind::NumberOverflow(tag, "variant tag"))
+                })?;
+                let ty = if self.is_type_start(0) { Some(self.parse_element_ty()?) } else { None };
+                Ok(Element {
+                    span,
+                    name: Some(name),
+                    tag: Some(tag),
+                    ty,
+                })
+            }
+            Some(Token::Dot | Token::Question) => Ok(Element {
+                span,
+                name: None,
+                tag: None,
+                ty: Some(ElementTy::Plain(self.parse_type()?)),
+            }),
+            _ if self.is_type_start(1) => {
+                self.pos += 1;
+                Ok(Element {
+                    span,
+                    name: Some(name),
+                    tag: None,
+                    ty: Some(self.parse_element_ty()?),
+                })
+            }
+            _ => {
+                self.pos += 1;
+                Ok(Element {
+                    span,
+                    name: Some(name),
+                    tag: None,
+                    ty: None,
+                })
+            }
+        }
+    }
+
+    fn parse_element_ty(&mut self) -> Result<ElementTy, ParseError> {
+        match self.peek().map(|l| &l.token) {
+            Some(Token::LParen) if self.peek_nth(1).map(|l| &l.token) == Some(&Token::RParen) => {
+                self.pos += 2;
+                Ok(ElementTy::Unit)
+            }
+            Some(Token::LParen) => {
+                self.pos += 1;
+                let ty = self.parse_compound(false)?;
+                self.expect(Token::RParen, "`)`")?;
+                Ok(ElementTy::Inline(ty))
+            }
+            Some(Token::Ident(_)) if self.is_type_start(1) => {
+                let (name, span) = self.ident("field name")?;
+                let name = parse_name(name, span)?;
+                Ok(ElementTy::Field(name, self.parse_type()?))
+            }
+            _ => Ok(ElementTy::Plain(self.parse_type()?)),
+        }
+    }
+
+    fn parse_sizing(&mut self) -> Result<Sizing, ParseError> {
+        let span = self.span();
+        let sizing = if self.eat(&Token::Range) {
+            Sizing::new(0, self.number("maximal size")?.0)
+        } else {
+            let (min, _) = self.number("size")?;
+            if !self.eat(&Token::Range) {
+                Sizing::fixed(min)
+            } else if matches!(self.peek().map(|l| &l.token), Some(Token::Number(_))) {
+                Sizing::new(min, self.number("maximal size")?.0)
+            } else {
+                Sizing::new(min, u16::MAX as u64)
+            }
+        };
+        if sizing.min > sizing.max {
+            return Err(ParseError::new(span, ParseErrorKind::InvalidSizing {
+                min: sizing.min,
+                max: sizing.max,
+            }));
+        }
+        Ok(sizing)
+    }
+
+    fn parse_type(&mut self) -> Result<TranspileRef, ParseError> {
+        let lexeme = self.next("type")?;
+        let mut ty = match lexeme.token {
+            Token::LParen if self.eat(&Token::RParen) => TranspileRef::unit(),
+            Token::LParen => {
+                let ty = self.parse_compound(false)?;
+                self.expect(Token::RParen, "`)`")?;
+                ty.into()
+            }
+            Token::LBracket => {
+                let item = self.parse_type()?;
+                let ty = if !self.eat(&Token::Caret) {
+                    Ty::List(item, Sizing::U16)
+                } else if let (
+                    Some(Lexeme {
+                        token: Token::Number(len),
+                        span,
+                        ..
+                    }),
+                    Some(Lexeme {
+                        token: Token::RBracket,
+                        ..
+                    }),
+                ) = (self.peek(), self.peek_nth(1))
+                {
+                    let len = u16::try_from(*len).map_err(|_| {
+                        ParseError::new(*span, ParseErrorKind::NumberOverflow(*len, "array length"))
+                    })?;
+                    self.pos += 1;
+                    Ty::Array(item, len)
+                } else {
+                    Ty::List(item, self.parse_sizing()?)
+                };
+                self.expect(Token::RBracket, "`]`")?;
+                ty.into()
+            }
+            Token::LBrace => {
+                let key = self.parse_type()?;
+                let ty = if self.eat(&Token::Arrow) {
+                    let sizing =
+                        if self.eat(&Token::Caret) { self.parse_sizing()? } else { Sizing::U16 };
+                    Ty::Map(key, self.parse_type()?, sizing)
+                } else {
+                    let sizing =
+                        if self.eat(&Token::Caret) { self.parse_sizing()? } else { Sizing::U16 };
+                    Ty::Set(key, sizing)
+                };
+                self.expect(Token::RBrace, "`}`")?;
+                ty.into()
+            }
+            Token::Ident(lib_name) if self.eat(&Token::Dot) => {
+                let lib_name = parse_name(lib_name, lexeme.span)?;
+                let (ty_name, span) = self.ident("type name")?;
+                let ty_name = parse_name(ty_name, span)?;
+                self.resolve_extern(lib_name, ty_name, span)?
+            }
+            Token::Ident(name) => self
+                .resolve_name(&name)
+                .ok_or_else(|| ParseError::new(lexeme.span, ParseErrorKind::UnknownType(name)))?,
+            _ => return Err(unexpected(lexeme, "type")),
+        };
+        while self.eat(&Token::Question) {
+            ty = option(ty);
+        }
+        Ok(ty)
+    }
+
+    fn build_variants(
+        &self,
+        elements: Vec<Element>,
+        span: Span,
+    ) -> Result<Ty<TranspileRef>, ParseError> {
+        let is_union = elements.iter().any(|e| e.ty.is_some());
+        let mut names = BTreeSet::new();
+        let mut tags = BTreeSet::new();
+        let mut variants = BTreeMap::new();
+        let mut next_tag = 0u8;
+        for element in elements {
+            let fail = |kind| ParseError::new(element.span, kind);
+            let name = element.name.ok_or_else(|| fail(ParseErrorKind::UnnamedVariant))?;
+            let name: VariantName = parse_name(name, element.span)?;
+            let tag = element.tag.unwrap_or(next_tag);
+            next_tag = tag.saturating_add(1);
+            if !names.insert(name.clone()) {
+                return Err(fail(ParseErrorKind::RepeatedVariant(name)));
+            }
+            if !tags.insert(tag) {
+                return Err(fail(ParseErrorKind::RepeatedTag(tag)));
+            }
+            let ty = match (element.ty, is_union) {
+                (Some(ty), true) => Some(ty.into_variant_ty()),
+                (None, false) => None,
+                _ => return Err(fail(ParseErrorKind::MixedVariants)),
+            };
+            variants.insert(Variant::named(tag, name), ty);
+        }
+        let too_many = |_| ParseError::new(span, ParseErrorKind::TooMany("variants"));
+        if is_union {
+            let variants = variants
+                .into_iter()
+                .map(|(variant, ty)| (variant, ty.expect("union variant")))
+                .collect::<BTreeMap<_, _>>();
+            UnionVariants::try_from(variants).map(Ty::Union).map_err(too_many)
+        } else {
+            EnumVariants::try_from(variants.into_keys().collect::<BTreeSet<_>>())
+                .map(Ty::Enum)
+                .map_err(too_many)
+        }
+    }
+
+    fn build_fields(
+        &self,
+        elements: Vec<Element>,
+        span: Span,
+    ) -> Result<Ty<TranspileRef>, ParseError> {
+        let is_struct = elements.iter().any(|e| e.name.is_some() && e.ty.is_some());
+        let mut names = BTreeSet::new();
+        let mut named = vec![];
+        let mut unnamed = vec![];
+        for element in elements {
+            let fail = |kind| ParseError::new(element.span, kind);
+            if element.tag.is_some() {
+                return Err(fail(ParseErrorKind::MisplacedTag));
+            }
+            match (element.name, element.ty, is_struct) {
+                (Some(name), Some(ty), true) => {
+                    let name: FieldName = parse_name(name, element.span)?;
+                    if !names.insert(name.clone()) {
+                        return Err(fail(ParseErrorKind::RepeatedField(name)));
+                    }
+                    named.push(Field {
+                        name,
+                        ty: ty.into_field_ty().map_err(fail)?,
+                    });
+                }
+                (Some(name), None, false) => {
+                    let ty = self
+                        .resolve_name(&name)
+                        .ok_or_else(|| fail(ParseErrorKind::UnknownType(name)))?;
+                    unnamed.push(ty);
+                }
+                (None, Some(ty), false) => unnamed.push(ty.into_field_ty().map_err(fail)?),
+                _ => return Err(fail(ParseErrorKind::MixedFields)),
+            }
+        }
+        let too_many = |_| ParseError::new(span, ParseErrorKind::TooMany("fields"));
+        if is_struct {
+            NamedFields::try_from(named).map(Ty::Struct).map_err(too_many)
+        } else {
+            UnnamedFields::try_from(unnamed).map(Ty::Tuple).map_err(too_many)
+        }
+    }
+}
+
+fn unexpected(lexeme: Lexeme, expected: &'static str) -> ParseError {
+    ParseError::new(lexeme.span, ParseErrorKind::Unexpected {
+        found: lexeme.token,
+        expected,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::stl::{std_stl, std_sym, strict_types_sym, LIB_ID_STD, LIB_ID_STRICT_TYPES};
+
+    #[test]
+    fn std_roundtrip() {
+        let src = include_str!("../../stl/Std@0.1.0.sty");
+        let lib = SymbolicLib::from_source(src, None).unwrap();
+        assert_eq!(lib, std_sym());
+        assert!(src.ends_with(&format!("{lib}\n")));
+        assert_eq!(lib.compile().unwrap().id().to_string(), LIB_ID_STD);
+    }
+
+    #[test]
+    fn strict_types_roundtrip() {
+        let src = include_str!("../../stl/StrictTypes@0.2.0.sty");
+        let std = std_stl();
+        let lib = SymbolicLib::from_source(src, [&std]).unwrap();
+        assert_eq!(lib, strict_types_sym());
+        assert!(src.ends_with(&format!("{lib}\n")));
+        assert_eq!(lib.compile().unwrap().id().to_string(), LIB_ID_STRICT_TYPES);
+    }
+
+    #[test]
+    fn fixed_list_roundtrip() {
+        let src =
+            "typelib Test\ndata Fixed : list [U16 ^ 4], array [U16 ^ 4]\ndata Top : [U8 ^ 2]\n";
+        let mut lib = SymbolicLib::from_source(src, None).unwrap();
+        // Turning `Fixed.list` and `Top` into fixed-size lists
+        for (name, mask) in [(tn!("Fixed"), 0b01), (tn!("Top"), 0b1)] {
+            let fixed = flip_fixed(&lib.types[&name], mask, &mut 0);
+            lib.types.insert(name, fixed).unwrap();
+        }
+        let Ty::Struct(fields) = &lib.types[&tn!("Fixed")] else {
+            unreachable!()
+        };
+        assert!(
+            matches!(&fields[0].ty, TranspileRef::Embedded(ty) if matches!(**ty, Ty::List(..)))
+        );
+        assert!(
+            matches!(&fields[1].ty, TranspileRef::Embedded(ty) if matches!(**ty, Ty::Array(..)))
+        );
+
+        let parsed = SymbolicLib::from_source(&lib.to_string(), None).unwrap();
+        assert_eq!(parsed, lib);
+        assert_eq!(parsed.compile().unwrap().id(), lib.compile().unwrap().id());
+    }
+
+    #[test]
+    fn missing_dependency() {
+        let src = include_str!("../../stl/StrictTypes@0.2.0.sty");
+        let err = SymbolicLib::from_source(src, None).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::UnknownLib(libname!("Std")));
+        assert_eq!(err.span.line, 14);
+    }
+
+    #[test]
+    fn mnemonic_mismatch() {
+        let src = "typelib Test\n\n@mnemonic(wrong-mnemonic-here)\ndata Some : U8\n";
+        let err = SymbolicLib::from_source(src, None).unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::MnemonicMismatch { .. }));
+        assert_eq!(err.span.line, 4);
+    }
+
+    #[test]
+    fn unknown_type() {
+        let src = "typelib Test\ndata Some : name Other, value U8\n";
+        let err = SymbolicLib::from_source(src, None).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::UnknownType(s!("Other")));
+        assert_eq!(err.to_string(), "2:18: unknown type `Other`.");
+    }
+}
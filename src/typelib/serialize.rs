@@ -44,8 +44,7 @@ impl TypeLib {
         use std::fs;
         use std::io::stdout;
 
-        let id = self.id();
-        let mut file = match dir {
+        let file = match dir {
             None => Box::new(stdout()) as Box<dyn io::Write>,
             Some(dir) => {
                 let mut filename = dir.as_ref().to_owned();
@@ -54,6 +53,18 @@ impl TypeLib {
             }
         };
 
+        self.write_to(format, file, Some(ver), header)
+    }
+
+    /// Writes the library in the given format. Version and header are used only by the source
+    /// format, where they are put into the header comment.
+    pub fn write_to(
+        &self,
+        format: StlFormat,
+        mut file: impl io::Write,
+        ver: Option<&str>,
+        header: Option<&str>,
+    ) -> io::Result<()> {
         match format {
             StlFormat::Binary => {
                 self.strict_encode(StrictWriter::with(StreamWriter::new::<U24MAX>(file)))?;
@@ -64,12 +75,11 @@ impl TypeLib {
                 writeln!(file, "{}", self.to_ascii_armored_string())?;
             }
             StlFormat::Source => {
-                writeln!(
-                    file,
-                    "{{-\n  Id: {id:+}\n  Name: {}\n  Version: {ver}{}\n-}}\n",
-                    self.name,
-                    header.unwrap_or_default()
-                )?;
+                write!(file, "{{-\n  Id: {:+}\n  Name: {}", self.id(), self.name)?;
+                if let Some(ver) = ver {
+                    write!(file, "\n  Version: {ver}")?;
+                }
+                writeln!(file, "{}\n-}}\n", header.unwrap_or_default())?;
                 writeln!(file, "{}", self.to_symbolic().expect("invalid library data"))?;
             }
         }
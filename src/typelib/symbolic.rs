@@ -32,6 +32,7 @@ use strict_encoding::{StrictDumb, TypeName, STRICT_TYPES_LIB};
 use super::{LibBuilder, SymbolContext};
 use crate::ast::{PrimitiveRef, SemCommit, SEM_ID_TAG};
 use crate::typelib::{CompileError, ExternRef, NestedContext, SymbolError, TypeIndex, TypeMap};
+use crate::util::recursion_groups;
 use crate::{Dependency, LibRef, SemId, Translate, Ty, TypeLib, TypeLibId, TypeRef};
 
 pub type ExternTypes = TinyOrdMap<LibName, SmallOrdMap<SemId, TypeName>>;
@@ -45,10 +46,10 @@ pub type ExternTypes = TinyOrdMap<LibName, SmallOrdMap<SemId, TypeName>>;
     serde(crate = "serde_crate", rename_all = "camelCase")
 )]
 pub struct SymbolicLib {
-    name: LibName,
-    dependencies: TinyOrdSet<Dependency>,
-    extern_types: ExternTypes,
-    types: SmallOrdMap<TypeName, Ty<TranspileRef>>,
+    pub(super) name: LibName,
+    pub(super) dependencies: TinyOrdSet<Dependency>,
+    pub(super) extern_types: ExternTypes,
+    pub(super) types: SmallOrdMap<TypeName, Ty<TranspileRef>>,
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Display)]
@@ -165,6 +166,7 @@ impl Display for TranspileRef {
 
 #[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
 #[display(doc_comments)]
+#[allow(clippy::large_enum_variant)]
 pub enum TranspileError {
     /// type `{unknown}` referenced inside `{within}` is not known.
     UnknownType {
@@ -254,6 +256,29 @@ impl SymbolicLib {
         let mut new_types = BTreeMap::<TypeName, Ty<LibRef>>::new();
         let names = old_types.keys().cloned().collect::<BTreeSet<_>>();
 
+        // Types referencing each other can't be compiled one after another, so inside each
+        // recursion group the references are replaced with back-reference markers. Ids of the
+        // group types become known only once all of them are compiled.
+        let graph = old_types
+            .iter()
+            .map(|(name, ty)| {
+                let mut refs = bset![];
+                named_refs(ty, &mut refs);
+                (name.clone(), refs)
+            })
+            .collect::<BTreeMap<_, _>>();
+        let mut back_refs = BTreeMap::<TypeName, TypeIndex>::new();
+        let mut group_ids = TypeIndex::new();
+        for group in recursion_groups(&graph) {
+            let markers = group
+                .iter()
+                .map(|ty_name| (ty_name.clone(), SemId::back_ref(&name, ty_name)))
+                .collect::<TypeIndex>();
+            for ty_name in group {
+                back_refs.insert(ty_name, markers.clone());
+            }
+        }
+
         while !old_types.is_empty() {
             let mut found = false;
             for name in &names {
@@ -263,6 +288,7 @@ impl SymbolicLib {
                 let mut ctx = NestedContext {
                     top_name: name.clone(),
                     index,
+                    back_refs: back_refs.get(name).cloned().unwrap_or_default(),
                     extern_types,
                     stack: empty!(),
                 };
@@ -279,11 +305,35 @@ impl SymbolicLib {
                 extern_types = ctx.extern_types;
                 found = true;
                 let id = ty.sem_id_named(name);
-                index.insert(name.clone(), id);
+                match back_refs.get(name) {
+                    None => {
+                        index.insert(name.clone(), id);
+                    }
+                    Some(group) => {
+                        group_ids.insert(name.clone(), id);
+                        if group.keys().all(|member| group_ids.contains_key(member)) {
+                            let group_id = SemId::recursion_group(
+                                group.keys().map(|member| group_ids[member]),
+                            );
+                            index.extend(group.keys().map(|member| {
+                                (member.clone(), group_ids[member].grouped(group_id))
+                            }));
+                        }
+                    }
+                }
                 new_types.insert(name.clone(), ty);
                 old_types.remove(name);
             }
-            debug_assert!(found, "incomplete type definition found in the library");
+            if !found {
+                let (unknown, within) = old_types
+                    .iter()
+                    .find_map(|(name, ty)| {
+                        let unknown = graph[name].iter().find(|r| !names.contains(*r))?;
+                        Some((unknown.clone(), ty.clone()))
+                    })
+                    .expect("only unknown types may prevent compilation");
+                return Err(CompileError::UnknownType { unknown, within });
+            }
         }
 
         let mut used_dependencies = BTreeSet::<Dependency>::new();
@@ -314,11 +364,28 @@ impl SymbolicLib {
     }
 }
 
+/// Collects names of all library types referenced by the type, including references from its
+/// embedded types.
+fn named_refs(ty: &Ty<TranspileRef>, refs: &mut BTreeSet<TypeName>) {
+    for (r, _) in ty.type_refs() {
+        match r {
+            TranspileRef::Named(name) => {
+                refs.insert(name.clone());
+            }
+            TranspileRef::Embedded(ty) => named_refs(ty, refs),
+            TranspileRef::Extern(_) => {}
+        }
+    }
+}
+
 impl TypeLib {
     pub fn to_symbolic(&self) -> Result<SymbolicLib, SymbolError> {
         let lib_index = self.dependencies.iter().map(|dep| (dep.id, dep.name.clone())).collect();
-        let reverse_index =
-            self.types.iter().map(|(name, ty)| (ty.sem_id_named(name), name.clone())).collect();
+        let reverse_index = self
+            .type_ids()
+            .into_iter()
+            .flat_map(|(name, id)| [(id, name.clone()), (SemId::back_ref(&self.name, &name), name)])
+            .collect();
         let ctx = SymbolContext {
             reverse_index,
             lib_index,
@@ -30,8 +30,9 @@ use encoding::StreamWriter;
 use strict_encoding::{
     DefineEnum, DefineStruct, DefineTuple, DefineUnion, FieldName, LibName, Primitive, Sizing,
     SplitParent, StrictDumb, StrictEncode, StrictEnum, StrictParent, StrictStruct, StrictSum,
-    StrictTuple, StrictUnion, StrictWriter, StructWriter, TypeName, TypedParent, TypedWrite,
-    UnionWriter, VariantName, WriteEnum, WriteStruct, WriteTuple, WriteUnion, LIB_EMBEDDED,
+    StrictTuple, StrictType, StrictUnion, StrictWriter, StructWriter, TypeName, TypedParent,
+    TypedWrite, UnionWriter, VariantName, WriteEnum, WriteStruct, WriteTuple, WriteUnion,
+    LIB_EMBEDDED,
 };
 
 use crate::ast::{EnumVariants, Field, NamedFields, UnionVariants, UnnamedFields};
@@ -48,6 +49,19 @@ pub trait BuilderParent: StrictParent<StreamWriter<Sink>> {
     fn report_compiled(self, lib: LibName, name: Option<TypeName>, ty: Ty<TranspileRef>) -> Self;
 }
 
+/// Named type which compilation is in progress.
+#[derive(Debug)]
+struct Frame {
+    lib: LibName,
+    name: TypeName,
+    /// Lowest position in the stack of a type reachable from this one.
+    low: usize,
+    /// Whether the type is a part of some recursion group.
+    recursive: bool,
+    /// Number of the recursion group members compiled before this type.
+    members: usize,
+}
+
 #[derive(Debug)]
 pub struct LibBuilder {
     pub(super) lib_name: LibName,
@@ -56,6 +70,10 @@ pub struct LibBuilder {
     pub(super) types: BTreeMap<TypeName, Ty<TranspileRef>>,
     sink: StreamWriter<Sink>,
     last_compiled: Option<TranspileRef>,
+    stack: Vec<Frame>,
+    /// External types from the recursion groups which compilation is not completed yet, with
+    /// their ids computed using the back-reference markers.
+    members: Vec<(LibName, TypeName, SemId)>,
 }
 
 impl LibBuilder {
@@ -70,6 +88,8 @@ impl LibBuilder {
             types: empty!(),
             sink: StreamWriter::sink::<MAX_WRITE_COUNT>(),
             last_compiled: None,
+            stack: empty!(),
+            members: empty!(),
         }
     }
 
@@ -77,6 +97,96 @@ impl LibBuilder {
         T::strict_dumb().strict_encode(self).expect("memory encoding doesn't error")
     }
 
+    fn frame_id<T: StrictType>() -> Option<(LibName, TypeName)> {
+        match (T::STRICT_LIB_NAME, T::strict_name()) {
+            (LIB_EMBEDDED, _) | (_, None) => None,
+            (lib, Some(name)) => Some((libname!(lib), name)),
+        }
+    }
+
+    /// Starts compilation of a named type. If the type is already being compiled, i.e. it is
+    /// recursive, returns a reference to it, which must be used instead of compiling the type
+    /// once again.
+    fn enter<T: StrictType>(&mut self) -> Option<TranspileRef> {
+        let (lib, name) = Self::frame_id::<T>()?;
+        let Some(pos) = self.stack.iter().position(|f| f.lib == lib && f.name == name) else {
+            self.stack.push(Frame {
+                lib,
+                name,
+                low: self.stack.len(),
+                recursive: false,
+                members: self.members.len(),
+            });
+            return None;
+        };
+        for frame in &mut self.stack[pos..] {
+            frame.recursive = true;
+        }
+        if let Some(top) = self.stack.last_mut() {
+            top.low = top.low.min(pos);
+        }
+        if lib == self.lib_name {
+            return Some(TranspileRef::Named(name));
+        }
+        let lib_id = self.dependency_id(&lib);
+        let sem_id = SemId::back_ref(&lib, &name);
+        Some(TranspileRef::Extern(SymbolRef::with(lib, name, lib_id, sem_id)))
+    }
+
+    /// Completes compilation of a named type started with [`Self::enter`]. External types
+    /// which are a part of a recursion group with their parents are referenced by their
+    /// back-reference markers, matching the way their library was compiled. Once the whole
+    /// group is compiled, ids of its external types are made to commit to the group.
+    fn leave<T: StrictType>(mut self) -> Self {
+        let Some((lib, name)) = Self::frame_id::<T>() else {
+            return self;
+        };
+        if !matches!(self.stack.last(), Some(f) if f.lib == lib && f.name == name) {
+            return self;
+        }
+        let frame = self.stack.pop().expect("checked above");
+        if frame.low >= self.stack.len() {
+            if frame.recursive {
+                self.complete_group(frame.members, &lib, &name);
+            }
+            return self;
+        }
+        if let Some(parent) = self.stack.last_mut() {
+            parent.low = parent.low.min(frame.low);
+        }
+        if let Some(TranspileRef::Extern(r)) = &mut self.last_compiled {
+            if r.lib_name == lib && r.ty_name == name {
+                r.sem_id = SemId::back_ref(&lib, &name);
+            }
+        }
+        self
+    }
+
+    /// Replaces ids of the external types of the just compiled recursion group, i.e. of the
+    /// members following the first `start` ones, with the ids committing to the group. The
+    /// reference to the group root `lib.name`, which was compiled last, is updated as well.
+    fn complete_group(&mut self, start: usize, lib: &LibName, name: &TypeName) {
+        let members = self.members.split_off(start);
+        if members.is_empty() {
+            return;
+        }
+        let ids = members
+            .iter()
+            .map(|(_, ty_name, id)| (ty_name.clone(), *id))
+            .collect::<BTreeMap<_, _>>();
+        let group_id = SemId::recursion_group(ids.into_values());
+        for (ty_lib, ty_name, id) in members {
+            let index = self.extern_types.entry(ty_lib).or_default();
+            index.remove(&id);
+            index.insert(id.grouped(group_id), ty_name);
+        }
+        if let Some(TranspileRef::Extern(r)) = &mut self.last_compiled {
+            if &r.lib_name == lib && &r.ty_name == name {
+                r.sem_id = r.sem_id.grouped(group_id);
+            }
+        }
+    }
+
     fn dependency_id(&self, lib_name: &LibName) -> TypeLibId {
         self.known_libs
             .iter()
@@ -95,11 +205,15 @@ impl TypedWrite for LibBuilder {
     unsafe fn raw_writer(&mut self) -> &mut Self::RawWriter { &mut self.sink }
 
     fn write_union<T: StrictUnion>(
-        self,
+        mut self,
         inner: impl FnOnce(Self::UnionDefiner) -> io::Result<Self>,
     ) -> io::Result<Self> {
+        if let Some(r) = self.enter::<T>() {
+            self.last_compiled = Some(r);
+            return Ok(self);
+        }
         let builder = UnionBuilder::with::<T>(self);
-        inner(builder)
+        inner(builder).map(Self::leave::<T>)
     }
 
     fn write_enum<T: StrictEnum>(self, value: T) -> io::Result<Self>
@@ -114,23 +228,31 @@ impl TypedWrite for LibBuilder {
     }
 
     fn write_tuple<T: StrictTuple>(
-        self,
+        mut self,
         inner: impl FnOnce(Self::TupleWriter) -> io::Result<Self>,
     ) -> io::Result<Self> {
+        if let Some(r) = self.enter::<T>() {
+            self.last_compiled = Some(r);
+            return Ok(self);
+        }
         let writer = StructWriter::tuple::<T>(self);
         let builder =
             StructBuilder::with(libname!(T::STRICT_LIB_NAME), T::strict_name(), writer, false);
-        inner(builder)
+        inner(builder).map(Self::leave::<T>)
     }
 
     fn write_struct<T: StrictStruct>(
-        self,
+        mut self,
         inner: impl FnOnce(Self::StructWriter) -> io::Result<Self>,
     ) -> io::Result<Self> {
+        if let Some(r) = self.enter::<T>() {
+            self.last_compiled = Some(r);
+            return Ok(self);
+        }
         let writer = StructWriter::structure::<T>(self);
         let builder =
             StructBuilder::with(libname!(T::STRICT_LIB_NAME), T::strict_name(), writer, false);
-        inner(builder)
+        inner(builder).map(Self::leave::<T>)
     }
 
     unsafe fn register_primitive(mut self, prim: Primitive) -> Self {
@@ -220,6 +342,10 @@ impl BuilderParent for LibBuilder {
             (LIB_EMBEDDED, _) | (_, None) => _compile(self),
             (lib, Some(name)) if lib != self.lib_name.as_str() => {
                 let (me, r) = _compile(self);
+                // Transparent wrappers, like `Box`, report the name of the wrapped type
+                if matches!(r, TranspileRef::Extern(ref ext) if ext.lib_name.as_str() == lib) {
+                    return (me, r);
+                }
                 let lib_name = libname!(lib);
                 let lib_id = me.dependency_id(&lib_name);
                 (me, TranspileRef::Extern(SymbolRef::with(lib_name, name, lib_id, r.id())))
@@ -248,6 +374,9 @@ impl BuilderParent for LibBuilder {
             }
             (lib, Some(name)) => {
                 let id = ty.sem_id_named(&name);
+                if matches!(self.stack.last(), Some(f) if f.recursive) {
+                    self.members.push((lib.clone(), name.clone(), id));
+                }
                 self.extern_types.entry(lib.clone()).or_default().insert(id, name.clone());
                 let lib_id = self.dependency_id(&lib);
                 TranspileRef::Extern(SymbolRef::with(lib, name, lib_id, id))
@@ -691,3 +820,43 @@ impl WriteUnion for UnionBuilder {
         self._complete_write(ty)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::TinyVec;
+
+    use super::*;
+    use crate::value::test_helpers::{Expr, Tree};
+    use crate::{SymbolicLib, SystemBuilder};
+
+    #[derive(Clone, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = "OtherLib")]
+    struct Forest {
+        trees: TinyVec<Tree>,
+        expr: Expr,
+    }
+
+    #[test]
+    fn recursive() {
+        let lib = LibBuilder::new("TestLib", None).transpile::<Expr>().compile().unwrap();
+        assert_eq!(lib.types.len(), 2);
+        let source = lib.to_symbolic().unwrap().to_string();
+        let parsed = SymbolicLib::from_source(&source, None).unwrap().compile().unwrap();
+        assert_eq!(parsed, lib);
+
+        let other = LibBuilder::new("OtherLib", [lib.to_dependency()])
+            .transpile::<Forest>()
+            .compile()
+            .unwrap();
+        // External recursive types are referenced by the ids committing to their groups
+        let ids = lib.type_ids();
+        let extern_types = &other.extern_types[&libname!("TestLib")];
+        assert_eq!(extern_types.get(&ids[&tn!("Tree")]), Some(&tn!("Tree")));
+        assert_eq!(extern_types.get(&ids[&tn!("Expr")]), Some(&tn!("Expr")));
+        let sys =
+            SystemBuilder::new().import(lib).unwrap().import(other).unwrap().finalize().unwrap();
+        assert!(sys.as_types().validate().is_ok());
+        assert_eq!(sys.as_types().recursion_groups().len(), 2);
+    }
+}
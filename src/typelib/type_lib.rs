@@ -21,7 +21,7 @@
 // limitations under the License.
 
 use std::cmp::Ordering;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{self, Display, Formatter};
 
 use amplify::confinement::{Confined, TinyOrdSet};
@@ -32,13 +32,63 @@ use strict_encoding::{LibName, TypeName, STRICT_TYPES_LIB};
 use crate::typelib::compile::CompileError;
 use crate::typelib::id::TypeLibId;
 use crate::typelib::ExternTypes;
+use crate::util::recursion_groups;
 use crate::{SemId, Ty, TypeRef};
 
-pub trait LibSubref: TypeRef {}
-impl LibSubref for LibRef {}
-impl LibSubref for InlineRef {}
-impl LibSubref for InlineRef1 {}
-impl LibSubref for InlineRef2 {}
+pub trait LibSubref: TypeRef {
+    /// Collects ids of the library types referenced by name, including the references from the
+    /// inline type.
+    fn collect_named(&self, ids: &mut BTreeSet<SemId>);
+}
+impl LibSubref for LibRef {
+    fn collect_named(&self, ids: &mut BTreeSet<SemId>) {
+        match self {
+            LibRef::Inline(ty) => ty.collect_named(ids),
+            LibRef::Named(id) => {
+                ids.insert(*id);
+            }
+            LibRef::Extern(_) => {}
+        }
+    }
+}
+impl LibSubref for InlineRef {
+    fn collect_named(&self, ids: &mut BTreeSet<SemId>) {
+        match self {
+            InlineRef::Inline(ty) => ty.collect_named(ids),
+            InlineRef::Named(id) => {
+                ids.insert(*id);
+            }
+            InlineRef::Extern(_) => {}
+        }
+    }
+}
+impl LibSubref for InlineRef1 {
+    fn collect_named(&self, ids: &mut BTreeSet<SemId>) {
+        match self {
+            InlineRef1::Inline(ty) => ty.collect_named(ids),
+            InlineRef1::Named(id) => {
+                ids.insert(*id);
+            }
+            InlineRef1::Extern(_) => {}
+        }
+    }
+}
+impl LibSubref for InlineRef2 {
+    fn collect_named(&self, ids: &mut BTreeSet<SemId>) {
+        if let InlineRef2::Named(id) = self {
+            ids.insert(*id);
+        }
+    }
+}
+
+impl<Ref: LibSubref> Ty<Ref> {
+    /// Collects ids of the library types referenced by name from the type or its inline types.
+    pub fn collect_named(&self, ids: &mut BTreeSet<SemId>) {
+        for (r, _) in self.type_refs() {
+            r.collect_named(ids);
+        }
+    }
+}
 
 #[derive(Clone, Eq, PartialEq, Debug, Display)]
 #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
@@ -66,6 +116,7 @@ impl ExternRef {
     derive(Serialize, Deserialize),
     serde(crate = "serde_crate", rename_all = "camelCase")
 )]
+#[allow(clippy::large_enum_variant)]
 pub enum InlineRef {
     #[from]
     Inline(Ty<InlineRef1>),
@@ -186,6 +237,7 @@ impl Display for InlineRef2 {
     derive(Serialize, Deserialize),
     serde(crate = "serde_crate", rename_all = "camelCase")
 )]
+#[allow(clippy::large_enum_variant)]
 pub enum LibRef {
     #[from]
     Inline(Ty<InlineRef>),
@@ -304,5 +356,49 @@ impl TypeLib {
         Ok(())
     }
 
+    /// Computes semantic ids of the library types.
+    ///
+    /// Types of a recursion group reference each other using back-reference markers (see
+    /// [`SemId::back_ref`]), thus their ids are additionally made to commit to the id of the
+    /// group (see [`SemId::grouped`]).
+    pub fn type_ids(&self) -> BTreeMap<TypeName, SemId> {
+        let groups = self.recursion_groups();
+        self.types
+            .iter()
+            .map(|(name, ty)| {
+                let id = ty.sem_id_named(name);
+                (name.clone(), groups.get(name).map_or(id, |group| id.grouped(*group)))
+            })
+            .collect()
+    }
+
+    /// Returns ids of the recursion groups (see [`SemId::recursion_group`]) indexed by the names
+    /// of the types belonging to them.
+    pub(crate) fn recursion_groups(&self) -> BTreeMap<TypeName, SemId> {
+        let markers = self
+            .types
+            .keys()
+            .map(|name| (SemId::back_ref(&self.name, name), name.clone()))
+            .collect::<BTreeMap<_, _>>();
+        let graph = self
+            .types
+            .iter()
+            .map(|(name, ty)| {
+                let mut ids = bset![];
+                ty.collect_named(&mut ids);
+                let refs = ids.iter().filter_map(|id| markers.get(id)).cloned().collect();
+                (name.clone(), refs)
+            })
+            .collect::<BTreeMap<_, BTreeSet<_>>>();
+        let mut groups = BTreeMap::new();
+        for group in recursion_groups(&graph) {
+            let group_id = SemId::recursion_group(
+                group.iter().map(|name| self.types[name].sem_id_named(name)),
+            );
+            groups.extend(group.into_iter().map(|name| (name, group_id)));
+        }
+        groups
+    }
+
     // TODO: Check that all dependencies are used
 }
@@ -0,0 +1,282 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storing type systems in files.
+
+use std::fmt::Display;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+use std::{fs, io};
+
+use amplify::confinement::{Confined, U24 as U24MAX};
+use encoding::{
+    DecodeError, DeserializeError, StreamWriter, StrictDeserialize, StrictEncode, StrictWriter,
+};
+
+use crate::{StsFormat, SymbolicSys, TypeSysId, TypeSystem, UnknownFormat};
+
+/// Errors storing and loading type system files.
+#[derive(Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum SysFileError {
+    /// file name `{0}` has no extension specifying type system format.
+    NoExtension(String),
+
+    #[from]
+    #[display(inner)]
+    UnknownFormat(UnknownFormat),
+
+    #[from]
+    #[display(inner)]
+    Io(io::Error),
+
+    /// invalid binary type system data. Details: {0}
+    #[from]
+    Deserialize(DeserializeError),
+
+    #[cfg(feature = "armor")]
+    #[from]
+    #[display(inner)]
+    Armor(armor::StrictArmorError),
+
+    /// type system listing is a human-readable format which can't be loaded.
+    Listing,
+
+    /// the file contains type system `{actual}`, while `{expected}` was expected.
+    IdMismatch {
+        expected: TypeSysId,
+        actual: TypeSysId,
+    },
+}
+
+/// Type system data which can be stored in files.
+trait SysFile: Sized + StrictEncode + StrictDeserialize + Display {
+    fn sys_id(&self) -> TypeSysId;
+
+    #[cfg(feature = "armor")]
+    fn to_armored(&self) -> String;
+
+    #[cfg(feature = "armor")]
+    fn from_armored(s: &str) -> Result<Self, armor::StrictArmorError>;
+
+    fn write_to(&self, format: StsFormat, mut file: impl io::Write) -> io::Result<()> {
+        match format {
+            StsFormat::Binary => {
+                self.strict_encode(StrictWriter::with(StreamWriter::new::<U24MAX>(file)))?;
+            }
+            #[cfg(feature = "armor")]
+            StsFormat::Armored => writeln!(file, "{}", self.to_armored())?,
+            StsFormat::Listing => write!(file, "{self}")?,
+        }
+        Ok(())
+    }
+
+    fn save(&self, path: &Path) -> Result<(), SysFileError> {
+        let format = file_format(path)?;
+        let file = fs::File::create(path)?;
+        let mut writer = io::BufWriter::new(file);
+        self.write_to(format, &mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn load(path: &Path, expected: Option<TypeSysId>) -> Result<Self, SysFileError> {
+        let data = match file_format(path)? {
+            StsFormat::Binary => {
+                // Reading the file first to report file system errors as such
+                let data = Confined::try_from(fs::read(path)?)
+                    .map_err(|err| DeserializeError::from(DecodeError::from(err)))?;
+                Self::from_strict_serialized::<U24MAX>(data)?
+            }
+            #[cfg(feature = "armor")]
+            StsFormat::Armored => {
+                use armor::StrictArmorError;
+
+                Self::from_armored(fs::read_to_string(path)?.trim()).map_err(|err| match err {
+                    StrictArmorError::MismatchedId { expected, actual } => {
+                        SysFileError::IdMismatch {
+                            expected: expected.parse().expect("armor ids are checked when parsed"),
+                            actual: actual.parse().expect("type system ids are always valid"),
+                        }
+                    }
+                    err => err.into(),
+                })?
+            }
+            StsFormat::Listing => return Err(SysFileError::Listing),
+        };
+        if let Some(expected) = expected {
+            let actual = data.sys_id();
+            if actual != expected {
+                return Err(SysFileError::IdMismatch { expected, actual });
+            }
+        }
+        Ok(data)
+    }
+}
+
+fn file_format(path: &Path) -> Result<StsFormat, SysFileError> {
+    let ext = path
+        .extension()
+        .ok_or_else(|| SysFileError::NoExtension(path.display().to_string()))?
+        .to_string_lossy();
+    Ok(StsFormat::from_str(&ext)?)
+}
+
+impl SysFile for TypeSystem {
+    fn sys_id(&self) -> TypeSysId { self.id() }
+
+    #[cfg(feature = "armor")]
+    fn to_armored(&self) -> String { armor::AsciiArmor::to_ascii_armored_string(self) }
+
+    #[cfg(feature = "armor")]
+    fn from_armored(s: &str) -> Result<Self, armor::StrictArmorError> {
+        armor::AsciiArmor::from_ascii_armored_str(s)
+    }
+}
+
+impl SysFile for SymbolicSys {
+    fn sys_id(&self) -> TypeSysId { self.id() }
+
+    #[cfg(feature = "armor")]
+    fn to_armored(&self) -> String { armor::AsciiArmor::to_ascii_armored_string(self) }
+
+    #[cfg(feature = "armor")]
+    fn from_armored(s: &str) -> Result<Self, armor::StrictArmorError> {
+        armor::AsciiArmor::from_ascii_armored_str(s)
+    }
+}
+
+impl TypeSystem {
+    /// Writes the type system in the given format.
+    pub fn write_to(&self, format: StsFormat, file: impl io::Write) -> io::Result<()> {
+        SysFile::write_to(self, format, file)
+    }
+
+    /// Saves the type system into a file, detecting the file format from the file extension.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SysFileError> {
+        SysFile::save(self, path.as_ref())
+    }
+
+    /// Loads a type system from a file, detecting the file format from the file extension.
+    ///
+    /// The loaded type system is validated with [`TypeSystem::validate`]; for the armored format
+    /// the id stated in the file is also checked against the id of the loaded type system.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SysFileError> {
+        <Self as SysFile>::load(path.as_ref(), None)
+    }
+
+    /// Loads a type system from a file like [`TypeSystem::load`], additionally checking that it
+    /// has the `expected` id.
+    pub fn load_checked(path: impl AsRef<Path>, expected: TypeSysId) -> Result<Self, SysFileError> {
+        <Self as SysFile>::load(path.as_ref(), Some(expected))
+    }
+}
+
+impl SymbolicSys {
+    /// Writes the type system with its symbols in the given format.
+    pub fn write_to(&self, format: StsFormat, file: impl io::Write) -> io::Result<()> {
+        SysFile::write_to(self, format, file)
+    }
+
+    /// Saves the type system with its symbols into a file, detecting the file format from the
+    /// file extension.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SysFileError> {
+        SysFile::save(self, path.as_ref())
+    }
+
+    /// Loads a type system with its symbols from a file, detecting the file format from the file
+    /// extension.
+    ///
+    /// The loaded type system is validated with [`SymbolicSys::validate`]; for the armored format
+    /// the id stated in the file is also checked against the id of the loaded type system.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SysFileError> {
+        <Self as SysFile>::load(path.as_ref(), None)
+    }
+
+    /// Loads a type system with its symbols from a file like [`SymbolicSys::load`], additionally
+    /// checking that it has the `expected` id.
+    pub fn load_checked(path: impl AsRef<Path>, expected: TypeSysId) -> Result<Self, SysFileError> {
+        <Self as SysFile>::load(path.as_ref(), Some(expected))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use amplify::confinement::Confined;
+
+    use super::*;
+    use crate::value::test_helpers::test_system;
+
+    fn tmp_file(name: &str) -> std::path::PathBuf {
+        env::temp_dir().join(format!("strict-types-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn roundtrip() {
+        let sys = test_system();
+        #[allow(unused_mut)]
+        let mut formats = vec!["sts"];
+        #[cfg(feature = "armor")]
+        formats.push("stsa");
+        for ext in formats {
+            let path = tmp_file(&format!("sys.{ext}"));
+            sys.save(&path).unwrap();
+            assert_eq!(SymbolicSys::load(&path).unwrap(), sys);
+            assert_eq!(SymbolicSys::load_checked(&path, sys.id()).unwrap(), sys);
+            assert!(matches!(
+                SymbolicSys::load_checked(&path, TypeSystem::new().id()),
+                Err(SysFileError::IdMismatch { .. })
+            ));
+
+            let path = tmp_file(&format!("types.{ext}"));
+            sys.as_types().save(&path).unwrap();
+            assert_eq!(&TypeSystem::load(&path).unwrap(), sys.as_types());
+        }
+
+        let path = tmp_file("sys.stsl");
+        sys.save(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), sys.to_string());
+        assert!(matches!(SymbolicSys::load(&path), Err(SysFileError::Listing)));
+        assert!(matches!(SymbolicSys::load("sys.txt"), Err(SysFileError::UnknownFormat(_))));
+    }
+
+    #[test]
+    fn incomplete() {
+        let sys = test_system();
+        let mut types = sys.as_types().as_inner().clone();
+        let sem_id = sys.to_sem_id("TestLib.Precision").unwrap();
+        types.remove(&sem_id).unwrap();
+        let types = TypeSystem::from(Confined::try_from(types).unwrap());
+
+        let path = tmp_file("incomplete.sts");
+        types.save(&path).unwrap();
+        assert!(matches!(
+            TypeSystem::load(&path),
+            Err(SysFileError::Deserialize(DeserializeError::Decode(
+                encoding::DecodeError::DataIntegrityError(_)
+            )))
+        ));
+    }
+}
@@ -42,6 +42,10 @@ pub struct TypeTree<'sys> {
 impl<'sys> TypeTree<'sys> {
     pub(super) fn new(sem_id: SemId, sys: &'sys SymbolicSys) -> Self { Self { sem_id, sys } }
 
+    pub fn sem_id(&self) -> SemId { self.sem_id }
+
+    pub fn sys(&self) -> &'sys SymbolicSys { self.sys }
+
     pub fn get(&self) -> &Ty<SemId> { self.sys.get(self.sem_id).expect("inconsistent type tree") }
 
     pub fn iter(&'sys self) -> TypeTreeIter<'sys> {
@@ -121,6 +125,9 @@ pub struct TypeInfo {
     pub fqn: Option<TypeFqn>,
     pub item: Option<ItemCase>,
     pub nested: TinyVec<NestedCase>,
+    /// Whether the type is a back-reference to one of its parent types (i.e. the type is
+    /// recursive), in which case its nested types are not repeated.
+    pub back_ref: bool,
 }
 
 /*
@@ -142,7 +149,7 @@ pub struct TypeTreeIter<'sys> {
     ty: Option<&'sys Ty<SemId>>,
     item: Option<ItemCase>,
     depth: u32,
-    path: Vec<(u32, ast::Iter<'sys, SemId>)>,
+    path: Vec<(u32, SemId, ast::Iter<'sys, SemId>)>,
     sys: &'sys SymbolicSys,
     nested: Vec<NestedCase>,
 }
@@ -155,6 +162,21 @@ impl<'sys> Iterator for TypeTreeIter<'sys> {
             let fqn = self.sys.symbols.lookup(self.sem_id);
             self.ty = None;
 
+            if self.path.iter().any(|(_, id, _)| *id == self.sem_id) {
+                let mut item = None;
+                swap(&mut item, &mut self.item);
+                let info = TypeInfo {
+                    depth: self.depth,
+                    ty: ty.clone(),
+                    fqn: fqn.cloned(),
+                    item,
+                    nested: Confined::from_collection_unsafe(self.nested.clone()),
+                    back_ref: true,
+                };
+                self.nested = vec![];
+                return Some(info);
+            }
+
             let mut nested = vec![];
             let mut dive = true;
             let mut push = true;
@@ -178,7 +200,7 @@ impl<'sys> Iterator for TypeTreeIter<'sys> {
                         if first.is_char_enum() && other.map(Ty::is_char_enum).unwrap_or_default() {
                             let first = self.sys.symbols.lookup(fields[0]);
                             let rest = self.sys.symbols.lookup(*rest);
-                            let mut sizing = sizing.clone();
+                            let mut sizing = *sizing;
                             sizing.min += 1;
                             sizing.max += 1;
                             let _ = iter.next(); // skipping first char
@@ -210,7 +232,7 @@ impl<'sys> Iterator for TypeTreeIter<'sys> {
                 self.depth += 1;
             }
             if push {
-                self.path.push((self.depth, iter));
+                self.path.push((self.depth, self.sem_id, iter));
             }
             self.nested.extend(nested);
             if ret {
@@ -222,13 +244,14 @@ impl<'sys> Iterator for TypeTreeIter<'sys> {
                     fqn: fqn.cloned(),
                     item,
                     nested: Confined::from_collection_unsafe(self.nested.clone()),
+                    back_ref: false,
                 };
                 self.nested = vec![];
                 return Some(info);
             }
         }
         loop {
-            let (depth, iter) = self.path.last_mut()?;
+            let (depth, _, iter) = self.path.last_mut()?;
             self.depth = *depth;
             match iter.next() {
                 None => {
@@ -25,9 +25,16 @@ mod translate;
 mod id;
 mod symbols;
 mod iter;
+mod path;
+mod resolve;
+mod file;
+mod recursion;
 
+pub use file::SysFileError;
 pub use id::TypeSysId;
 pub use iter::{NestedCase, TypeInfo, TypeTree, TypeTreeIter};
-pub use symbols::{SymbolicSys, Symbols};
+pub use path::TyPathError;
+pub(crate) use resolve::{Resolved, TypeSource};
+pub use symbols::{ExtractError, SymbolicSys, Symbols};
 pub use translate::{Error, SystemBuilder, TypeSymbol};
-pub use type_sys::{SymTy, TypeFqn, TypeSystem, UnknownType};
+pub use type_sys::{FqnParseError, SymTy, TypeFqn, TypeSystem, UnknownType};
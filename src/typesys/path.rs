@@ -0,0 +1,155 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Resolution of type paths through a type system.
+
+use crate::ast::{Path, Step};
+use crate::typesys::{SymbolicSys, TypeFqn};
+use crate::typify::TypeSpec;
+use crate::{SemId, TypeSystem};
+
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum TyPathError {
+    /// type `{0}` is not known to the type system.
+    UnknownSpec(TypeSpec),
+
+    /// type with id `{0}` is not a part of the type system.
+    UnknownType(SemId),
+
+    /// path step #{0} `{1}` can't be applied to the type `{2}` reached by the path `{3}`.
+    InvalidStep(usize, Step, SemId, Path),
+}
+
+impl TypeSystem {
+    /// Resolves type path starting from the type with a given semantic id.
+    ///
+    /// Returns the list of semantic ids of the types visited by each step of the path, starting
+    /// with the root type; thus the last element of the list is the id of the type located at the
+    /// path.
+    pub fn ty_at_path(&self, sem_id: SemId, path: &Path) -> Result<Vec<SemId>, TyPathError> {
+        let mut ids = Vec::with_capacity(path.len() + 1);
+        let mut ty = self.find(sem_id).ok_or(TyPathError::UnknownType(sem_id))?;
+        let mut passed = Path::new();
+        ids.push(sem_id);
+        for (pos, step) in path.iter().enumerate() {
+            let next = *ty.ty_at_step(step).ok_or_else(|| {
+                TyPathError::InvalidStep(pos, step.clone(), ids[pos], passed.clone())
+            })?;
+            ty = self.find(next).ok_or(TyPathError::UnknownType(next))?;
+            passed.push(step.clone()).expect("confinement collection guarantees");
+            ids.push(next);
+        }
+        Ok(ids)
+    }
+}
+
+impl SymbolicSys {
+    /// Resolves type path starting from the type with a given specification.
+    ///
+    /// Returns the list of semantic ids of the types visited by each step of the path, starting
+    /// with the root type, accompanied with the fully qualified type names for the named types.
+    pub fn ty_at_path(
+        &self,
+        spec: impl Into<TypeSpec>,
+        path: &Path,
+    ) -> Result<Vec<(SemId, Option<TypeFqn>)>, TyPathError> {
+        let spec = spec.into();
+        let sem_id = self.to_sem_id(spec.clone()).ok_or(TyPathError::UnknownSpec(spec))?;
+        let ids = self.as_types().ty_at_path(sem_id, path)?;
+        Ok(ids.into_iter().map(|id| (id, self.lookup(id).cloned())).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::value::test_helpers::test_system;
+    use crate::PrimitiveRef;
+
+    fn path(steps: impl IntoIterator<Item = Step>) -> Path {
+        let mut path = Path::new();
+        for step in steps {
+            path.push(step).unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn resolve() {
+        let sys = test_system();
+        let root = sys.to_sem_id("TestLib.Nominal").unwrap();
+
+        let res = sys.ty_at_path("TestLib.Nominal", &Path::new()).unwrap();
+        assert_eq!(res, vec![(root, Some(TypeFqn::from("TestLib.Nominal")))]);
+
+        let res = sys
+            .ty_at_path("TestLib.Nominal", &path([Step::NamedField(fname!("precision"))]))
+            .unwrap();
+        assert_eq!(res[1].1, Some(TypeFqn::from("TestLib.Precision")));
+
+        let res = sys
+            .ty_at_path("TestLib.Nominal", &path([Step::NamedField(fname!("name")), Step::List]))
+            .unwrap();
+        assert_eq!(res.len(), 3);
+        assert_eq!(res[0].0, root);
+        assert_eq!(res[2].0, SemId::unicode_char());
+        assert_eq!(res[2].1, None);
+
+        let res = sys.as_types().ty_at_path(root, &path([Step::UnnamedField(2)])).unwrap();
+        assert_eq!(res[1], sys.to_sem_id("TestLib.Precision").unwrap());
+    }
+
+    #[test]
+    fn failures() {
+        let sys = test_system();
+        let root = sys.to_sem_id("TestLib.Nominal").unwrap();
+
+        assert_eq!(
+            sys.ty_at_path("TestLib.Absent", &Path::new()).unwrap_err(),
+            TyPathError::UnknownSpec(TypeSpec::from("TestLib.Absent"))
+        );
+
+        let prefix = path([Step::NamedField(fname!("precision"))]);
+        let err = sys
+            .ty_at_path(
+                "TestLib.Nominal",
+                &path([Step::NamedField(fname!("precision")), Step::List]),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            TyPathError::InvalidStep(
+                1,
+                Step::List,
+                sys.to_sem_id("TestLib.Precision").unwrap(),
+                prefix
+            )
+        );
+        assert_eq!(
+            sys.as_types()
+                .ty_at_path(root, &path([Step::NamedField(fname!("absent"))]))
+                .unwrap_err(),
+            TyPathError::InvalidStep(0, Step::NamedField(fname!("absent")), root, Path::new())
+        );
+    }
+}
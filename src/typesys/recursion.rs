@@ -0,0 +1,93 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
+
+use crate::util::recursion_groups;
+use crate::{SemId, Ty, TypeSystem};
+
+impl TypeSystem {
+    /// Returns groups of mutually recursive types of the system, i.e. types which reference each
+    /// other, directly or via some other types. A group may consist of a single type referencing
+    /// itself.
+    pub fn recursion_groups(&self) -> Vec<BTreeSet<SemId>> {
+        let graph = self
+            .iter()
+            .map(|(id, ty)| (*id, ty.type_refs().map(|(id, _)| *id).collect()))
+            .collect();
+        recursion_groups(&graph)
+    }
+
+    /// Computes minimal depth of the values for each of the system types. Types without nested
+    /// types, as well as empty collections, have values of zero depth.
+    ///
+    /// Types which don't have values of a finite depth, like a structure containing itself, are
+    /// absent from the returned map.
+    pub(crate) fn value_depths(&self) -> BTreeMap<SemId, usize> {
+        // A type depth becomes known once the depth of some of its variants (for unions) or of
+        // all of its nested types (for other types) is known. Types are processed in the order
+        // of their depth, thus the first known variant of a union is always the shallowest one.
+        let mut pending = BTreeMap::<SemId, usize>::new();
+        let mut dependents = BTreeMap::<SemId, Vec<SemId>>::new();
+        let mut queue = BinaryHeap::new();
+        for (id, ty) in self.iter() {
+            let nested = match ty {
+                Ty::Array(_, 0) => bset![],
+                Ty::List(_, sizing) | Ty::Set(_, sizing) | Ty::Map(_, _, sizing)
+                    if sizing.min == 0 =>
+                {
+                    bset![]
+                }
+                ty => ty.type_refs().map(|(id, _)| *id).collect::<BTreeSet<_>>(),
+            };
+            if nested.is_empty() {
+                queue.push(Reverse((0usize, *id)));
+                continue;
+            }
+            let required = if matches!(ty, Ty::Union(_)) { 1 } else { nested.len() };
+            pending.insert(*id, required);
+            for nested_id in nested {
+                dependents.entry(nested_id).or_default().push(*id);
+            }
+        }
+
+        let mut depths = BTreeMap::new();
+        while let Some(Reverse((depth, id))) = queue.pop() {
+            if depths.contains_key(&id) {
+                continue;
+            }
+            depths.insert(id, depth);
+            for parent in dependents.get(&id).into_iter().flatten() {
+                let required = pending.get_mut(parent).expect("dependent types are pending");
+                if *required == 0 {
+                    continue;
+                }
+                *required -= 1;
+                if *required == 0 {
+                    queue.push(Reverse((depth + 1, *parent)));
+                }
+            }
+        }
+        depths
+    }
+}
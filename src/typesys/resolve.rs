@@ -0,0 +1,94 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::typesys::{SymbolicSys, TypeFqn, UnknownType};
+use crate::{SemId, SymbolicLib, TranspileRef, Ty, TypeRef, TypeSystem};
+
+/// Type reference resolved within some collection of types.
+pub(crate) enum Resolved<'a, Ref: TypeRef> {
+    /// Reference to a named type, which may be defined outside of the collection.
+    Named(TypeFqn),
+    /// Type which has no name and is defined in place.
+    Inline(&'a Ty<Ref>),
+}
+
+/// Collection of types which can be traversed by following type references.
+pub(crate) trait TypeSource<Ref: TypeRef> {
+    fn resolve_ref<'a>(&'a self, r: &'a Ref) -> Result<Resolved<'a, Ref>, UnknownType>;
+
+    /// Returns definition of a named type, if it is known to the collection.
+    fn named_ty(&self, fqn: &TypeFqn) -> Option<&Ty<Ref>>;
+
+    /// Returns semantic id for references to types external to the collection, which
+    /// definitions can't be accessed.
+    fn extern_id(&self, _r: &Ref) -> Option<SemId> { None }
+}
+
+impl TypeSource<TranspileRef> for SymbolicLib {
+    fn resolve_ref<'a>(
+        &'a self,
+        r: &'a TranspileRef,
+    ) -> Result<Resolved<'a, TranspileRef>, UnknownType> {
+        Ok(match r {
+            TranspileRef::Embedded(ty) => Resolved::Inline(ty),
+            TranspileRef::Named(name) => {
+                Resolved::Named(TypeFqn::with(self.name().clone(), name.clone()))
+            }
+            TranspileRef::Extern(sym) => {
+                Resolved::Named(TypeFqn::with(sym.lib_name.clone(), sym.ty_name.clone()))
+            }
+        })
+    }
+
+    fn named_ty(&self, fqn: &TypeFqn) -> Option<&Ty<TranspileRef>> {
+        if &fqn.lib != self.name() {
+            return None;
+        }
+        self.types().get(&fqn.name)
+    }
+
+    fn extern_id(&self, r: &TranspileRef) -> Option<SemId> {
+        match r {
+            TranspileRef::Extern(sym) => Some(sym.sem_id),
+            _ => None,
+        }
+    }
+}
+
+impl TypeSource<SemId> for SymbolicSys {
+    fn resolve_ref<'a>(&'a self, r: &'a SemId) -> Result<Resolved<'a, SemId>, UnknownType> {
+        if let Some(fqn) = self.lookup(*r) {
+            return Ok(Resolved::Named(fqn.clone()));
+        }
+        self.as_types().get(*r).map(Resolved::Inline).ok_or(UnknownType(*r))
+    }
+
+    fn named_ty(&self, fqn: &TypeFqn) -> Option<&Ty<SemId>> { self.get(fqn.clone()) }
+}
+
+impl TypeSource<SemId> for TypeSystem {
+    fn resolve_ref<'a>(&'a self, r: &'a SemId) -> Result<Resolved<'a, SemId>, UnknownType> {
+        self.get(*r).map(Resolved::Inline).ok_or(UnknownType(*r))
+    }
+
+    fn named_ty(&self, _fqn: &TypeFqn) -> Option<&Ty<SemId>> { None }
+}
@@ -20,18 +20,30 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{self, Display, Formatter};
 use std::ops::Index;
 
-use amplify::confinement::{self, MediumOrdSet, SmallOrdSet};
-use encoding::{StrictDeserialize, StrictSerialize, STRICT_TYPES_LIB};
+use amplify::confinement::{self, Confined, MediumOrdSet, SmallOrdSet};
+use encoding::{
+    DecodeError, ReadStruct, StrictDecode, StrictDeserialize, StrictSerialize, TypedRead,
+    STRICT_TYPES_LIB,
+};
 
+use crate::typelib::InlineRef;
+use crate::typesys::translate::BackRefs;
 use crate::typesys::{translate, SymTy, TypeFqn, TypeSymbol, TypeSysId, TypeTree};
 use crate::typify::TypeSpec;
-use crate::{Dependency, SemId, Translate, Ty, TypeSystem};
+use crate::{Dependency, LibRef, SemId, Translate, Ty, TypeSystem};
 
-#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum ExtractError {
+    /// unknown type `{0}`.
+    TypeAbsent(TypeSpec),
+}
+
+#[derive(Getters, Clone, Eq, PartialEq, Debug)]
 #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
 #[strict_type(lib = STRICT_TYPES_LIB)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(crate = "serde_crate"))]
@@ -108,9 +120,13 @@ impl Display for Symbols {
 
 #[derive(Getters, Clone, Eq, PartialEq, Debug)]
 #[getter(prefix = "as_")]
-#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[derive(StrictType, StrictDumb, StrictEncode)]
 #[strict_type(lib = STRICT_TYPES_LIB)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(crate = "serde_crate"))]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", try_from = "UncheckedSys")
+)]
 pub struct SymbolicSys {
     pub(super) symbols: Symbols,
     types: TypeSystem,
@@ -119,6 +135,43 @@ pub struct SymbolicSys {
 impl StrictSerialize for SymbolicSys {}
 impl StrictDeserialize for SymbolicSys {}
 
+impl StrictDecode for SymbolicSys {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let sys = reader.read_struct(|r| {
+            Ok(SymbolicSys {
+                symbols: r.read_field(fname!("symbols"))?,
+                types: r.read_field(fname!("types"))?,
+            })
+        })?;
+        sys.validate()
+            .map_err(|mut errors| DecodeError::DataIntegrityError(errors.remove(0).to_string()))?;
+        Ok(sys)
+    }
+}
+
+/// Symbolic type system data before they are validated.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(crate = "serde_crate")]
+struct UncheckedSys {
+    symbols: Symbols,
+    types: TypeSystem,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedSys> for SymbolicSys {
+    type Error = translate::Error;
+
+    fn try_from(sys: UncheckedSys) -> Result<Self, Self::Error> {
+        let sys = SymbolicSys {
+            symbols: sys.symbols,
+            types: sys.types,
+        };
+        sys.validate().map_err(|mut errors| errors.remove(0))?;
+        Ok(sys)
+    }
+}
+
 impl SymbolicSys {
     pub(crate) fn with(
         libs: impl IntoIterator<Item = Dependency>,
@@ -142,6 +195,128 @@ impl SymbolicSys {
 
     pub fn id(&self) -> TypeSysId { self.types.id() }
 
+    /// Checks that the type system is complete, all symbols refer to the types of the system
+    /// and type ids match the type definitions.
+    ///
+    /// Ids of the recursive types are computed using back-reference markers (see
+    /// [`SemId::back_ref`]) for the references between the types of the same recursion group,
+    /// and then are made to commit to the id of the group (see [`SemId::grouped`]).
+    pub fn validate(&self) -> Result<(), Vec<translate::Error>> {
+        let mut errors = self.types.validate().err().unwrap_or_default();
+        let groups = self.types.recursion_groups();
+        let mut back_refs = BTreeMap::<SemId, BackRefs>::new();
+        for group in &groups {
+            let markers = group
+                .iter()
+                .filter_map(|id| {
+                    let fqn = self.lookup(*id)?;
+                    Some((*id, SemId::back_ref(&fqn.lib, &fqn.name)))
+                })
+                .collect::<BackRefs>();
+            for id in group {
+                back_refs.insert(*id, markers.clone());
+            }
+        }
+        let mut computed = BTreeMap::new();
+        for (id, ty) in self.types.iter() {
+            let mut markers = back_refs.get(id).cloned().unwrap_or_default();
+            let res = match self.lookup(*id) {
+                Some(fqn) => Translate::<Ty<LibRef>>::translate(ty.clone(), &mut markers, self)
+                    .map(|ty| ty.sem_id_named(&fqn.name)),
+                None => Translate::<Ty<InlineRef>>::translate(ty.clone(), &mut markers, self)
+                    .map(|ty| ty.sem_id_unnamed()),
+            };
+            match res {
+                Ok(sem_id) => {
+                    computed.insert(*id, sem_id);
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+        for group in groups {
+            let members = group
+                .iter()
+                .filter_map(|id| Some((&self.lookup(*id)?.name, *computed.get(id)?)))
+                .collect::<BTreeMap<_, _>>();
+            let group_id = SemId::recursion_group(members.into_values());
+            for id in group {
+                if let Some(sem_id) = computed.get_mut(&id) {
+                    *sem_id = sem_id.grouped(group_id);
+                }
+            }
+        }
+        for (id, computed) in computed {
+            if computed != id {
+                errors.push(translate::Error::IdMismatch {
+                    stated: id,
+                    computed,
+                });
+            }
+        }
+        for sym in &self.symbols.symbols {
+            if self.types.get(sym.id).is_none() {
+                errors.push(translate::Error::UnknownType(sym.id));
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(())
+    }
+
+    /// Lists anonymous types which are not referenced by any other type of the system. Such
+    /// types can't be produced from the library types, however, they are valid roots of an
+    /// extracted system (see [`Self::extract`]).
+    pub(crate) fn unreachable_types(&self) -> Vec<translate::Error> {
+        let referenced = self
+            .types
+            .values()
+            .flat_map(|ty| ty.type_refs().map(|(id, _)| *id))
+            .collect::<BTreeSet<_>>();
+        self.types
+            .keys()
+            .filter(|id| self.lookup(**id).is_none() && !referenced.contains(*id))
+            .map(|id| translate::Error::UnreachableType(*id))
+            .collect()
+    }
+
+    /// Extracts a minimal type system containing only the types reachable from the `roots`,
+    /// together with their symbols and the libraries defining them. Anonymous types can be used
+    /// as roots by their semantic ids.
+    pub fn extract(&self, roots: impl IntoIterator<Item = TypeSpec>) -> Result<Self, ExtractError> {
+        let mut ids = BTreeSet::new();
+        for spec in roots {
+            match self.to_sem_id(spec.clone()) {
+                Some(sem_id) if self.types.get(sem_id).is_some() => ids.insert(sem_id),
+                _ => return Err(ExtractError::TypeAbsent(spec)),
+            };
+        }
+        let types = self.types.extract(ids).expect("type system is complete");
+
+        let symbols = self
+            .symbols
+            .symbols
+            .iter()
+            .filter(|sym| types.get(sym.id).is_some())
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        let used_libs =
+            symbols.iter().filter_map(|sym| Some(&sym.fqn.as_ref()?.lib)).collect::<BTreeSet<_>>();
+        let libs = self
+            .symbols
+            .libs
+            .iter()
+            .filter(|dep| used_libs.contains(&dep.name))
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        let symbols = Symbols {
+            libs: Confined::from_collection_unsafe(libs),
+            symbols: Confined::from_collection_unsafe(symbols),
+        };
+
+        Ok(Self { symbols, types })
+    }
+
     pub fn get(&self, spec: impl Into<TypeSpec>) -> Option<&Ty<SemId>> {
         let sem_id = self.to_sem_id(spec)?;
         self.types.get(sem_id)
@@ -172,7 +347,8 @@ impl Display for SymbolicSys {
         writeln!(f, "typesys -- {:+}", self.id())?;
         writeln!(f)?;
         for (id, ty) in self.types.as_inner() {
-            let ty = ty.clone().translate(&mut (), self).expect("type system inconsistency");
+            let ty: Ty<TypeSymbol> =
+                ty.clone().translate(&mut (), self).expect("type system inconsistency");
             match self.lookup(*id) {
                 Some(fqn) => {
                     writeln!(f, "-- {id:-}")?;
@@ -192,3 +368,224 @@ impl armor::StrictArmor for SymbolicSys {
 
     fn armor_id(&self) -> Self::Id { self.id() }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::value::test_helpers::test_system;
+    use crate::{SymbolicLib, SystemBuilder};
+
+    fn tampered(
+        sys: &SymbolicSys,
+        f: impl FnOnce(&mut BTreeMap<SemId, Ty<SemId>>, &mut Symbols),
+    ) -> SymbolicSys {
+        let mut types = sys.as_types().as_inner().clone();
+        let mut symbols = sys.as_symbols().clone();
+        f(&mut types, &mut symbols);
+        SymbolicSys::new(TypeSystem::from(Confined::try_from(types).unwrap()), symbols)
+    }
+
+    #[test]
+    fn validate() {
+        let sys = test_system();
+        sys.validate().unwrap();
+        let data = sys.to_strict_serialized::<{ u32::MAX as usize }>().unwrap();
+        assert_eq!(
+            SymbolicSys::from_strict_serialized::<{ u32::MAX as usize }>(data).unwrap(),
+            sys
+        );
+    }
+
+    #[test]
+    fn id_mismatch() {
+        let sys = test_system();
+        let sem_id = sys.to_sem_id("TestLib.Precision").unwrap();
+        let tampered = tampered(&sys, |types, _| {
+            types.insert(sem_id, Ty::U8);
+        });
+        assert!(matches!(
+            tampered.validate().unwrap_err().as_slice(),
+            [translate::Error::IdMismatch { stated, .. }] if *stated == sem_id
+        ));
+        let data = tampered.to_strict_serialized::<{ u32::MAX as usize }>().unwrap();
+        assert!(matches!(
+            SymbolicSys::from_strict_serialized::<{ u32::MAX as usize }>(data),
+            Err(encoding::DeserializeError::Decode(DecodeError::DataIntegrityError(_)))
+        ));
+    }
+
+    #[test]
+    fn unreachable() {
+        let sys = test_system();
+        let ty = Ty::<InlineRef>::U1024;
+        let sem_id = ty.sem_id_unnamed();
+        let tampered = tampered(&sys, |types, symbols| {
+            types.insert(sem_id, Ty::U1024);
+            symbols.update_unchecked(sem_id, None).unwrap();
+        });
+        assert_eq!(tampered.unreachable_types(), vec![translate::Error::UnreachableType(sem_id)]);
+        // Unreferenced types may be roots of the system, thus they are not rejected on load
+        tampered.validate().unwrap();
+        tampered.as_types().validate().unwrap();
+        assert!(sys.unreachable_types().is_empty());
+    }
+
+    #[test]
+    fn extract_anonymous() {
+        let sys = test_system();
+        let nominal = sys.to_sem_id("TestLib.Nominal").unwrap();
+        let Some(Ty::Struct(fields)) = sys.get(nominal) else {
+            panic!("nominal must be a structure")
+        };
+        let name = fields.iter().find(|field| field.name == fname!("name")).unwrap().ty;
+        let types = sys.as_types().extract([name]).unwrap();
+        types.validate().unwrap();
+        let data = types.to_strict_serialized::<{ u32::MAX as usize }>().unwrap();
+        assert_eq!(
+            TypeSystem::from_strict_serialized::<{ u32::MAX as usize }>(data).unwrap(),
+            types
+        );
+    }
+
+    #[test]
+    fn unnamed_id_mismatch() {
+        let sys = test_system();
+        let sem_id = Ty::<InlineRef>::U1024.sem_id_unnamed();
+        let tampered = tampered(&sys, |types, _| {
+            types.insert(sem_id, Ty::U8);
+        });
+        assert_eq!(tampered.validate().unwrap_err(), vec![translate::Error::IdMismatch {
+            stated: sem_id,
+            computed: Ty::<InlineRef>::U8.sem_id_unnamed(),
+        }]);
+    }
+
+    #[test]
+    fn unknown_symbol() {
+        let sys = test_system();
+        let sem_id = sys.to_sem_id("TestLib.Nominal").unwrap();
+        let tampered = tampered(&sys, |types, _| {
+            types.remove(&sem_id);
+        });
+        assert!(tampered.validate().unwrap_err().contains(&translate::Error::UnknownType(sem_id)));
+    }
+
+    #[test]
+    fn recursive() {
+        let sys = test_system();
+        let types = sys.as_types();
+        let tree = sys.to_sem_id("TestLib.Tree").unwrap();
+        let expr = sys.to_sem_id("TestLib.Expr").unwrap();
+        let groups = types.recursion_groups();
+        assert!(groups.iter().any(|group| group.contains(&tree)));
+        assert!(groups.iter().any(|group| group.contains(&expr) && !group.contains(&tree)));
+
+        let extracted = types.extract([expr]).unwrap();
+        assert!(extracted.get(tree).is_some());
+        extracted.validate().unwrap();
+
+        let infos = sys.type_tree(expr).unwrap().iter().collect::<Vec<_>>();
+        let back_refs = infos.iter().filter(|info| info.back_ref).collect::<Vec<_>>();
+        assert!(back_refs.iter().any(|info| info.fqn.as_ref() == sys.lookup(expr)));
+        assert!(back_refs.iter().any(|info| info.fqn.as_ref() == sys.lookup(tree)));
+        assert!(sys.type_tree(expr).unwrap().to_string().contains("recursive"));
+    }
+
+    #[test]
+    fn infinite() {
+        let src = "typelib Test
+data Infinite : value U8, next Infinite
+data Finite : value U8, next Infinite?
+";
+        let lib = SymbolicLib::from_source(src, None).unwrap().compile().unwrap();
+        let name = tn!("Infinite");
+        let infinite = lib.type_ids()[&name];
+        let errors = SystemBuilder::new().import(lib).unwrap().finalize().unwrap_err();
+        // The `some` variant of `Infinite?` wraps the infinite type into a tuple, which is
+        // infinite as well, while `Finite` may have no next item.
+        assert_eq!(errors.len(), 2);
+        assert!(errors.contains(&translate::Error::InfiniteType(infinite)));
+        assert!(errors.iter().all(|err| matches!(err, translate::Error::InfiniteType(_))));
+    }
+
+    #[test]
+    fn recursion_group_ids() {
+        let compile = |src: &str| SymbolicLib::from_source(src, None).unwrap().compile().unwrap();
+        let lib1 = compile("typelib Test\ndata A : next [B ^ ..0xff]\ndata B : a A?\n");
+        let lib2 = compile("typelib Test\ndata A : next [B ^ ..0xff]\ndata B : a A?, x U8\n");
+        let name = tn!("A");
+        // `A` refers to `B` by its back-reference marker only, but still commits to its definition
+        assert_eq!(lib1.types[&name].sem_id_named(&name), lib2.types[&name].sem_id_named(&name));
+        assert_ne!(lib1.type_ids()[&name], lib2.type_ids()[&name]);
+
+        let sys1 = SystemBuilder::new().import(lib1.clone()).unwrap().finalize().unwrap();
+        let sys2 = SystemBuilder::new().import(lib2.clone()).unwrap().finalize().unwrap();
+        assert_eq!(
+            sys1.resolve(TypeFqn::with(libname!("Test"), name.clone())),
+            Some(&lib1.type_ids()[&name])
+        );
+        assert_ne!(sys1.to_sem_id("Test.A"), sys2.to_sem_id("Test.A"));
+        assert_eq!(lib1.to_symbolic().unwrap().compile().unwrap(), lib1);
+    }
+
+    #[test]
+    fn conflicting_import() {
+        // `A` references `B` by its back-reference marker, which is dangling in one library and
+        // resolves to a non-recursive type in another one, giving the same id to the different
+        // definitions of `A`.
+        let lib = SymbolicLib::from_source("typelib Test\ndata A : next B\ndata B : a A?\n", None)
+            .unwrap()
+            .compile()
+            .unwrap();
+        let mut lib1 = lib.clone();
+        lib1.types.remove(&tn!("B")).unwrap();
+        let mut lib2 = lib;
+        lib2.types.insert(tn!("B"), Ty::U8).unwrap();
+        let id = lib1.type_ids()[&tn!("A")];
+        assert_eq!(lib2.type_ids()[&tn!("A")], id);
+        let builder = SystemBuilder::new().import(lib1).unwrap();
+        assert_eq!(builder.import(lib2).unwrap_err(), translate::Error::ConflictingType(id));
+    }
+
+    #[test]
+    fn extract() {
+        let sys = test_system();
+        let nominal = sys.to_sem_id("TestLib.Nominal").unwrap();
+        let extracted = sys.extract([TypeSpec::from("TestLib.Nominal")]).unwrap();
+        extracted.validate().unwrap();
+        assert_eq!(extracted.to_sem_id("TestLib.Nominal"), Some(nominal));
+        assert!(extracted.to_sem_id("TestLib.Precision").is_some());
+        assert!(extracted.to_sem_id("TestLib.Amounts").is_none());
+        assert!(extracted.as_types().len() < sys.as_types().len());
+        assert!(extracted.to_sem_id("StrictTypes.TypeLib").is_none());
+
+        let extracted = sys.extract([TypeSpec::from("TestLib.Precision")]).unwrap();
+        extracted.validate().unwrap();
+        assert_eq!(extracted.as_types().len(), 1);
+        assert_eq!(extracted.as_symbols().libs().len(), 1);
+
+        let expr = sys.to_sem_id("TestLib.Expr").unwrap();
+        let extracted = sys.extract([TypeSpec::from(expr)]).unwrap();
+        extracted.validate().unwrap();
+        assert!(extracted.to_sem_id("TestLib.Tree").is_some());
+
+        let Some(Ty::Struct(fields)) = sys.get(nominal) else {
+            panic!("nominal must be a structure")
+        };
+        let name = fields.iter().find(|field| field.name == fname!("name")).unwrap().ty;
+        let extracted = sys.extract([TypeSpec::from(name)]).unwrap();
+        extracted.validate().unwrap();
+        assert!(extracted.as_types().get(name).is_some());
+        assert!(extracted.to_sem_id("TestLib.Nominal").is_none());
+        let data = extracted.to_strict_serialized::<{ u32::MAX as usize }>().unwrap();
+        assert_eq!(
+            SymbolicSys::from_strict_serialized::<{ u32::MAX as usize }>(data).unwrap(),
+            extracted
+        );
+        assert!(sys.extract([TypeSpec::from(name), TypeSpec::from(nominal)]).is_ok());
+        assert_eq!(
+            sys.extract([TypeSpec::from("TestLib.Absent")]).unwrap_err(),
+            ExtractError::TypeAbsent(TypeSpec::from("TestLib.Absent"))
+        );
+    }
+}
@@ -92,6 +92,11 @@ pub struct SystemBuilder {
     pending_deps: BTreeSet<Dependency>,
     imported_deps: BTreeSet<Dependency>,
     types: BTreeMap<SemId, SymTy>,
+    /// Back-reference markers used by the recursive types of the library being imported.
+    back_refs: BTreeMap<SemId, SemId>,
+    /// Ids of the recursion groups of the library being imported, indexed by the
+    /// back-reference markers of their types.
+    groups: BTreeMap<SemId, SemId>,
 }
 
 impl SystemBuilder {
@@ -101,51 +106,68 @@ impl SystemBuilder {
         let dependency = Dependency::from(&lib);
         self.pending_deps.remove(&dependency);
         self.imported_deps.insert(dependency);
-        self.pending_deps
-            .extend(lib.dependencies.into_iter().filter(|dep| !self.imported_deps.contains(dep)));
-
+        self.pending_deps.extend(
+            lib.dependencies.iter().filter(|dep| !self.imported_deps.contains(*dep)).cloned(),
+        );
+
+        let ids = lib.type_ids();
+        self.back_refs =
+            ids.iter().map(|(ty_name, id)| (SemId::back_ref(&lib.name, ty_name), *id)).collect();
+        self.groups = lib
+            .recursion_groups()
+            .into_iter()
+            .map(|(ty_name, group)| (SemId::back_ref(&lib.name, &ty_name), group))
+            .collect();
         for (ty_name, ty) in lib.types {
-            let id = ty.sem_id_named(&ty_name);
+            let id = ids[&ty_name];
             let ty = ty.translate(&mut self, &())?;
             let info = SymTy::named(lib.name.clone(), ty_name.clone(), ty);
-            self.types.insert(id, info);
+            self.insert(id, info)?;
         }
 
         Ok(self)
     }
 
     pub fn finalize(self) -> Result<SymbolicSys, Vec<Error>> {
-        let mut errors = vec![];
-
-        for dep in self.pending_deps {
-            errors.push(Error::AbsentImport(dep));
-        }
+        let mut errors = self.pending_deps.into_iter().map(Error::AbsentImport).collect::<Vec<_>>();
 
-        for (sem_id, info) in &self.types {
-            for (inner_id, _) in info.ty.type_refs() {
-                if !self.types.contains_key(sem_id) {
-                    errors.push(Error::InnerTypeAbsent {
-                        unknown: *inner_id,
-                        known: *sem_id,
-                    });
-                }
-            }
-        }
+        let sys = SymbolicSys::with(self.imported_deps, self.types).map_err(|err| vec![err])?;
+        errors.extend(sys.validate().err().unwrap_or_default());
+        errors.extend(sys.unreachable_types());
         if !errors.is_empty() {
             return Err(errors);
         }
 
-        SymbolicSys::with(self.imported_deps, self.types).map_err(|err| vec![err])
+        Ok(sys)
+    }
+
+    fn resolve(&self, sem_id: SemId) -> SemId {
+        self.back_refs.get(&sem_id).copied().unwrap_or(sem_id)
+    }
+
+    /// Adds type to the system, failing if the same id was already used for a different type.
+    fn insert(&mut self, id: SemId, info: SymTy) -> Result<(), Error> {
+        if matches!(self.types.get(&id), Some(present) if present.ty != info.ty) {
+            return Err(Error::ConflictingType(id));
+        }
+        self.types.insert(id, info);
+        Ok(())
     }
 
-    fn translate_inline<Ref: LibSubref>(&mut self, inline_ty: Ty<Ref>) -> Result<SemId, Error>
-    where Ref: Translate<SemId, Context = (), Builder = SystemBuilder, Error = Error> {
-        // compute id
-        let id = inline_ty.sem_id_unnamed();
+    fn translate_inline<Ref>(&mut self, inline_ty: Ty<Ref>) -> Result<SemId, Error>
+    where Ref: LibSubref + Translate<SemId, Context = (), Builder = SystemBuilder, Error = Error>
+    {
+        // compute id; inline types using back-reference markers are a part of a recursion group
+        let mut id = inline_ty.sem_id_unnamed();
+        let mut refs = bset![];
+        inline_ty.collect_named(&mut refs);
+        if let Some(group) = refs.iter().find_map(|marker| self.groups.get(marker)) {
+            id = id.grouped(*group);
+        }
         // run for nested types
         let ty = inline_ty.translate(self, &())?;
         // add to system
-        self.types.insert(id, SymTy::unnamed(ty));
+        self.insert(id, SymTy::unnamed(ty))?;
         Ok(id)
     }
 }
@@ -161,7 +183,7 @@ impl Translate<SemId> for LibRef {
         _ctx: &Self::Context,
     ) -> Result<SemId, Self::Error> {
         match self {
-            LibRef::Named(sem_id) => Ok(sem_id),
+            LibRef::Named(sem_id) => Ok(builder.resolve(sem_id)),
             LibRef::Inline(inline_ty) => builder.translate_inline(inline_ty),
             LibRef::Extern(ExternRef { sem_id, .. }) => Ok(sem_id),
         }
@@ -179,7 +201,7 @@ impl Translate<SemId> for InlineRef {
         _ctx: &Self::Context,
     ) -> Result<SemId, Self::Error> {
         match self {
-            InlineRef::Named(sem_id) => Ok(sem_id),
+            InlineRef::Named(sem_id) => Ok(builder.resolve(sem_id)),
             InlineRef::Inline(inline_ty) => builder.translate_inline(inline_ty),
             InlineRef::Extern(ExternRef { sem_id, .. }) => Ok(sem_id),
         }
@@ -197,7 +219,7 @@ impl Translate<SemId> for InlineRef1 {
         _ctx: &Self::Context,
     ) -> Result<SemId, Self::Error> {
         match self {
-            InlineRef1::Named(sem_id) => Ok(sem_id),
+            InlineRef1::Named(sem_id) => Ok(builder.resolve(sem_id)),
             InlineRef1::Inline(inline_ty) => builder.translate_inline(inline_ty),
             InlineRef1::Extern(ExternRef { sem_id, .. }) => Ok(sem_id),
         }
@@ -211,16 +233,95 @@ impl Translate<SemId> for InlineRef2 {
 
     fn translate(
         self,
-        _builder: &mut Self::Builder,
+        builder: &mut Self::Builder,
         _ctx: &Self::Context,
     ) -> Result<SemId, Self::Error> {
         match self {
-            InlineRef2::Named(sem_id) => Ok(sem_id),
+            InlineRef2::Named(sem_id) => Ok(builder.resolve(sem_id)),
             InlineRef2::Extern(ExternRef { sem_id, .. }) => Ok(sem_id),
         }
     }
 }
 
+/// Back-reference markers for the named types of a recursion group, indexed by their ids.
+pub(crate) type BackRefs = BTreeMap<SemId, SemId>;
+
+impl SymbolicSys {
+    /// Returns definition of an anonymous type, which has to be inlined when the id of the type
+    /// referencing it is computed.
+    fn inline_ty(&self, sem_id: SemId) -> Result<Option<Ty<SemId>>, Error> {
+        let ty = self.as_types().get(sem_id).ok_or(Error::UnknownType(sem_id))?;
+        Ok(self.symbols.lookup(sem_id).is_none().then(|| ty.clone()))
+    }
+}
+
+// Translations of the type system types back into library types, such that the type ids can be
+// recomputed from the type definitions.
+
+impl Translate<LibRef> for SemId {
+    type Context = SymbolicSys;
+    type Builder = BackRefs;
+    type Error = Error;
+
+    fn translate(self, builder: &mut Self::Builder, ctx: &Self::Context) -> Result<LibRef, Error> {
+        match ctx.inline_ty(self)? {
+            Some(ty) => ty.translate(builder, ctx).map(LibRef::Inline),
+            None => Ok(LibRef::Named(builder.get(&self).copied().unwrap_or(self))),
+        }
+    }
+}
+
+impl Translate<InlineRef> for SemId {
+    type Context = SymbolicSys;
+    type Builder = BackRefs;
+    type Error = Error;
+
+    fn translate(
+        self,
+        builder: &mut Self::Builder,
+        ctx: &Self::Context,
+    ) -> Result<InlineRef, Error> {
+        match ctx.inline_ty(self)? {
+            Some(ty) => ty.translate(builder, ctx).map(InlineRef::Inline),
+            None => Ok(InlineRef::Named(builder.get(&self).copied().unwrap_or(self))),
+        }
+    }
+}
+
+impl Translate<InlineRef1> for SemId {
+    type Context = SymbolicSys;
+    type Builder = BackRefs;
+    type Error = Error;
+
+    fn translate(
+        self,
+        builder: &mut Self::Builder,
+        ctx: &Self::Context,
+    ) -> Result<InlineRef1, Error> {
+        match ctx.inline_ty(self)? {
+            Some(ty) => ty.translate(builder, ctx).map(InlineRef1::Inline),
+            None => Ok(InlineRef1::Named(builder.get(&self).copied().unwrap_or(self))),
+        }
+    }
+}
+
+impl Translate<InlineRef2> for SemId {
+    type Context = SymbolicSys;
+    type Builder = BackRefs;
+    type Error = Error;
+
+    fn translate(
+        self,
+        builder: &mut Self::Builder,
+        ctx: &Self::Context,
+    ) -> Result<InlineRef2, Error> {
+        match ctx.inline_ty(self)? {
+            Some(_) => Err(Error::TooDeep),
+            None => Ok(InlineRef2::Named(builder.get(&self).copied().unwrap_or(self))),
+        }
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Debug, Display, From, Error)]
 #[display(doc_comments)]
 pub enum Error {
@@ -240,6 +341,18 @@ pub enum Error {
     /// library defining this type.
     InnerTypeAbsent { unknown: SemId, known: SemId },
 
+    /// anonymous type `{0}` is not referenced by any other type of the type system.
+    UnreachableType(SemId),
+
+    /// type `{stated}` doesn't match its definition, which has id `{computed}`.
+    IdMismatch { stated: SemId, computed: SemId },
+
+    /// type `{0}` is defined differently by the imported libraries.
+    ConflictingType(SemId),
+
+    /// type `{0}` is infinitely recursive and doesn't have values of a finite size.
+    InfiniteType(SemId),
+
     #[from]
     #[display(inner)]
     Confinement(confinement::Error),
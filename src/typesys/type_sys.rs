@@ -26,18 +26,23 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{self, Display, Formatter};
 use std::ops::Index;
+use std::str::FromStr;
 
 use amplify::confinement::{self, Confined, MediumOrdMap};
 use amplify::num::u24;
-use encoding::{LibName, Sizing, StrictDeserialize, StrictSerialize, TypeName};
+use encoding::{
+    DecodeError, InvalidRString, LibName, Sizing, StrictDecode, StrictDeserialize, StrictSerialize,
+    TypeName, TypedRead,
+};
 use strict_encoding::STRICT_TYPES_LIB;
 
 use crate::ast::UnnamedFields;
+use crate::typesys::translate;
 use crate::{SemId, Ty};
 
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display, Error)]
 #[display("type with id `{0}` is not a part of the type system.")]
-pub struct UnknownType(SemId);
+pub struct UnknownType(pub(crate) SemId);
 
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
 #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
@@ -58,6 +63,34 @@ impl TypeFqn {
     }
 }
 
+/// Errors parsing fully qualified type name.
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum FqnParseError {
+    /// type name `{0}` is not fully qualified, i.e. it lacks library name prefix.
+    NoLib(String),
+
+    /// invalid library name in `{0}`. Details: {1}
+    InvalidLib(String, InvalidRString),
+
+    /// invalid type name in `{0}`. Details: {1}
+    InvalidName(String, InvalidRString),
+}
+
+impl FromStr for TypeFqn {
+    type Err = FqnParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (lib, name) = s.split_once('.').ok_or_else(|| FqnParseError::NoLib(s.to_owned()))?;
+        Ok(TypeFqn {
+            lib: LibName::from_str(lib)
+                .map_err(|err| FqnParseError::InvalidLib(s.to_owned(), err))?,
+            name: TypeName::from_str(name)
+                .map_err(|err| FqnParseError::InvalidName(s.to_owned(), err))?,
+        })
+    }
+}
+
 impl From<&'static str> for TypeFqn {
     fn from(value: &'static str) -> Self {
         let Some((lib, name)) = value.split_once('.') else {
@@ -110,17 +143,41 @@ impl SymTy {
 /// - Type system is complete (i.e. no type references a type which is not a part of the system).
 #[derive(Wrapper, Clone, Eq, PartialEq, Debug, Default, From)]
 #[wrapper(Deref)]
-#[derive(StrictType, StrictEncode, StrictDecode)]
+#[derive(StrictType, StrictEncode)]
 #[strict_type(lib = STRICT_TYPES_LIB)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(crate = "serde_crate"))]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(crate = "serde_crate"))]
 pub struct TypeSystem(MediumOrdMap<SemId, Ty<SemId>>);
 
 impl StrictSerialize for TypeSystem {}
 impl StrictDeserialize for TypeSystem {}
 
+impl StrictDecode for TypeSystem {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let types = MediumOrdMap::strict_decode(reader)?;
+        TypeSystem::with_checked(types)
+            .map_err(|err| DecodeError::DataIntegrityError(err.to_string()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for TypeSystem {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let types = MediumOrdMap::deserialize(deserializer)?;
+        TypeSystem::with_checked(types).map_err(serde::de::Error::custom)
+    }
+}
+
 impl TypeSystem {
     pub fn new() -> Self { Self::default() }
 
+    /// Constructs type system, checking it with [`TypeSystem::validate`] and reporting the first
+    /// of the found errors.
+    fn with_checked(types: MediumOrdMap<SemId, Ty<SemId>>) -> Result<Self, translate::Error> {
+        let sys = TypeSystem(types);
+        sys.validate().map_err(|mut errors| errors.remove(0))?;
+        Ok(sys)
+    }
+
     pub fn count_types(&self) -> u24 { self.0.len_u24() }
 
     pub(super) fn insert_unchecked(
@@ -152,14 +209,52 @@ impl TypeSystem {
         Ok(Self(Confined::from_collection_unsafe(extract)))
     }
 
+    /// Checks that the type system is complete, i.e. all types referenced by the types of the
+    /// system are present in it, and that all recursive types have values of a finite size.
+    ///
+    /// Types which are not referenced by other types are accepted, since any of them may be a
+    /// root of the system, like the ones of a system produced by [`Self::extract`]. Type system
+    /// doesn't know which of its types are named, thus the type ids are checked against the type
+    /// definitions only by [`SymbolicSys::validate`].
+    ///
+    /// [`SymbolicSys::validate`]: crate::SymbolicSys::validate
+    pub fn validate(&self) -> Result<(), Vec<translate::Error>> {
+        let errors = self
+            .0
+            .iter()
+            .flat_map(|(known, ty)| {
+                ty.type_refs().filter(|(id, _)| !self.0.contains_key(*id)).map(|(unknown, _)| {
+                    translate::Error::InnerTypeAbsent {
+                        unknown: *unknown,
+                        known: *known,
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        let depths = self.value_depths();
+        let errors = self
+            .0
+            .keys()
+            .filter(|id| !depths.contains_key(*id))
+            .map(|id| translate::Error::InfiniteType(*id))
+            .collect::<Vec<_>>();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(())
+    }
+
     pub(crate) fn rstring_sizing(
         &self,
         fields: &UnnamedFields<SemId>,
     ) -> Result<Option<(SemId, Sizing)>, UnknownType> {
         let rest = fields[1];
-        let rest = self.find(rest).ok_or_else(|| UnknownType(rest))?;
+        let rest = self.find(rest).ok_or(UnknownType(rest))?;
         if let Ty::List(rest, sizing) = rest {
-            let mut sizing = sizing.clone();
+            let mut sizing = *sizing;
             sizing.min += 1;
             sizing.max += 1;
             return Ok(Some((*rest, sizing)));
@@ -176,8 +271,8 @@ impl TypeSystem {
             return Ok(false);
         };
 
-        Ok(self.find(first).ok_or_else(|| UnknownType(first))?.is_char_enum()
-            && self.find(rest).ok_or_else(|| UnknownType(rest))?.is_char_enum())
+        Ok(self.find(first).ok_or(UnknownType(first))?.is_char_enum()
+            && self.find(rest).ok_or(UnknownType(rest))?.is_char_enum())
     }
 }
 
@@ -20,6 +20,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::env;
 use std::fmt::{self, Display, Formatter};
 use std::str::FromStr;
@@ -59,71 +60,56 @@ impl FromStr for StlFormat {
     }
 }
 
-pub fn parse_args() -> (StlFormat, Option<String>) {
-    let args: Vec<String> = env::args().collect();
-    let ext = args.get(1).map(String::as_str).map(|s| s.trim_start_matches("--")).unwrap_or("sty");
-    let format = StlFormat::from_str(ext).expect("unrecognized file format argument");
-    let dir = match args.len() {
-        1 => None,
-        2 | 3 => Some(args.get(2).cloned().unwrap_or_else(|| s!("stl"))),
-        _ => panic!("invalid argument count"),
-    };
-    (format, dir)
-}
-
-/* TODO: Move into layout mod
-/// Measure of a type size in bytes
-#[derive(Copy, Clone, PartialEq, Eq, Debug, Display)]
-pub enum Size {
-    /// Type has a fixed size known at compile time
-    #[display(inner)]
-    Fixed(u16),
-
-    /// Type has variable size
-    #[display("variable")]
-    Variable,
+/// File formats for type systems.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+pub enum StsFormat {
+    #[display("sts")]
+    Binary,
+    #[cfg(feature = "armor")]
+    #[display("stsa")]
+    Armored,
+    /// Human-readable listing of the type system, which can't be loaded back.
+    #[display("stsl")]
+    Listing,
 }
 
-impl PartialOrd for Size {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
-}
+impl FromStr for StsFormat {
+    type Err = UnknownFormat;
 
-impl Ord for Size {
-    fn cmp(&self, other: &Self) -> Ordering {
-        match (self, other) {
-            (Size::Variable, Size::Variable) => Ordering::Equal,
-            (Size::Variable, _) => Ordering::Greater,
-            (_, Size::Variable) => Ordering::Less,
-            (Size::Fixed(a), Size::Fixed(b)) => a.cmp(b),
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sts" => Ok(StsFormat::Binary),
+            #[cfg(feature = "armor")]
+            "stsa" => Ok(StsFormat::Armored),
+            "stsl" => Ok(StsFormat::Listing),
+            invalid => Err(UnknownFormat(invalid.to_owned())),
         }
     }
 }
 
-impl Add for Size {
-    type Output = Size;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        match (self, rhs) {
-            (Size::Fixed(a), Size::Fixed(b)) => Size::Fixed(a + b),
-            _ => Size::Variable,
-        }
-    }
-}
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum ArgsError {
+    /// {0}
+    #[from]
+    Format(UnknownFormat),
 
-impl AddAssign for Size {
-    fn add_assign(&mut self, rhs: Self) { *self = *self + rhs; }
+    /// invalid argument count {0}; expected a format and an optional output directory.
+    Count(usize),
 }
 
-impl Sum for Size {
-    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
-        let mut acc = Size::Fixed(0);
-        for item in iter {
-            acc += item;
-        }
-        acc
-    }
+#[deprecated(since = "2.7.0", note = "use the `strict-types` command-line tool")]
+pub fn parse_args() -> Result<(StlFormat, Option<String>), ArgsError> {
+    let args: Vec<String> = env::args().collect();
+    let ext = args.get(1).map(String::as_str).map(|s| s.trim_start_matches("--")).unwrap_or("sty");
+    let format = StlFormat::from_str(ext)?;
+    let dir = match args.len() {
+        1 => None,
+        2 | 3 => Some(args.get(2).cloned().unwrap_or_else(|| s!("stl"))),
+        count => return Err(ArgsError::Count(count - 1)),
+    };
+    Ok((format, dir))
 }
- */
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Display, From)]
 #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
@@ -210,3 +196,100 @@ pub enum Urn {
     #[display("urn:sten:id:{0}", alt = "urn:sten:id:{0:#}")]
     Type(SemId),
 }
+
+/// Finds groups of mutually recursive nodes in a directed graph, i.e. its strongly connected
+/// components containing a cycle. Edges leading to nodes which are not keys of the graph are
+/// ignored.
+///
+/// The graph is traversed without recursion, so this is safe to run on untrusted data.
+pub(crate) fn recursion_groups<N: Ord + Clone>(
+    graph: &BTreeMap<N, BTreeSet<N>>,
+) -> Vec<BTreeSet<N>> {
+    const UNVISITED: usize = usize::MAX;
+
+    let nodes = graph.keys().collect::<Vec<_>>();
+    let pos = nodes.iter().enumerate().map(|(no, node)| (*node, no)).collect::<BTreeMap<_, _>>();
+    let edges = graph
+        .values()
+        .map(|refs| refs.iter().filter_map(|node| pos.get(node).copied()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let mut index = vec![UNVISITED; nodes.len()];
+    let mut low = vec![0usize; nodes.len()];
+    let mut on_stack = vec![false; nodes.len()];
+    let mut stack = Vec::<usize>::new();
+    let mut groups = Vec::new();
+    let mut counter = 0usize;
+
+    for root in 0..nodes.len() {
+        if index[root] != UNVISITED {
+            continue;
+        }
+        index[root] = counter;
+        low[root] = counter;
+        counter += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        let mut calls = vec![(root, 0usize)];
+
+        while let Some((v, next)) = calls.last().copied() {
+            if let Some(&w) = edges[v].get(next) {
+                if let Some(call) = calls.last_mut() {
+                    call.1 += 1;
+                }
+                if index[w] == UNVISITED {
+                    index[w] = counter;
+                    low[w] = counter;
+                    counter += 1;
+                    stack.push(w);
+                    on_stack[w] = true;
+                    calls.push((w, 0));
+                } else if on_stack[w] {
+                    low[v] = low[v].min(index[w]);
+                }
+                continue;
+            }
+
+            calls.pop();
+            if let Some((u, _)) = calls.last() {
+                low[*u] = low[*u].min(low[v]);
+            }
+            if low[v] != index[v] {
+                continue;
+            }
+            let mut group = BTreeSet::new();
+            loop {
+                let w = stack.pop().expect("node of a component is always on stack");
+                on_stack[w] = false;
+                group.insert(nodes[w].clone());
+                if w == v {
+                    break;
+                }
+            }
+            if group.len() > 1 || edges[v].contains(&v) {
+                groups.push(group);
+            }
+        }
+    }
+
+    groups
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn groups() {
+        let graph = bmap! {
+            1 => bset![2],
+            2 => bset![3, 5],
+            3 => bset![1],
+            4 => bset![4],
+            5 => bset![6],
+            6 => bset![],
+            7 => bset![8, 9],
+        };
+        assert_eq!(recursion_groups(&graph), vec![bset![1, 2, 3], bset![4]]);
+    }
+}
@@ -21,8 +21,29 @@
 // limitations under the License.
 
 //! Converts strict values from/to non-STON value serialization formats (JSON, YAML, TOML etc).
+//!
+//! Conversions implemented with [`From`] do not use type information and produce strict values
+//! which must be [typified](crate::typify) before they can be used. Type-directed conversions, like
+//! [`TypeSystem::from_json`] and [`TypedVal::to_json`], interpret values according to a type,
+//! following the representation described by [JSON Schema](crate::codegen::JsonSchemaGen)
+//! generated for the type.
 
-use crate::StrictVal;
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+
+use amplify::hex::{FromHex, ToHex};
+use amplify::num::apfloat::Float;
+use base64::prelude::{Engine, BASE64_STANDARD};
+use encoding::{FieldName, Primitive, Sizing, VariantName};
+use indexmap::IndexMap;
+
+use super::typify::{Error, TypeSpec, TypedVal};
+use super::val::float_to_le_bytes;
+use super::{EnumTag, StrictNum};
+use crate::ast::{EnumVariants, UnionVariants};
+use crate::codegen::BytesEncoding;
+use crate::typesys::TypeSymbol;
+use crate::{SemId, StrictVal, SymbolicSys, Ty, TypeRef, TypeSystem};
 
 impl From<serde_json::Value> for StrictVal {
     fn from(json: serde_json::Value) -> Self {
@@ -33,7 +54,7 @@ impl From<serde_json::Value> for StrictVal {
             Value::Bool(v) => StrictVal::bool(v),
             Value::Number(no) if no.is_u64() => StrictVal::num(no.as_u64().unwrap()),
             Value::Number(no) if no.is_i64() => StrictVal::num(no.as_i64().unwrap()),
-            Value::Number(no) if no.is_f64() => todo!(),
+            Value::Number(no) if no.is_f64() => StrictVal::num(no.as_f64().unwrap()),
             Value::Number(_) => {
                 unreachable!()
             }
@@ -55,7 +76,7 @@ impl From<serde_yaml::Value> for StrictVal {
             Value::Bool(v) => StrictVal::bool(v),
             Value::Number(no) if no.is_u64() => StrictVal::num(no.as_u64().unwrap()),
             Value::Number(no) if no.is_i64() => StrictVal::num(no.as_i64().unwrap()),
-            Value::Number(no) if no.is_f64() => todo!(),
+            Value::Number(no) if no.is_f64() => StrictVal::num(no.as_f64().unwrap()),
             Value::Number(_) => {
                 unreachable!()
             }
@@ -75,14 +96,1224 @@ impl From<toml::Value> for StrictVal {
 
         match toml {
             Value::Integer(no) => StrictVal::num(no),
-            Value::Float(_f) => todo!(),
+            Value::Float(no) => StrictVal::num(no),
             Value::Boolean(v) => StrictVal::bool(v),
             Value::String(s) => StrictVal::String(s),
             Value::Array(vec) => StrictVal::list(vec.into_iter().map(StrictVal::from)),
             Value::Table(map) => {
                 StrictVal::map(map.into_iter().map(|(k, v)| (k, StrictVal::from(v))))
             }
-            Value::Datetime(_dt) => todo!(),
+            Value::Datetime(dt) => StrictVal::String(dt.to_string()),
+        }
+    }
+}
+
+/// Format-independent representation of a JSON, YAML or TOML value.
+enum Node<'a> {
+    Null,
+    Bool(bool),
+    Number(StrictNum),
+    Str(Cow<'a, str>),
+    Seq(Vec<Node<'a>>),
+    Map(Vec<(Node<'a>, Node<'a>)>),
+}
+
+impl<'a> From<&'a serde_json::Value> for Node<'a> {
+    fn from(json: &'a serde_json::Value) -> Self {
+        use serde_json::Value;
+
+        match json {
+            Value::Null => Node::Null,
+            Value::Bool(v) => Node::Bool(*v),
+            Value::Number(no) => Node::Number(json_num(no)),
+            Value::String(s) => Node::Str(Cow::Borrowed(s)),
+            Value::Array(vec) => Node::Seq(vec.iter().map(Node::from).collect()),
+            Value::Object(map) => Node::Map(
+                map.iter().map(|(k, v)| (Node::Str(Cow::Borrowed(k)), Node::from(v))).collect(),
+            ),
+        }
+    }
+}
+
+impl<'a> From<&'a serde_yaml::Value> for Node<'a> {
+    fn from(yaml: &'a serde_yaml::Value) -> Self {
+        use serde_yaml::Value;
+
+        match yaml {
+            Value::Null => Node::Null,
+            Value::Bool(v) => Node::Bool(*v),
+            Value::Number(no) if no.is_u64() => Node::Number(no.as_u64().unwrap().into()),
+            Value::Number(no) if no.is_i64() => Node::Number(no.as_i64().unwrap().into()),
+            Value::Number(no) => Node::Number(no.as_f64().unwrap_or(f64::NAN).into()),
+            Value::String(s) => Node::Str(Cow::Borrowed(s)),
+            Value::Sequence(vec) => Node::Seq(vec.iter().map(Node::from).collect()),
+            Value::Mapping(map) => {
+                Node::Map(map.iter().map(|(k, v)| (Node::from(k), Node::from(v))).collect())
+            }
+            Value::Tagged(tagged) => Node::from(&tagged.value),
+        }
+    }
+}
+
+impl<'a> From<&'a toml::Value> for Node<'a> {
+    fn from(toml: &'a toml::Value) -> Self {
+        use toml::Value;
+
+        match toml {
+            Value::Integer(no) => Node::Number((*no).into()),
+            Value::Float(no) => Node::Number((*no).into()),
+            Value::Boolean(v) => Node::Bool(*v),
+            Value::String(s) => Node::Str(Cow::Borrowed(s)),
+            Value::Datetime(dt) => Node::Str(Cow::Owned(dt.to_string())),
+            Value::Array(vec) => Node::Seq(vec.iter().map(Node::from).collect()),
+            Value::Table(map) => Node::Map(
+                map.iter().map(|(k, v)| (Node::Str(Cow::Borrowed(k)), Node::from(v))).collect(),
+            ),
+        }
+    }
+}
+
+fn json_num(no: &serde_json::Number) -> StrictNum {
+    if let Some(no) = no.as_u64() {
+        StrictNum::from(no)
+    } else if let Some(no) = no.as_i64() {
+        StrictNum::from(no)
+    } else {
+        StrictNum::from(no.as_f64().unwrap_or(f64::NAN))
+    }
+}
+
+impl Node<'_> {
+    /// Converts the node into a strict value without using type information, for error reporting.
+    fn to_untyped(&self) -> StrictVal {
+        match self {
+            Node::Null => StrictVal::Unit,
+            Node::Bool(v) => StrictVal::bool(*v),
+            Node::Number(no) => StrictVal::Number(*no),
+            Node::Str(s) => StrictVal::String(s.to_string()),
+            Node::Seq(vec) => StrictVal::list(vec.iter().map(Node::to_untyped)),
+            Node::Map(map) => {
+                StrictVal::Map(map.iter().map(|(k, v)| (k.to_untyped(), v.to_untyped())).collect())
+            }
+        }
+    }
+}
+
+/// Encodes byte string using the provided encoding.
+fn encode_bytes(data: &[u8], encoding: BytesEncoding) -> String {
+    match encoding {
+        BytesEncoding::Hex => data.to_hex(),
+        BytesEncoding::Base64 => BASE64_STANDARD.encode(data),
+    }
+}
+
+/// Decodes byte string using the provided encoding.
+fn decode_bytes(s: &str, encoding: BytesEncoding) -> Option<Vec<u8>> {
+    match encoding {
+        BytesEncoding::Hex => Vec::<u8>::from_hex(s).ok(),
+        BytesEncoding::Base64 => BASE64_STANDARD.decode(s).ok(),
+    }
+}
+
+/// Attaches JSON pointer, constructed from the provided tokens, to the error.
+fn locate(tokens: &[String], err: Error) -> Error {
+    let mut pointer = String::new();
+    for token in tokens {
+        pointer.push('/');
+        pointer.push_str(&token.replace('~', "~0").replace('/', "~1"));
+    }
+    Error::Located(pointer, Box::new(err))
+}
+
+impl TypeSystem {
+    /// Converts JSON value into a strict value of the type with the provided id. Byte strings
+    /// must be hex-encoded.
+    ///
+    /// On failure the returned error is [`Error::Located`], providing JSON pointer to the invalid
+    /// value.
+    pub fn from_json(&self, sem_id: SemId, json: &serde_json::Value) -> Result<TypedVal, Error> {
+        self.from_json_with(sem_id, json, BytesEncoding::default())
+    }
+
+    /// Converts JSON value into a strict value of the type with the provided id, decoding byte
+    /// strings with the provided encoding.
+    pub fn from_json_with(
+        &self,
+        sem_id: SemId,
+        json: &serde_json::Value,
+        bytes: BytesEncoding,
+    ) -> Result<TypedVal, Error> {
+        self.load_node(sem_id, &Node::from(json), bytes)
+    }
+
+    /// Converts YAML value into a strict value of the type with the provided id. Byte strings
+    /// must be hex-encoded.
+    ///
+    /// On failure the returned error is [`Error::Located`], providing JSON pointer to the invalid
+    /// value.
+    pub fn from_yaml(&self, sem_id: SemId, yaml: &serde_yaml::Value) -> Result<TypedVal, Error> {
+        self.from_yaml_with(sem_id, yaml, BytesEncoding::default())
+    }
+
+    /// Converts YAML value into a strict value of the type with the provided id, decoding byte
+    /// strings with the provided encoding.
+    pub fn from_yaml_with(
+        &self,
+        sem_id: SemId,
+        yaml: &serde_yaml::Value,
+        bytes: BytesEncoding,
+    ) -> Result<TypedVal, Error> {
+        self.load_node(sem_id, &Node::from(yaml), bytes)
+    }
+
+    /// Converts TOML value into a strict value of the type with the provided id. Byte strings
+    /// must be hex-encoded.
+    ///
+    /// On failure the returned error is [`Error::Located`], providing JSON pointer to the invalid
+    /// value.
+    pub fn from_toml(&self, sem_id: SemId, toml: &toml::Value) -> Result<TypedVal, Error> {
+        self.from_toml_with(sem_id, toml, BytesEncoding::default())
+    }
+
+    /// Converts TOML value into a strict value of the type with the provided id, decoding byte
+    /// strings with the provided encoding.
+    pub fn from_toml_with(
+        &self,
+        sem_id: SemId,
+        toml: &toml::Value,
+        bytes: BytesEncoding,
+    ) -> Result<TypedVal, Error> {
+        self.load_node(sem_id, &Node::from(toml), bytes)
+    }
+
+    fn load_node(
+        &self,
+        sem_id: SemId,
+        node: &Node,
+        bytes: BytesEncoding,
+    ) -> Result<TypedVal, Error> {
+        let mut loader = Loader {
+            sys: self,
+            bytes,
+            pointer: vec![],
+        };
+        let val = loader.load(node, sem_id)?;
+        Ok(TypedVal {
+            orig: TypeSymbol::unnamed(sem_id),
+            val,
+        })
+    }
+}
+
+impl SymbolicSys {
+    /// Converts JSON value into a strict value of the provided type.
+    pub fn from_json(
+        &self,
+        spec: impl Into<TypeSpec>,
+        json: &serde_json::Value,
+    ) -> Result<TypedVal, Error> {
+        self.from_json_with(spec, json, BytesEncoding::default())
+    }
+
+    /// Converts JSON value into a strict value of the provided type, decoding byte strings with
+    /// the provided encoding.
+    pub fn from_json_with(
+        &self,
+        spec: impl Into<TypeSpec>,
+        json: &serde_json::Value,
+        bytes: BytesEncoding,
+    ) -> Result<TypedVal, Error> {
+        let spec = spec.into();
+        let sem_id = self.to_sem_id(spec.clone()).ok_or(Error::TypeAbsent(spec))?;
+        self.as_types().from_json_with(sem_id, json, bytes)
+    }
+
+    /// Converts YAML value into a strict value of the provided type.
+    pub fn from_yaml(
+        &self,
+        spec: impl Into<TypeSpec>,
+        yaml: &serde_yaml::Value,
+    ) -> Result<TypedVal, Error> {
+        self.from_yaml_with(spec, yaml, BytesEncoding::default())
+    }
+
+    /// Converts YAML value into a strict value of the provided type, decoding byte strings with
+    /// the provided encoding.
+    pub fn from_yaml_with(
+        &self,
+        spec: impl Into<TypeSpec>,
+        yaml: &serde_yaml::Value,
+        bytes: BytesEncoding,
+    ) -> Result<TypedVal, Error> {
+        let spec = spec.into();
+        let sem_id = self.to_sem_id(spec.clone()).ok_or(Error::TypeAbsent(spec))?;
+        self.as_types().from_yaml_with(sem_id, yaml, bytes)
+    }
+
+    /// Converts TOML value into a strict value of the provided type.
+    pub fn from_toml(
+        &self,
+        spec: impl Into<TypeSpec>,
+        toml: &toml::Value,
+    ) -> Result<TypedVal, Error> {
+        self.from_toml_with(spec, toml, BytesEncoding::default())
+    }
+
+    /// Converts TOML value into a strict value of the provided type, decoding byte strings with
+    /// the provided encoding.
+    pub fn from_toml_with(
+        &self,
+        spec: impl Into<TypeSpec>,
+        toml: &toml::Value,
+        bytes: BytesEncoding,
+    ) -> Result<TypedVal, Error> {
+        let spec = spec.into();
+        let sem_id = self.to_sem_id(spec.clone()).ok_or(Error::TypeAbsent(spec))?;
+        self.as_types().from_toml_with(sem_id, toml, bytes)
+    }
+}
+
+struct Loader<'sys> {
+    sys: &'sys TypeSystem,
+    bytes: BytesEncoding,
+    pointer: Vec<String>,
+}
+
+impl Loader<'_> {
+    fn locate(&self, err: Error) -> Error { locate(&self.pointer, err) }
+
+    /// Typifies a value which doesn't contain nested values requiring type-directed conversion.
+    fn leaf(&self, val: StrictVal, sem_id: SemId) -> Result<StrictVal, Error> {
+        self.sys.typify(val, sem_id).map(TypedVal::unbox).map_err(|err| self.locate(err))
+    }
+
+    fn nested(
+        &mut self,
+        token: impl ToString,
+        node: &Node,
+        sem_id: SemId,
+    ) -> Result<StrictVal, Error> {
+        self.pointer.push(token.to_string());
+        let val = self.load(node, sem_id)?;
+        self.pointer.pop();
+        Ok(val)
+    }
+
+    fn check_sizing(&self, len: usize, sizing: Sizing, sem_id: SemId) -> Result<(), Error> {
+        if !sizing.check(len) {
+            return Err(self.locate(Error::OutOfBounds(sem_id.into(), len, sizing)));
+        }
+        Ok(())
+    }
+
+    fn load(&mut self, node: &Node, sem_id: SemId) -> Result<StrictVal, Error> {
+        let Some(ty) = self.sys.find(sem_id) else {
+            return Err(self.locate(Error::TypeAbsent(sem_id.into())));
+        };
+        Ok(match (node, ty) {
+            (Node::Null, Ty::Primitive(prim)) if *prim == Primitive::UNIT => StrictVal::Unit,
+            (Node::Number(no), Ty::Primitive(_)) => self.leaf(StrictVal::Number(*no), sem_id)?,
+            // Large numbers may be represented with strings
+            (Node::Str(s), Ty::Primitive(prim)) if *prim != Primitive::UNIT => {
+                match StrictVal::parse_ston(s) {
+                    Ok(val @ StrictVal::Number(_)) => self.leaf(val, sem_id)?,
+                    _ => return Err(self.mismatch(node, ty)),
+                }
+            }
+
+            (Node::Bool(v), Ty::Enum(_)) => self.leaf(StrictVal::bool(*v), sem_id)?,
+            (Node::Number(no), Ty::Enum(_)) => {
+                // TOML has only signed integers
+                let no = match *no {
+                    StrictNum::Int(tag) if tag >= 0 => StrictNum::Uint(tag as u128),
+                    no => no,
+                };
+                self.leaf(StrictVal::Number(no), sem_id)?
+            }
+            (Node::Str(s), Ty::Enum(_) | Ty::UnicodeChar) => {
+                self.leaf(StrictVal::String(s.to_string()), sem_id)?
+            }
+
+            (Node::Null, Ty::Union(_)) if ty.is_option() => StrictVal::none(),
+            (node, Ty::Union(_)) if ty.is_option() => {
+                let some = *ty.as_some().expect("checked for option");
+                StrictVal::some(self.load(node, some)?)
+            }
+            (Node::Str(s), Ty::Union(variants)) => {
+                let name = self.variant_name(s)?;
+                match variants.ty_by_name(&name) {
+                    None => return Err(self.union_tag_invalid(name, variants)),
+                    Some(id) if self.sys.find(*id) == Some(&Ty::UNIT) => StrictVal::union(name, ()),
+                    Some(_) => return Err(self.mismatch(node, ty)),
+                }
+            }
+            (Node::Map(map), Ty::Union(variants)) if map.len() == 1 => {
+                let (key, val) = &map[0];
+                let Node::Str(s) = key else {
+                    return Err(self.mismatch(node, ty));
+                };
+                let name = self.variant_name(s)?;
+                let Some(id) = variants.ty_by_name(&name) else {
+                    return Err(self.union_tag_invalid(name, variants));
+                };
+                StrictVal::union(name, self.nested(s, val, *id)?)
+            }
+
+            (node, Ty::Tuple(fields)) if fields.len() == 1 => {
+                StrictVal::Tuple(vec![self.load(node, fields[0])?])
+            }
+            (Node::Str(s), Ty::Tuple(fields))
+                if self.sys.is_rstring(fields).unwrap_or_default() =>
+            {
+                self.leaf(StrictVal::String(s.to_string()), sem_id)?
+            }
+            (Node::Seq(items), Ty::Tuple(fields)) => {
+                if items.len() != fields.len() {
+                    return Err(self.locate(Error::FieldNumberMismatch {
+                        spec: sem_id.into(),
+                        expected: fields.len(),
+                        found: items.len(),
+                    }));
+                }
+                let mut vals = Vec::with_capacity(items.len());
+                for (pos, (item, id)) in items.iter().zip(fields).enumerate() {
+                    vals.push(self.nested(pos, item, *id)?);
+                }
+                StrictVal::Tuple(vals)
+            }
+            (Node::Map(map), Ty::Struct(fields)) => {
+                let mut entries = IndexMap::with_capacity(map.len());
+                for (key, val) in map {
+                    let Node::Str(s) = key else {
+                        return Err(self.locate(Error::MapNotStructure));
+                    };
+                    let name = FieldName::try_from(s.to_string())
+                        .map_err(|err| self.locate(err.into()))?;
+                    if fields.ty_by_name(&name).is_none() {
+                        return Err(self.locate(Error::ExtraField(name)));
+                    }
+                    entries.insert(name, val);
+                }
+                let mut vals = IndexMap::with_capacity(fields.len());
+                for field in fields {
+                    let val = match entries.get(&field.name) {
+                        Some(val) => self.nested(&field.name, val, field.ty)?,
+                        // Formats without null values (like TOML) omit empty optional fields
+                        None if self.sys.find(field.ty).map(Ty::is_option).unwrap_or_default() => {
+                            StrictVal::none()
+                        }
+                        None => return Err(self.locate(Error::MissingField(field.name.clone()))),
+                    };
+                    vals.insert(field.name.clone(), val);
+                }
+                StrictVal::Struct(vals)
+            }
+
+            (Node::Str(s), Ty::Array(id, len)) if id.is_byte() => {
+                let Some(data) =
+                    decode_bytes(s, self.bytes).filter(|data| data.len() == *len as usize)
+                else {
+                    return Err(self.locate(Error::InvalidBytes(s.to_string())));
+                };
+                StrictVal::Bytes(data)
+            }
+            (Node::Str(s), Ty::List(id, sizing)) if id.is_byte() => {
+                let Some(data) = decode_bytes(s, self.bytes) else {
+                    return Err(self.locate(Error::InvalidBytes(s.to_string())));
+                };
+                self.check_sizing(data.len(), *sizing, sem_id)?;
+                StrictVal::Bytes(data)
+            }
+            (Node::Str(s), Ty::Array(id, _) | Ty::List(id, _)) if self.is_char(*id) => {
+                self.leaf(StrictVal::String(s.to_string()), sem_id)?
+            }
+            (Node::Seq(items), Ty::Array(id, len)) => {
+                self.check_sizing(items.len(), Sizing::fixed(*len as u64), sem_id)?;
+                StrictVal::List(self.items(items, *id)?)
+            }
+            (Node::Seq(items), Ty::List(id, sizing)) => {
+                self.check_sizing(items.len(), *sizing, sem_id)?;
+                StrictVal::List(self.items(items, *id)?)
+            }
+            (Node::Seq(items), Ty::Set(id, sizing)) => {
+                self.check_sizing(items.len(), *sizing, sem_id)?;
+                let vals = self.items(items, *id)?;
+                for (pos, val) in vals.iter().enumerate() {
+                    if vals[..pos].contains(val) {
+                        self.pointer.push(pos.to_string());
+                        let err = Error::RepeatedSetValue(sem_id.into(), val.clone());
+                        return Err(self.locate(err));
+                    }
+                }
+                StrictVal::Set(vals)
+            }
+            (Node::Map(map), Ty::Map(key_id, val_id, sizing)) => {
+                self.check_sizing(map.len(), *sizing, sem_id)?;
+                let mut vals = Vec::<(StrictVal, StrictVal)>::with_capacity(map.len());
+                for (key, val) in map {
+                    let token = match key {
+                        Node::Str(s) => s.to_string(),
+                        key => key.to_untyped().to_string(),
+                    };
+                    let key = self.nested(&token, key, *key_id)?;
+                    let val = self.nested(&token, val, *val_id)?;
+                    self.push_entry(&mut vals, key, val, sem_id, token)?;
+                }
+                StrictVal::Map(vals)
+            }
+            (Node::Seq(pairs), Ty::Map(key_id, val_id, sizing)) => {
+                self.check_sizing(pairs.len(), *sizing, sem_id)?;
+                let mut vals = Vec::<(StrictVal, StrictVal)>::with_capacity(pairs.len());
+                for (pos, pair) in pairs.iter().enumerate() {
+                    self.pointer.push(pos.to_string());
+                    let Node::Seq(pair) = pair else {
+                        return Err(self.mismatch(pair, ty));
+                    };
+                    let [key, val] = pair.as_slice() else {
+                        return Err(self.mismatch(&Node::Seq(vec![]), ty));
+                    };
+                    let key = self.nested(0, key, *key_id)?;
+                    let val = self.nested(1, val, *val_id)?;
+                    self.pointer.pop();
+                    self.push_entry(&mut vals, key, val, sem_id, pos)?;
+                }
+                StrictVal::Map(vals)
+            }
+
+            (node, ty) => return Err(self.mismatch(node, ty)),
+        })
+    }
+
+    fn is_char(&self, sem_id: SemId) -> bool {
+        sem_id.is_unicode_char() || self.sys.find(sem_id).map(Ty::is_char_enum).unwrap_or_default()
+    }
+
+    fn items(&mut self, items: &[Node], sem_id: SemId) -> Result<Vec<StrictVal>, Error> {
+        let mut vals = Vec::with_capacity(items.len());
+        for (pos, item) in items.iter().enumerate() {
+            vals.push(self.nested(pos, item, sem_id)?);
+        }
+        Ok(vals)
+    }
+
+    fn push_entry(
+        &mut self,
+        entries: &mut Vec<(StrictVal, StrictVal)>,
+        key: StrictVal,
+        val: StrictVal,
+        sem_id: SemId,
+        token: impl ToString,
+    ) -> Result<(), Error> {
+        if entries.iter().any(|(k, _)| k == &key) {
+            self.pointer.push(token.to_string());
+            return Err(self.locate(Error::RepeatedKeyValue(sem_id.into(), key)));
+        }
+        entries.push((key, val));
+        Ok(())
+    }
+
+    fn variant_name(&self, s: &str) -> Result<VariantName, Error> {
+        VariantName::try_from(s.to_owned()).map_err(|err| self.locate(err.into()))
+    }
+
+    fn mismatch(&self, node: &Node, ty: &Ty<SemId>) -> Error {
+        self.locate(Error::TypeMismatch {
+            value: node.to_untyped(),
+            expected: ty.clone(),
+        })
+    }
+
+    fn union_tag_invalid(&self, tag: VariantName, variants: &UnionVariants<SemId>) -> Error {
+        let names = variants.keys().cloned().collect::<BTreeSet<_>>();
+        let names = EnumVariants::try_from(names).expect("same collection size");
+        self.locate(Error::UnionTagInvalid(EnumTag::Name(tag), names))
+    }
+}
+
+impl TypeSystem {
+    /// Checks whether values of the type are represented by strings in JSON, YAML and TOML.
+    pub(crate) fn is_string_like(&self, sem_id: SemId) -> bool {
+        if sem_id.is_unicode_char() {
+            return true;
+        }
+        match self.find(sem_id) {
+            Some(Ty::Enum(_) | Ty::UnicodeChar) => true,
+            Some(Ty::Tuple(fields)) if fields.len() == 1 => self.is_string_like(fields[0]),
+            Some(Ty::Tuple(fields)) => self.is_rstring(fields).unwrap_or_default(),
+            Some(Ty::Array(id, _) | Ty::List(id, _)) => {
+                id.is_byte()
+                    || id.is_unicode_char()
+                    || self.find(*id).map(Ty::is_char_enum).unwrap_or_default()
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Display)]
+enum Format {
+    #[display("JSON")]
+    Json,
+    #[display("YAML")]
+    Yaml,
+    #[display("TOML")]
+    Toml,
+}
+
+impl Format {
+    /// Maximal unsigned integer which can be represented by the format without the loss of
+    /// precision.
+    fn max_uint(self) -> u128 {
+        match self {
+            Format::Json | Format::Yaml => u64::MAX as u128,
+            Format::Toml => i64::MAX as u128,
+        }
+    }
+}
+
+impl Node<'_> {
+    fn into_json(self) -> serde_json::Value {
+        use serde_json::Value;
+
+        match self {
+            Node::Null => Value::Null,
+            Node::Bool(v) => Value::Bool(v),
+            Node::Number(StrictNum::Uint(no)) => Value::from(no as u64),
+            Node::Number(StrictNum::Int(no)) => Value::from(no as i64),
+            Node::Number(no) => Value::from(to_f64(no)),
+            Node::Str(s) => Value::String(s.into_owned()),
+            Node::Seq(vec) => Value::Array(vec.into_iter().map(Node::into_json).collect()),
+            Node::Map(map) => {
+                Value::Object(map.into_iter().map(|(k, v)| (k.into_key(), v.into_json())).collect())
+            }
+        }
+    }
+
+    fn into_yaml(self) -> serde_yaml::Value {
+        use serde_yaml::Value;
+
+        match self {
+            Node::Null => Value::Null,
+            Node::Bool(v) => Value::Bool(v),
+            Node::Number(StrictNum::Uint(no)) => Value::from(no as u64),
+            Node::Number(StrictNum::Int(no)) => Value::from(no as i64),
+            Node::Number(no) => Value::from(to_f64(no)),
+            Node::Str(s) => Value::String(s.into_owned()),
+            Node::Seq(vec) => Value::Sequence(vec.into_iter().map(Node::into_yaml).collect()),
+            Node::Map(map) => Value::Mapping(
+                map.into_iter().map(|(k, v)| (k.into_yaml(), v.into_yaml())).collect(),
+            ),
+        }
+    }
+
+    fn into_toml(self) -> toml::Value {
+        use toml::Value;
+
+        match self {
+            Node::Null => unreachable!("TOML values can't be null"),
+            Node::Bool(v) => Value::Boolean(v),
+            Node::Number(StrictNum::Uint(no)) => Value::Integer(no as i64),
+            Node::Number(StrictNum::Int(no)) => Value::Integer(no as i64),
+            Node::Number(no) => Value::Float(to_f64(no)),
+            Node::Str(s) => Value::String(s.into_owned()),
+            Node::Seq(vec) => Value::Array(vec.into_iter().map(Node::into_toml).collect()),
+            Node::Map(map) => {
+                Value::Table(map.into_iter().map(|(k, v)| (k.into_key(), v.into_toml())).collect())
+            }
+        }
+    }
+
+    fn into_key(self) -> String {
+        match self {
+            Node::Str(s) => s.into_owned(),
+            key => key.to_untyped().to_string(),
         }
     }
 }
+
+fn to_f64(no: StrictNum) -> f64 {
+    let StrictNum::Float(val) = no else {
+        unreachable!("integers are converted before");
+    };
+    let bytes = float_to_le_bytes(val, Primitive::F64).value;
+    f64::from_le_bytes(bytes.try_into().expect("f64 has 8 bytes"))
+}
+
+impl TypedVal {
+    /// Converts the value into JSON, using the provided type system.
+    ///
+    /// Byte strings are hex-encoded, unions become objects with a single property named after
+    /// the variant, and integers wider than 64 bits become strings.
+    pub fn to_json(&self, sys: &TypeSystem) -> Result<serde_json::Value, Error> {
+        self.to_json_with(sys, BytesEncoding::default())
+    }
+
+    /// Converts the value into JSON, using the provided type system and encoding byte strings
+    /// with the provided encoding.
+    pub fn to_json_with(
+        &self,
+        sys: &TypeSystem,
+        bytes: BytesEncoding,
+    ) -> Result<serde_json::Value, Error> {
+        self.export(sys, Format::Json, bytes).map(Node::into_json)
+    }
+
+    /// Converts the value into YAML, using the provided type system.
+    ///
+    /// The representation follows the one used by [`Self::to_json`].
+    pub fn to_yaml(&self, sys: &TypeSystem) -> Result<serde_yaml::Value, Error> {
+        self.to_yaml_with(sys, BytesEncoding::default())
+    }
+
+    /// Converts the value into YAML, using the provided type system and encoding byte strings
+    /// with the provided encoding.
+    pub fn to_yaml_with(
+        &self,
+        sys: &TypeSystem,
+        bytes: BytesEncoding,
+    ) -> Result<serde_yaml::Value, Error> {
+        self.export(sys, Format::Yaml, bytes).map(Node::into_yaml)
+    }
+
+    /// Converts the value into TOML, using the provided type system.
+    ///
+    /// The representation follows the one used by [`Self::to_json`], except that empty optional
+    /// fields are omitted and unsigned integers exceeding `i64::MAX` become strings. Unit values
+    /// can't be represented in TOML and result in an error.
+    pub fn to_toml(&self, sys: &TypeSystem) -> Result<toml::Value, Error> {
+        self.to_toml_with(sys, BytesEncoding::default())
+    }
+
+    /// Converts the value into TOML, using the provided type system and encoding byte strings
+    /// with the provided encoding.
+    pub fn to_toml_with(
+        &self,
+        sys: &TypeSystem,
+        bytes: BytesEncoding,
+    ) -> Result<toml::Value, Error> {
+        self.export(sys, Format::Toml, bytes).map(Node::into_toml)
+    }
+
+    fn export(
+        &self,
+        sys: &TypeSystem,
+        format: Format,
+        bytes: BytesEncoding,
+    ) -> Result<Node<'static>, Error> {
+        let mut exporter = Exporter {
+            sys,
+            format,
+            bytes,
+            pointer: vec![],
+        };
+        exporter.export(&self.val, self.orig.id)
+    }
+}
+
+struct Exporter<'sys> {
+    sys: &'sys TypeSystem,
+    format: Format,
+    bytes: BytesEncoding,
+    pointer: Vec<String>,
+}
+
+impl Exporter<'_> {
+    fn locate(&self, err: Error) -> Error { locate(&self.pointer, err) }
+
+    fn nested(
+        &mut self,
+        token: impl ToString,
+        val: &StrictVal,
+        sem_id: SemId,
+    ) -> Result<Node<'static>, Error> {
+        self.pointer.push(token.to_string());
+        let node = self.export(val, sem_id)?;
+        self.pointer.pop();
+        Ok(node)
+    }
+
+    fn mismatch(&self, val: &StrictVal, ty: &Ty<SemId>) -> Error {
+        self.locate(Error::TypeMismatch {
+            value: val.clone(),
+            expected: ty.clone(),
+        })
+    }
+
+    fn export(&mut self, val: &StrictVal, sem_id: SemId) -> Result<Node<'static>, Error> {
+        let Some(ty) = self.sys.find(sem_id) else {
+            return Err(self.locate(Error::TypeAbsent(sem_id.into())));
+        };
+        Ok(match (val, ty) {
+            (StrictVal::Unit, Ty::Primitive(prim)) if *prim == Primitive::UNIT => {
+                if self.format == Format::Toml {
+                    return Err(self.locate(Error::Unrepresentable(s!("TOML"), val.clone())));
+                }
+                Node::Null
+            }
+            (StrictVal::Number(no), Ty::Primitive(prim)) => self.number(*no, *prim),
+
+            (StrictVal::Enum(tag), Ty::Enum(variants)) => {
+                let Some(var) = variants.iter().find(|var| match tag {
+                    EnumTag::Name(name) => &var.name == name,
+                    EnumTag::Ord(ord) => var.tag == *ord,
+                }) else {
+                    return Err(self.locate(Error::EnumTagInvalid(tag.clone(), variants.clone())));
+                };
+                if ty.is_char_enum() {
+                    Node::Str(Cow::Owned((var.tag as char).to_string()))
+                } else if is_bool(variants) {
+                    Node::Bool(var.tag == 1)
+                } else {
+                    Node::Str(Cow::Owned(var.name.to_string()))
+                }
+            }
+            (StrictVal::String(s), Ty::Enum(_) | Ty::UnicodeChar) => {
+                Node::Str(Cow::Owned(s.clone()))
+            }
+
+            (StrictVal::Union(tag, inner), Ty::Union(variants)) => {
+                let Some((var, id)) = variants.iter().find(|(var, _)| match tag {
+                    EnumTag::Name(name) => &var.name == name,
+                    EnumTag::Ord(ord) => var.tag == *ord,
+                }) else {
+                    return Err(self.union_tag_invalid(tag, variants));
+                };
+                if ty.is_option() {
+                    match var.tag {
+                        0 if self.format == Format::Toml => {
+                            return Err(self.locate(Error::Unrepresentable(s!("TOML"), val.clone())))
+                        }
+                        0 => Node::Null,
+                        _ => self.export(inner, *id)?,
+                    }
+                } else if self.sys.find(*id) == Some(&Ty::UNIT) {
+                    Node::Str(Cow::Owned(var.name.to_string()))
+                } else {
+                    let name = var.name.to_string();
+                    let node = self.nested(&name, inner, *id)?;
+                    Node::Map(vec![(Node::Str(Cow::Owned(name)), node)])
+                }
+            }
+
+            (StrictVal::Tuple(fields), Ty::Tuple(req)) if req.len() == 1 && fields.len() == 1 => {
+                self.export(&fields[0], req[0])?
+            }
+            (val, Ty::Tuple(req)) if req.len() == 1 => self.export(val, req[0])?,
+            (StrictVal::String(s), Ty::Tuple(_)) => Node::Str(Cow::Owned(s.clone())),
+            (StrictVal::Tuple(fields), Ty::Tuple(req)) => {
+                if fields.len() != req.len() {
+                    return Err(self.locate(Error::FieldNumberMismatch {
+                        spec: sem_id.into(),
+                        expected: req.len(),
+                        found: fields.len(),
+                    }));
+                }
+                let mut nodes = Vec::with_capacity(fields.len());
+                for (pos, (field, id)) in fields.iter().zip(req).enumerate() {
+                    nodes.push(self.nested(pos, field, *id)?);
+                }
+                Node::Seq(nodes)
+            }
+            (StrictVal::Struct(fields), Ty::Struct(req)) => {
+                if let Some(name) = fields.keys().find(|name| req.ty_by_name(name).is_none()) {
+                    return Err(self.locate(Error::ExtraField(name.clone())));
+                }
+                let mut entries = Vec::with_capacity(req.len());
+                for field in req {
+                    let Some(val) = fields.get(&field.name) else {
+                        return Err(self.locate(Error::MissingField(field.name.clone())));
+                    };
+                    if self.format == Format::Toml && self.is_none(val, field.ty) {
+                        continue;
+                    }
+                    let node = self.nested(&field.name, val, field.ty)?;
+                    entries.push((Node::Str(Cow::Owned(field.name.to_string())), node));
+                }
+                Node::Map(entries)
+            }
+
+            (StrictVal::Bytes(data), Ty::Array(id, _) | Ty::List(id, _)) if id.is_byte() => {
+                Node::Str(Cow::Owned(encode_bytes(data, self.bytes)))
+            }
+            (StrictVal::List(items), Ty::Array(id, _) | Ty::List(id, _)) if id.is_byte() => {
+                let data = items
+                    .iter()
+                    .map(|item| match item {
+                        StrictVal::Number(StrictNum::Uint(byte)) if *byte <= 0xFF => {
+                            Some(*byte as u8)
+                        }
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>();
+                match data {
+                    Some(data) => Node::Str(Cow::Owned(encode_bytes(&data, self.bytes))),
+                    None => Node::Seq(self.items(items, *id)?),
+                }
+            }
+            (StrictVal::String(s), Ty::Array(..) | Ty::List(..)) => {
+                Node::Str(Cow::Owned(s.clone()))
+            }
+            (StrictVal::List(items), Ty::Array(id, _) | Ty::List(id, _))
+            | (StrictVal::Set(items) | StrictVal::List(items), Ty::Set(id, _)) => {
+                Node::Seq(self.items(items, *id)?)
+            }
+            (StrictVal::Set(items), Ty::Map(..)) if items.is_empty() => Node::Map(vec![]),
+            (StrictVal::Map(entries), Ty::Map(key_id, val_id, _)) => {
+                let string_keys = self.sys.is_string_like(*key_id);
+                let mut nodes = Vec::with_capacity(entries.len());
+                for (pos, (key, val)) in entries.iter().enumerate() {
+                    if string_keys {
+                        let key = self.nested(pos, key, *key_id)?;
+                        let key = Node::Str(Cow::Owned(key.into_key()));
+                        let val = self.nested(key.to_untyped().to_string(), val, *val_id)?;
+                        nodes.push((key, val));
+                    } else {
+                        self.pointer.push(pos.to_string());
+                        let key = self.nested(0, key, *key_id)?;
+                        let val = self.nested(1, val, *val_id)?;
+                        self.pointer.pop();
+                        nodes.push((Node::Seq(vec![key, val]), Node::Null));
+                    }
+                }
+                if string_keys {
+                    Node::Map(nodes)
+                } else {
+                    Node::Seq(nodes.into_iter().map(|(pair, _)| pair).collect())
+                }
+            }
+
+            (val, ty) => return Err(self.mismatch(val, ty)),
+        })
+    }
+
+    fn is_none(&self, val: &StrictVal, sem_id: SemId) -> bool {
+        let Some(ty @ Ty::Union(variants)) = self.sys.find(sem_id) else {
+            return false;
+        };
+        if !ty.is_option() {
+            return false;
+        }
+        match val {
+            StrictVal::Union(EnumTag::Ord(tag), _) => *tag == 0,
+            StrictVal::Union(EnumTag::Name(name), _) => variants.name_by_tag(0) == Some(name),
+            _ => false,
+        }
+    }
+
+    fn items(&mut self, items: &[StrictVal], sem_id: SemId) -> Result<Vec<Node<'static>>, Error> {
+        let mut nodes = Vec::with_capacity(items.len());
+        for (pos, item) in items.iter().enumerate() {
+            nodes.push(self.nested(pos, item, sem_id)?);
+        }
+        Ok(nodes)
+    }
+
+    fn number(&self, no: StrictNum, prim: Primitive) -> Node<'static> {
+        let native = match no {
+            _ if prim.byte_size() > 8 => false,
+            StrictNum::Uint(val) => val <= self.format.max_uint(),
+            StrictNum::Int(val) => val >= i64::MIN as i128 && val <= i64::MAX as i128,
+            StrictNum::BigUint(_) | StrictNum::BigInt(_) => false,
+            StrictNum::Float(val) => val.is_finite(),
+        };
+        if native {
+            Node::Number(no)
+        } else {
+            Node::Str(Cow::Owned(no.to_string()))
+        }
+    }
+
+    fn union_tag_invalid(&self, tag: &EnumTag, variants: &UnionVariants<SemId>) -> Error {
+        let names = variants.keys().cloned().collect::<BTreeSet<_>>();
+        let names = EnumVariants::try_from(names).expect("same collection size");
+        self.locate(Error::UnionTagInvalid(tag.clone(), names))
+    }
+}
+
+fn is_bool(variants: &EnumVariants) -> bool {
+    variants.len() == 2
+        && variants.name_by_tag(0).map(VariantName::as_str) == Some("false")
+        && variants.name_by_tag(1).map(VariantName::as_str) == Some("true")
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use amplify::num::apfloat::ieee;
+    use amplify::num::{i1024, i256, u24, u256, u512};
+    use encoding::StrictSerialize;
+    use serde_json::json;
+
+    use super::super::test_helpers::*;
+    use super::*;
+    use crate::{SymbolicLib, SystemBuilder};
+
+    fn data_sys() -> SymbolicSys {
+        let src = "typelib Test
+data Data : id [Byte ^ 4], data [Byte], opt U8?, var Var, set {U16 ^ ..0xff}, map {U8 -> I8}
+data Var : empty () | single (U8) | pair (a U8, b U16)
+";
+        let lib = SymbolicLib::from_source(src, None).unwrap().compile().unwrap();
+        SystemBuilder::new().import(lib).unwrap().finalize().unwrap()
+    }
+
+    #[test]
+    fn struct_enum() {
+        let sys = test_system();
+        let json = json!({ "ticker": "TICK", "name": "Some name", "precision": "twoDecimals" });
+        let val = sys.from_json("TestLib.Nominal", &json).unwrap();
+        let ston = r#"(ticker=("TICK"), name="Some name", precision=twoDecimals)"#;
+        assert_eq!(val.val, sys.typify_ston(ston, "TestLib.Nominal").unwrap().val);
+
+        let yaml = serde_yaml::from_str("ticker: TICK\nname: Some name\nprecision: 2").unwrap();
+        assert_eq!(sys.from_yaml("TestLib.Nominal", &yaml).unwrap().val, val.val);
+
+        let toml = toml::from_str("ticker = 'TICK'\nname = 'Some name'\nprecision = 2").unwrap();
+        assert_eq!(sys.from_toml("TestLib.Nominal", &toml).unwrap().val, val.val);
+    }
+
+    #[test]
+    fn toml_datetime() {
+        let toml = toml::from_str::<toml::Value>("at = 1979-05-27T07:32:00Z").unwrap();
+        assert_eq!(
+            StrictVal::from(toml["at"].clone()),
+            StrictVal::String(s!("1979-05-27T07:32:00Z"))
+        );
+    }
+
+    #[test]
+    fn large_numbers() {
+        let sys = test_system();
+        let json = json!({
+            "small": 0xFFFFFF,
+            "medium": "340282366920938463463374607431768211455",
+            "large": "1",
+            "signed": -1,
+            "huge": 0,
+            "negative": "-1000000000000000000000000000000000000000"
+        });
+        let val = sys.from_json("TestLib.Amounts", &json).unwrap();
+        assert_eq!(val.val.unwrap_struct("medium"), &svnum!(u128::MAX));
+
+        let json = json!({
+            "small": 0x1000000, "medium": 0, "large": 0, "signed": 0, "huge": 0, "negative": 0
+        });
+        let err = sys.from_json("TestLib.Amounts", &json).unwrap_err();
+        assert!(matches!(&err, Error::Located(ptr, err)
+            if ptr == "/small" && matches!(**err, Error::IntOverflow(..))));
+    }
+
+    #[test]
+    fn typed_collections() {
+        let sys = data_sys();
+        let json = json!({
+            "id": "00010203",
+            "data": "00010203",
+            "opt": null,
+            "var": { "pair": { "a": 1, "b": 2 } },
+            "set": [1, 2, 3],
+            "map": { "1": -1, "2": -2 }
+        });
+        let val = sys.from_json("Test.Data", &json).unwrap().val;
+        assert_eq!(val.unwrap_struct("id"), &svbytes!([0u8, 1, 2, 3]));
+        assert_eq!(val.unwrap_struct("data"), &svbytes!([0u8, 1, 2, 3]));
+        assert_eq!(val.unwrap_struct("opt"), &StrictVal::none());
+        assert_eq!(val.unwrap_struct("var").unwrap_union().0, &EnumTag::Name(vname!("pair")));
+        assert_eq!(val.unwrap_struct("set"), &StrictVal::set([1u16, 2, 3]));
+        assert_eq!(val.unwrap_struct("map").unwrap_key(2u8), &svnum!(-2i8));
+
+        let json = json!({
+            "id": "AAECAw==",
+            "data": "",
+            "opt": 5,
+            "var": "empty",
+            "set": [],
+            "map": [[1, -1]]
+        });
+        let val = sys.from_json_with("Test.Data", &json, BytesEncoding::Base64).unwrap().val;
+        assert_eq!(val.unwrap_struct("id"), &svbytes!([0u8, 1, 2, 3]));
+        assert_eq!(val.unwrap_struct("opt"), &StrictVal::some(svnewtype!(5u8)));
+        assert_eq!(val.unwrap_struct("var"), &StrictVal::union(vname!("empty"), ()));
+        assert_eq!(val.unwrap_struct("map").unwrap_key(1u8), &svnum!(-1i8));
+    }
+
+    #[test]
+    fn bytes_encoding() {
+        let sys = data_sys();
+        let mut json = json!({
+            "id": "00010203", "data": "AAAA", "opt": null, "var": "empty", "set": [], "map": {}
+        });
+        // The string is valid in both encodings, so the encoding is never guessed
+        let val = sys.from_json("Test.Data", &json).unwrap().val;
+        assert_eq!(val.unwrap_struct("data"), &svbytes!([0xAAu8, 0xAA]));
+        assert!(sys.from_json_with("Test.Data", &json, BytesEncoding::Base64).is_err());
+        json["id"] = json!("AAECAw==");
+        let val = sys.from_json_with("Test.Data", &json, BytesEncoding::Base64).unwrap().val;
+        assert_eq!(val.unwrap_struct("data"), &svbytes!([0u8, 0, 0]));
+        let err = sys.from_json("Test.Data", &json).unwrap_err();
+        assert!(matches!(&err, Error::Located(ptr, err)
+            if ptr == "/id" && matches!(**err, Error::InvalidBytes(_))));
+    }
+
+    #[test]
+    fn error_pointer() {
+        let sys = data_sys();
+        let check = |json: serde_json::Value, pointer: &str| {
+            let err = sys.from_json("Test.Data", &json).unwrap_err();
+            let Error::Located(ptr, _) = &err else {
+                panic!("error without location: {err}");
+            };
+            assert_eq!(ptr, pointer, "{err}");
+        };
+        let valid = json!({
+            "id": "00010203", "data": "", "opt": null, "var": "empty", "set": [], "map": {}
+        });
+        sys.from_json("Test.Data", &valid).unwrap();
+
+        let mut json = valid.clone();
+        json["id"] = json!("000102");
+        check(json, "/id");
+        let mut json = valid.clone();
+        json["var"] = json!({ "pair": { "a": 1, "b": 0x10000 } });
+        check(json, "/var/pair/b");
+        let mut json = valid.clone();
+        json["var"] = json!("single");
+        check(json, "/var");
+        let mut json = valid.clone();
+        json["set"] = json!([1, 2, 1]);
+        check(json, "/set/2");
+        let mut json = valid.clone();
+        json["map"] = json!({ "1": 0, "0x100": 0 });
+        check(json, "/map/0x100");
+        let mut json = valid.clone();
+        json["map"] = json!([[1, 0], [2, 128]]);
+        check(json, "/map/1/1");
+        let mut json = valid.clone();
+        json.as_object_mut().unwrap().remove("var");
+        check(json, "");
+
+        let err = sys.from_json("Test.Data", &json!([])).unwrap_err();
+        assert!(err.to_string().starts_with("invalid value at ``: value `"));
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let sys = data_sys();
+        let json = json!({
+            "id": "00010203",
+            "data": "0a0b",
+            "opt": 5,
+            "var": { "pair": { "a": 1, "b": 2 } },
+            "set": [1, 2, 3],
+            "map": [[1, -1], [2, -2]]
+        });
+        let val = sys.from_json("Test.Data", &json).unwrap();
+        assert_eq!(val.to_json(sys.as_types()).unwrap(), json);
+
+        let base64 = val.to_json_with(sys.as_types(), BytesEncoding::Base64).unwrap();
+        assert_eq!(base64["id"], json!("AAECAw=="));
+        assert_eq!(base64["data"], json!("Cgs="));
+        assert_eq!(sys.from_json_with("Test.Data", &base64, BytesEncoding::Base64).unwrap(), val);
+
+        let json = json!({
+            "id": "00010203",
+            "data": "",
+            "opt": null,
+            "var": "empty",
+            "set": [],
+            "map": []
+        });
+        let val = sys.from_json("Test.Data", &json).unwrap();
+        assert_eq!(val.to_json(sys.as_types()).unwrap(), json);
+
+        let toml = val.to_toml(sys.as_types()).unwrap();
+        assert_eq!(toml.get("opt"), None);
+        assert_eq!(sys.from_toml("Test.Data", &toml).unwrap(), val);
+    }
+
+    #[test]
+    fn decoded_roundtrip() {
+        let sys = test_system();
+        let amounts = Amounts {
+            small: u24::with(0xFFFFFF),
+            medium: u128::MAX,
+            large: u256::from(1u64) << 200,
+            signed: -i256::from(1000u64),
+            huge: u512::ONE,
+            negative: i1024::from(-1i64),
+        };
+        let reading = Reading {
+            brain: BFloat(half::bf16::from_f32(-0.375)),
+            half: ieee::Half::from_str("-2.5").unwrap(),
+            single: ieee::Single::from_str("0.1").unwrap(),
+            double: ieee::Double::from_str("1e300").unwrap(),
+            extended: ieee::X87DoubleExtended::from_str("1.5").unwrap(),
+            quad: ieee::Quad::NAN,
+            oct: ieee::Oct::from_str("42").unwrap(),
+        };
+        let nominal = Nominal::with("TICK", "Some name", 2);
+
+        fn check<T: StrictSerialize>(
+            sys: &SymbolicSys,
+            name: &'static str,
+            val: T,
+        ) -> serde_json::Value {
+            let data = val.to_strict_serialized::<{ usize::MAX }>().unwrap();
+            let decoded = sys.strict_deserialize_type(name, &data).unwrap();
+
+            let json = decoded.to_json(sys.as_types()).unwrap();
+            let loaded = sys.from_json(name, &json).unwrap();
+            let mut serialized = vec![];
+            sys.as_types().strict_write_type(&loaded, &mut serialized).unwrap();
+            assert_eq!(serialized, data.as_slice());
+
+            let yaml = decoded.to_yaml(sys.as_types()).unwrap();
+            assert_eq!(sys.from_yaml(name, &yaml).unwrap(), loaded);
+            let toml = decoded.to_toml(sys.as_types()).unwrap();
+            assert_eq!(sys.from_toml(name, &toml).unwrap(), loaded);
+            json
+        }
+
+        let json = check(&sys, "TestLib.Amounts", amounts);
+        assert_eq!(json["small"], json!(0xFFFFFF));
+        assert_eq!(json["medium"], json!(u128::MAX.to_string()));
+        assert_eq!(json["negative"], json!("-1"));
+        let json = check(&sys, "TestLib.Reading", reading);
+        assert_eq!(json["brain"], json!(-0.375));
+        assert_eq!(json["half"], json!(-2.5));
+        assert_eq!(json["double"], json!(1e300));
+        assert_eq!(json["quad"], json!("NaN"));
+        assert_eq!(json["oct"], json!("42.0"));
+        let json = check(&sys, "TestLib.Nominal", nominal);
+        assert_eq!(
+            json,
+            json!({ "ticker": "TICK", "name": "Some name", "precision": "twoDecimals" })
+        );
+    }
+
+    #[test]
+    fn export_errors() {
+        let sys = data_sys();
+        let json = json!({
+            "id": "00010203", "data": "", "opt": null, "var": "empty", "set": [], "map": []
+        });
+        let val = sys.from_json("Test.Data", &json).unwrap();
+        let with_field = |name: &'static str, field: StrictVal| {
+            let mut val = val.clone();
+            let StrictVal::Struct(fields) = &mut val.val else {
+                unreachable!()
+            };
+            fields.insert(fname!(name), field);
+            val
+        };
+
+        let invalid = with_field("var", StrictVal::union(vname!("unknown"), ()));
+        let err = invalid.to_json(sys.as_types()).unwrap_err();
+        assert!(matches!(&err, Error::Located(ptr, _) if ptr == "/var"), "{err}");
+
+        let invalid = with_field("set", StrictVal::set([svstr!("a")]));
+        let err = invalid.to_yaml(sys.as_types()).unwrap_err();
+        assert!(matches!(&err, Error::Located(ptr, _) if ptr == "/set/0"), "{err}");
+    }
+}
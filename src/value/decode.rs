@@ -22,18 +22,21 @@
 
 //! Reification module: reads & writes strict values from binary strict encodings.
 
+use std::str::Utf8Error;
+
 use amplify::ascii::AsciiString;
-use amplify::confinement::{
-    Confined, LargeAscii, LargeBlob, LargeString, MediumAscii, MediumBlob, MediumString,
-    SmallAscii, SmallBlob, SmallString, TinyAscii, TinyBlob, TinyString, U16 as MAX16,
-    U32 as MAX32,
+use amplify::confinement::{U16 as MAX16, U32 as MAX32};
+use amplify::num::apfloat::ieee;
+use amplify::num::{i1024, i256, i512, u1024, u24, u256, u40, u48, u512, u56};
+use encoding::{
+    DecodeError, DecodeRawLe, Primitive, ReadRaw, Sizing, StreamReader, StrictDecode, StrictReader,
 };
-use amplify::num::{u24, u40, u48, u56};
-use encoding::{DecodeError, Primitive, ReadRaw, StreamReader, StrictDecode, StrictReader};
 use indexmap::IndexMap;
 
 use crate::typesys::{SymbolicSys, TypeSymbol, UnknownType};
 use crate::typify::{TypeSpec, TypedVal};
+use crate::value::val::float_from_le_bytes;
+use crate::value::visit::DecodeLimits;
 use crate::{SemId, StrictVal, Ty, TypeRef, TypeSystem};
 
 #[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
@@ -55,6 +58,117 @@ pub enum Error {
 
     /// data provided to reify operation are not entirely consumed during deserialization.
     NotEntirelyConsumed,
+
+    /// invalid UTF-8 data: {0}.
+    #[from]
+    InvalidUtf8(Utf8Error),
+
+    /// value nesting exceeds the depth limit of {0}.
+    DepthLimit(usize),
+
+    /// collection length {len} is outside of the allowed range {min}..={max}.
+    InvalidLen { len: u64, min: u64, max: u64 },
+}
+
+/// Reads collection length prefix, which size depends on the maximal allowed
+/// number of collection items.
+pub(super) fn strict_read_len(sizing: &Sizing, d: &mut impl ReadRaw) -> Result<usize, Error> {
+    let len = if sizing.max <= u8::MAX as u64 {
+        u8::decode_raw_le(d)? as u64
+    } else if sizing.max <= u16::MAX as u64 {
+        u16::decode_raw_le(d)? as u64
+    } else if sizing.max <= u24::MAX.into_u64() {
+        u24::decode_raw_le(d)?.into_u64()
+    } else if sizing.max <= u32::MAX as u64 {
+        u32::decode_raw_le(d)? as u64
+    } else {
+        u64::decode_raw_le(d)?
+    };
+    if len < sizing.min || len > sizing.max {
+        return Err(Error::InvalidLen {
+            len,
+            min: sizing.min,
+            max: sizing.max,
+        });
+    }
+    Ok(len as usize)
+}
+
+/// Reads a primitive value. Kept out of [`TypeSystem::read_type`] to reduce the stack frame of the
+/// recursive decoding.
+fn read_primitive(
+    prim: Primitive,
+    reader: &mut StrictReader<impl ReadRaw>,
+) -> Result<StrictVal, Error> {
+    Ok(match prim {
+        Primitive::UNIT => StrictVal::Unit,
+        Primitive::BYTE => StrictVal::num(u8::strict_decode(reader)?),
+        Primitive::U8 => StrictVal::num(u8::strict_decode(reader)?),
+        Primitive::U16 => StrictVal::num(u16::strict_decode(reader)?),
+        Primitive::U24 => StrictVal::num(u24::strict_decode(reader)?.into_u32()),
+        Primitive::U32 => StrictVal::num(u32::strict_decode(reader)?),
+        Primitive::U40 => StrictVal::num(u40::strict_decode(reader)?),
+        Primitive::U48 => StrictVal::num(u48::strict_decode(reader)?),
+        Primitive::U56 => StrictVal::num(u56::strict_decode(reader)?),
+        Primitive::U64 => StrictVal::num(u64::strict_decode(reader)?),
+        Primitive::U128 => StrictVal::num(u128::strict_decode(reader)?),
+        Primitive::U160 => StrictVal::num(uint_from_le(&<[u8; 20]>::strict_decode(reader)?)),
+        Primitive::U256 => StrictVal::num(u256::strict_decode(reader)?),
+        Primitive::U512 => StrictVal::num(u512::strict_decode(reader)?),
+        Primitive::U1024 => StrictVal::num(u1024::strict_decode(reader)?),
+        Primitive::I8 => StrictVal::num(i8::strict_decode(reader)?),
+        Primitive::I16 => StrictVal::num(i16::strict_decode(reader)?),
+        Primitive::I24 => StrictVal::num(int_from_le(&<[u8; 3]>::strict_decode(reader)?)),
+        Primitive::I32 => StrictVal::num(i32::strict_decode(reader)?),
+        Primitive::I40 => StrictVal::num(int_from_le(&<[u8; 5]>::strict_decode(reader)?)),
+        Primitive::I48 => StrictVal::num(int_from_le(&<[u8; 6]>::strict_decode(reader)?)),
+        Primitive::I56 => StrictVal::num(int_from_le(&<[u8; 7]>::strict_decode(reader)?)),
+        Primitive::I64 => StrictVal::num(i64::strict_decode(reader)?),
+        Primitive::I128 => StrictVal::num(i128::strict_decode(reader)?),
+        Primitive::I256 => StrictVal::num(i256::strict_decode(reader)?),
+        Primitive::I512 => StrictVal::num(i512::strict_decode(reader)?),
+        Primitive::I1024 => StrictVal::num(i1024::strict_decode(reader)?),
+        Primitive::F16B => StrictVal::num(half::bf16::from_bits(u16::strict_decode(reader)?)),
+        Primitive::F16 => StrictVal::num(ieee::Half::strict_decode(reader)?),
+        Primitive::F32 => StrictVal::num(ieee::Single::strict_decode(reader)?),
+        Primitive::F64 => StrictVal::num(ieee::Double::strict_decode(reader)?),
+        Primitive::F80 => StrictVal::Number(float_from_le_bytes(
+            &<[u8; 10]>::strict_decode(reader)?,
+            Primitive::F80,
+        )),
+        Primitive::F128 => StrictVal::num(ieee::Quad::strict_decode(reader)?),
+        Primitive::F256 => StrictVal::num(ieee::Oct::strict_decode(reader)?),
+        other => {
+            return Err(Error::NotImplemented(format!(
+                "loading {other} into a typed value is not yet implemented"
+            )))
+        }
+    })
+}
+
+/// Reads an ASCII string with a length prefix matching the string size limits.
+fn read_ascii(sizing: &Sizing, d: &mut impl ReadRaw) -> Result<StrictVal, Error> {
+    let len = strict_read_len(sizing, d)?;
+    let bytes = d.read_raw::<{ usize::MAX }>(len).map_err(DecodeError::from)?;
+    let string =
+        AsciiString::from_ascii(bytes).map_err(|err| DecodeError::from(err.ascii_error()))?;
+    Ok(StrictVal::String(string.to_string()))
+}
+
+/// Reads a little-endian unsigned integer which has no dedicated Rust type.
+fn uint_from_le(bytes: &[u8]) -> u1024 {
+    let mut buf = [0u8; 128];
+    buf[..bytes.len()].copy_from_slice(bytes);
+    u1024::from_le_bytes(buf)
+}
+
+/// Reads a little-endian signed integer which has no dedicated Rust type,
+/// sign-extending it.
+fn int_from_le(bytes: &[u8]) -> i128 {
+    let negative = bytes.last().copied().unwrap_or_default() & 0x80 != 0;
+    let mut buf = [if negative { 0xFF } else { 0x00 }; 16];
+    buf[..bytes.len()].copy_from_slice(bytes);
+    i128::from_le_bytes(buf)
 }
 
 impl SymbolicSys {
@@ -68,6 +182,17 @@ impl SymbolicSys {
         self.as_types().strict_deserialize_type(sem_id, data)
     }
 
+    pub fn strict_deserialize_type_with(
+        &self,
+        spec: impl Into<TypeSpec>,
+        data: &[u8],
+        limits: DecodeLimits,
+    ) -> Result<TypedVal, Error> {
+        let spec = spec.into();
+        let sem_id = self.to_sem_id(spec.clone()).ok_or(Error::TypeAbsent(spec))?;
+        self.as_types().strict_deserialize_type_with(sem_id, data, limits)
+    }
+
     pub fn strict_read_type(
         &self,
         spec: impl Into<TypeSpec>,
@@ -77,18 +202,38 @@ impl SymbolicSys {
         let sem_id = self.to_sem_id(spec.clone()).ok_or(Error::TypeAbsent(spec))?;
         self.as_types().strict_read_type(sem_id, d)
     }
+
+    pub fn strict_read_type_with(
+        &self,
+        spec: impl Into<TypeSpec>,
+        d: &mut impl ReadRaw,
+        limits: DecodeLimits,
+    ) -> Result<TypedVal, Error> {
+        let spec = spec.into();
+        let sem_id = self.to_sem_id(spec.clone()).ok_or(Error::TypeAbsent(spec))?;
+        self.as_types().strict_read_type_with(sem_id, d, limits)
+    }
 }
 
 impl TypeSystem {
+    /// Detects unicode characters by their type, such that named types wrapping
+    /// a unicode character are also recognized.
+    fn is_unicode_char(&self, sem_id: SemId) -> Result<bool, Error> {
+        let ty = self.find(sem_id).ok_or_else(|| Error::TypeAbsent(sem_id.into()))?;
+        Ok(ty.is_unicode_char())
+    }
+
     fn strict_read_list(
         &self,
         len: usize,
         ty: SemId,
         d: &mut impl ReadRaw,
+        depth: usize,
+        limits: &DecodeLimits,
     ) -> Result<Vec<StrictVal>, Error> {
         let mut list = Vec::with_capacity(len);
         for _ in 0..len {
-            let item = self.strict_read_type(ty, d)?;
+            let item = self.read_type(ty, d, depth, limits)?;
             list.push(item.val);
         }
         Ok(list)
@@ -100,64 +245,87 @@ impl TypeSystem {
         key_ty: SemId,
         ty: SemId,
         d: &mut impl ReadRaw,
+        depth: usize,
+        limits: &DecodeLimits,
     ) -> Result<Vec<(StrictVal, StrictVal)>, Error> {
         let mut list = Vec::with_capacity(len);
         for _ in 0..len {
-            let key = self.strict_read_type(key_ty, d)?;
-            let item = self.strict_read_type(ty, d)?;
+            let key = self.read_type(key_ty, d, depth, limits)?;
+            let item = self.read_type(ty, d, depth, limits)?;
             list.push((key.val, item.val));
         }
         Ok(list)
     }
 
     pub fn strict_deserialize_type(&self, sem_id: SemId, data: &[u8]) -> Result<TypedVal, Error> {
+        self.strict_deserialize_type_with(sem_id, data, DecodeLimits::default())
+    }
+
+    /// Deserializes a value of type `sem_id`, limiting the nesting of the value to
+    /// [`DecodeLimits::max_depth`].
+    pub fn strict_deserialize_type_with(
+        &self,
+        sem_id: SemId,
+        data: &[u8],
+        limits: DecodeLimits,
+    ) -> Result<TypedVal, Error> {
         let mut cursor = StreamReader::cursor::<MAX32>(data);
-        let ty = self.strict_read_type(sem_id, &mut cursor)?;
+        let ty = self.strict_read_type_with(sem_id, &mut cursor, limits)?;
         if cursor.unconfine().position() as usize != data.len() {
             return Err(Error::NotEntirelyConsumed);
         }
         Ok(ty)
     }
 
-    pub fn strict_read_type(
+    /// Reads a value of type `sem_id`. Since the data may be untrusted and the type may be
+    /// recursive, the nesting of the value is limited to [`DecodeLimits::max_depth`] of the
+    /// default limits.
+    pub fn strict_read_type(&self, sem_id: SemId, d: &mut impl ReadRaw) -> Result<TypedVal, Error> {
+        self.strict_read_type_with(sem_id, d, DecodeLimits::default())
+    }
+
+    /// Reads a value of type `sem_id`, limiting the nesting of the value to
+    /// [`DecodeLimits::max_depth`].
+    pub fn strict_read_type_with(
+        &self,
+        sem_id: SemId,
+        d: &mut impl ReadRaw,
+        limits: DecodeLimits,
+    ) -> Result<TypedVal, Error> {
+        self.read_type(sem_id, d, 0, &limits)
+    }
+
+    fn read_type(
         &self,
         sem_id: SemId,
         mut d: &mut impl ReadRaw,
+        depth: usize,
+        limits: &DecodeLimits,
     ) -> Result<TypedVal, Error> {
+        if depth >= limits.max_depth {
+            return Err(Error::DepthLimit(limits.max_depth));
+        }
+        let depth = depth + 1;
         let spec = TypeSpec::from(sem_id);
         let ty = self.find(sem_id).ok_or_else(|| Error::TypeAbsent(spec.clone()))?;
 
         let mut reader = StrictReader::with(d);
 
         let val = match ty {
-            Ty::Primitive(prim) => {
-                match *prim {
-                    Primitive::UNIT => StrictVal::Unit,
-                    Primitive::BYTE => StrictVal::num(u8::strict_decode(&mut reader)?),
-                    Primitive::U8 => StrictVal::num(u8::strict_decode(&mut reader)?),
-                    Primitive::U16 => StrictVal::num(u16::strict_decode(&mut reader)?),
-                    Primitive::U24 => StrictVal::num(u24::strict_decode(&mut reader)?.into_u32()),
-                    Primitive::U32 => StrictVal::num(u32::strict_decode(&mut reader)?),
-                    Primitive::U40 => StrictVal::num(u40::strict_decode(&mut reader)?),
-                    Primitive::U48 => StrictVal::num(u48::strict_decode(&mut reader)?),
-                    Primitive::U56 => StrictVal::num(u56::strict_decode(&mut reader)?),
-                    Primitive::U64 => StrictVal::num(u64::strict_decode(&mut reader)?),
-                    Primitive::U128 => StrictVal::num(u128::strict_decode(&mut reader)?),
-                    Primitive::I8 => StrictVal::num(i8::strict_decode(&mut reader)?),
-                    Primitive::I16 => StrictVal::num(i16::strict_decode(&mut reader)?),
-                    // I24 => StrictVal::num(i24::strict_decode(&mut reader)?),
-                    Primitive::I32 => StrictVal::num(i32::strict_decode(&mut reader)?),
-                    Primitive::I64 => StrictVal::num(i64::strict_decode(&mut reader)?),
-                    Primitive::I128 => StrictVal::num(i128::strict_decode(&mut reader)?),
-                    other => {
-                        return Err(Error::NotImplemented(format!(
-                            "loading {other} into a typed value is not yet implemented"
-                        )))
-                    }
-                }
-            }
+            Ty::Primitive(prim) => read_primitive(*prim, &mut reader)?,
             Ty::UnicodeChar => {
-                todo!()
+                let d = reader.unbox();
+                let first = d.read_raw_array::<1>().map_err(DecodeError::from)?[0];
+                let len = match first {
+                    0xC0..=0xDF => 2,
+                    0xE0..=0xEF => 3,
+                    0xF0..=0xF7 => 4,
+                    // Invalid leading bytes are reported by the UTF-8 check below
+                    _ => 1,
+                };
+                let mut buf = vec![first];
+                buf.extend(d.read_raw::<4>(len - 1).map_err(DecodeError::from)?);
+                StrictVal::String(std::str::from_utf8(&buf)?.to_owned())
             }
 
             // ASCII strings:
@@ -167,42 +335,12 @@ impl TypeSystem {
                     .ok_or_else(|| Error::TypeAbsent(spec.clone()))?
                     .is_char_enum() =>
             {
-                if sizing.max <= u8::MAX as u64 {
-                    StrictVal::String(TinyAscii::strict_decode(&mut reader)?.to_string())
-                } else if sizing.max <= u16::MAX as u64 {
-                    StrictVal::String(SmallAscii::strict_decode(&mut reader)?.to_string())
-                } else if sizing.max <= u24::MAX.into_u64() {
-                    StrictVal::String(MediumAscii::strict_decode(&mut reader)?.to_string())
-                } else if sizing.max <= u32::MAX as u64 {
-                    StrictVal::String(LargeAscii::strict_decode(&mut reader)?.to_string())
-                } else {
-                    StrictVal::String(
-                        Confined::<AsciiString, 0, { u64::MAX as usize }>::strict_decode(
-                            &mut reader,
-                        )?
-                        .to_string(),
-                    )
-                }
+                read_ascii(sizing, reader.unbox())?
             }
             // Restricted strings:
             Ty::Tuple(fields) if self.is_rstring(fields)? => {
                 let (_, sizing) = self.rstring_sizing(fields)?.expect("checked in match");
-                if sizing.max <= u8::MAX as u64 {
-                    StrictVal::String(TinyAscii::strict_decode(&mut reader)?.to_string())
-                } else if sizing.max <= u16::MAX as u64 {
-                    StrictVal::String(SmallAscii::strict_decode(&mut reader)?.to_string())
-                } else if sizing.max <= u24::MAX.into_u64() {
-                    StrictVal::String(MediumAscii::strict_decode(&mut reader)?.to_string())
-                } else if sizing.max <= u32::MAX as u64 {
-                    StrictVal::String(LargeAscii::strict_decode(&mut reader)?.to_string())
-                } else {
-                    StrictVal::String(
-                        Confined::<AsciiString, 0, { u64::MAX as usize }>::strict_decode(
-                            &mut reader,
-                        )?
-                        .to_string(),
-                    )
-                }
+                read_ascii(&sizing, reader.unbox())?
             }
 
             Ty::Enum(variants) => {
@@ -217,14 +355,14 @@ impl TypeSystem {
                 let Some(ty) = variants.ty_by_tag(tag) else {
                     return Err(DecodeError::EnumTagNotKnown(spec.to_string(), tag).into());
                 };
-                let fields = self.strict_read_type(*ty, reader.unbox())?;
+                let fields = self.read_type(*ty, reader.unbox(), depth, limits)?;
                 StrictVal::union(tag, fields.val)
             }
             Ty::Tuple(reqs) => {
                 let mut fields = Vec::with_capacity(reqs.len());
                 let d = reader.unbox();
                 for ty in reqs {
-                    let checked = self.strict_read_type(*ty, d)?;
+                    let checked = self.read_type(*ty, d, depth, limits)?;
                     fields.push(checked.val);
                 }
                 StrictVal::tuple(fields)
@@ -233,12 +371,19 @@ impl TypeSystem {
                 let mut fields = IndexMap::with_capacity(reqs.len());
                 let d = reader.unbox();
                 for field in reqs {
-                    let checked = self.strict_read_type(field.ty, d)?;
+                    let checked = self.read_type(field.ty, d, depth, limits)?;
                     fields.insert(field.name.clone(), checked.val);
                 }
                 StrictVal::Struct(fields)
             }
 
+            // Fixed-size unicode strings:
+            Ty::Array(ty, len) if self.is_unicode_char(*ty)? => {
+                let d = reader.unbox();
+                let buf = d.read_raw::<MAX16>(*len as usize).map_err(DecodeError::from)?;
+                StrictVal::String(String::from_utf8(buf).map_err(|err| err.utf8_error())?)
+            }
+
             // Fixed-size arrays:
             Ty::Array(ty, len) if ty.is_byte() => {
                 let d = reader.unbox();
@@ -249,139 +394,43 @@ impl TypeSystem {
                 let mut list = Vec::<StrictVal>::with_capacity(*len as usize);
                 let d = reader.unbox();
                 for _ in 0..*len {
-                    let checked = self.strict_read_type(*ty, d)?;
+                    let checked = self.read_type(*ty, d, depth, limits)?;
                     list.push(checked.val);
                 }
                 StrictVal::List(list)
             }
 
             // Byte strings:
-            Ty::List(ty, sizing) if ty.is_byte() && sizing.max <= u8::MAX as u64 => {
-                let string = TinyBlob::strict_decode(&mut reader)?;
-                StrictVal::Bytes(string.into_inner())
-            }
-            Ty::List(ty, sizing) if ty.is_byte() && sizing.max <= u16::MAX as u64 => {
-                let string = SmallBlob::strict_decode(&mut reader)?;
-                StrictVal::Bytes(string.into_inner())
-            }
-            Ty::List(ty, sizing) if ty.is_byte() && sizing.max <= u24::MAX.into_u64() => {
-                let string = MediumBlob::strict_decode(&mut reader)?;
-                StrictVal::Bytes(string.into_inner())
-            }
-            Ty::List(ty, sizing) if ty.is_byte() && sizing.max <= u32::MAX as u64 => {
-                let string = LargeBlob::strict_decode(&mut reader)?;
-                StrictVal::Bytes(string.into_inner())
+            Ty::List(ty, sizing) if ty.is_byte() => {
+                let d = reader.unbox();
+                let len = strict_read_len(sizing, d)?;
+                StrictVal::Bytes(d.read_raw::<{ usize::MAX }>(len).map_err(DecodeError::from)?)
             }
 
             // Unicode strings:
-            Ty::List(ty, sizing) if ty.is_unicode_char() && sizing.max <= u8::MAX as u64 => {
-                let string = TinyString::strict_decode(&mut reader)?;
-                StrictVal::String(string.into_inner())
-            }
-            Ty::List(ty, sizing) if ty.is_unicode_char() && sizing.max <= u16::MAX as u64 => {
-                let string = SmallString::strict_decode(&mut reader)?;
-                StrictVal::String(string.into_inner())
-            }
-            Ty::List(ty, sizing) if ty.is_unicode_char() && sizing.max <= u24::MAX.into_u64() => {
-                let string = MediumString::strict_decode(&mut reader)?;
-                StrictVal::String(string.into_inner())
-            }
-            Ty::List(ty, sizing) if ty.is_unicode_char() && sizing.max <= u32::MAX as u64 => {
-                let string = LargeString::strict_decode(&mut reader)?;
-                StrictVal::String(string.into_inner())
+            Ty::List(ty, sizing) if self.is_unicode_char(*ty)? => {
+                let d = reader.unbox();
+                let len = strict_read_len(sizing, d)?;
+                let buf = d.read_raw::<{ usize::MAX }>(len).map_err(DecodeError::from)?;
+                StrictVal::String(String::from_utf8(buf).map_err(|err| err.utf8_error())?)
             }
 
-            // Other lists:
-            Ty::List(ty, sizing) if sizing.max <= u8::MAX as u64 => {
-                let len = u8::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_list(len as usize, *ty, d)?;
-                StrictVal::List(list)
-            }
-            Ty::List(ty, sizing) if sizing.max <= u16::MAX as u64 => {
-                let len = u16::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_list(len as usize, *ty, d)?;
-                StrictVal::List(list)
-            }
-            Ty::List(ty, sizing) if sizing.max <= u24::MAX.into_u64() => {
-                let len = u24::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_list(len.into_usize(), *ty, d)?;
-                StrictVal::List(list)
-            }
-            Ty::List(ty, sizing) if sizing.max <= u32::MAX as u64 => {
-                let len = u32::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_list(len as usize, *ty, d)?;
-                StrictVal::List(list)
-            }
-            Ty::List(ty, _) => {
-                let len = u64::strict_decode(&mut reader)?;
+            // Other collections:
+            Ty::List(ty, sizing) => {
                 d = reader.unbox();
-                let list = self.strict_read_list(len as usize, *ty, d)?;
-                StrictVal::List(list)
+                let len = strict_read_len(sizing, d)?;
+                StrictVal::List(self.strict_read_list(len, *ty, d, depth, limits)?)
             }
             // TODO: Find a way to check for the uniqueness of the set values
-            Ty::Set(ty, sizing) if sizing.max <= u8::MAX as u64 => {
-                let len = u8::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_list(len as usize, *ty, d)?;
-                StrictVal::Set(list)
-            }
-            Ty::Set(ty, sizing) if sizing.max <= u16::MAX as u64 => {
-                let len = u16::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_list(len as usize, *ty, d)?;
-                StrictVal::Set(list)
-            }
-            Ty::Set(ty, sizing) if sizing.max <= u24::MAX.into_u64() => {
-                let len = u24::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_list(len.into_usize(), *ty, d)?;
-                StrictVal::Set(list)
-            }
-            Ty::Set(ty, sizing) if sizing.max <= u32::MAX as u64 => {
-                let len = u32::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_list(len as usize, *ty, d)?;
-                StrictVal::Set(list)
-            }
-            Ty::Set(ty, _) => {
-                let len = u64::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_list(len as usize, *ty, d)?;
-                StrictVal::Set(list)
-            }
-            Ty::Map(key_id, id, sizing) if sizing.max <= u8::MAX as u64 => {
-                let len = u8::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_map(len as usize, *key_id, *id, d)?;
-                StrictVal::Map(list)
-            }
-            Ty::Map(key_id, id, sizing) if sizing.max <= u16::MAX as u64 => {
-                let len = u16::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_map(len as usize, *key_id, *id, d)?;
-                StrictVal::Map(list)
-            }
-            Ty::Map(key_id, id, sizing) if sizing.max <= u24::MAX.into_u64() => {
-                let len = u24::strict_decode(&mut reader)?;
+            Ty::Set(ty, sizing) => {
                 d = reader.unbox();
-                let list = self.strict_read_map(len.into_usize(), *key_id, *id, d)?;
-                StrictVal::Map(list)
+                let len = strict_read_len(sizing, d)?;
+                StrictVal::Set(self.strict_read_list(len, *ty, d, depth, limits)?)
             }
-            Ty::Map(key_id, id, sizing) if sizing.max <= u32::MAX as u64 => {
-                let len = u32::strict_decode(&mut reader)?;
+            Ty::Map(key_id, id, sizing) => {
                 d = reader.unbox();
-                let list = self.strict_read_map(len as usize, *key_id, *id, d)?;
-                StrictVal::Map(list)
-            }
-            Ty::Map(key_id, id, _sizing) => {
-                let len = u64::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_map(len as usize, *key_id, *id, d)?;
-                StrictVal::Map(list)
+                let len = strict_read_len(sizing, d)?;
+                StrictVal::Map(self.strict_read_map(len, *key_id, *id, d, depth, limits)?)
             }
         };
 
@@ -395,7 +444,124 @@ impl TypeSystem {
 #[cfg(test)]
 mod test {
     use super::super::test_helpers::*;
-    // use super::*;
+    use super::*;
+
+    #[test]
+    fn unicode() {
+        use encoding::StrictSerialize;
+
+        use crate::ast::PrimitiveRef;
+
+        let sys = test_system();
+        let types = sys.as_types();
+        let char_id = SemId::unicode_char();
+        let val = types.strict_deserialize_type(char_id, "∑".as_bytes()).unwrap();
+        assert_eq!(val.val, svstr!("∑"));
+        let val = types.strict_deserialize_type(char_id, b"a").unwrap();
+        assert_eq!(val.val, svstr!("a"));
+        assert!(matches!(
+            types.strict_deserialize_type(char_id, &[0xFF]).unwrap_err(),
+            Error::InvalidUtf8(_)
+        ));
+        assert!(matches!(
+            types.strict_deserialize_type(char_id, &[0xC3, 0x28]).unwrap_err(),
+            Error::InvalidUtf8(_)
+        ));
+        assert!(matches!(
+            types.strict_deserialize_type(char_id, &[0xE2, 0x88]).unwrap_err(),
+            Error::Decode(_)
+        ));
+
+        let nominal = Nominal::with("TICK", "Ünïcödé ∑", 2);
+        let mut data = nominal.to_strict_serialized::<{ usize::MAX }>().unwrap().into_inner();
+        let loaded = sys.strict_deserialize_type("TestLib.Nominal", &data).unwrap();
+        assert_eq!(
+            loaded.val,
+            svstruct!(ticker => svnewtype!("TICK"), name => "Ünïcödé ∑", precision => svenum!(2))
+        );
+        // Breaking the first byte of the name after its length prefix
+        data[6] = 0xFF;
+        assert!(matches!(
+            sys.strict_deserialize_type("TestLib.Nominal", &data).unwrap_err(),
+            Error::InvalidUtf8(_)
+        ));
+
+        // Length prefixes outside of the type bounds are rejected before reading the data
+        for len in [0, 33, 0xFF] {
+            data[5] = len;
+            assert_eq!(
+                sys.strict_deserialize_type("TestLib.Nominal", &data).unwrap_err(),
+                Error::InvalidLen {
+                    len: len as u64,
+                    min: 1,
+                    max: 32
+                }
+            );
+        }
+        data[0] = 0;
+        assert!(matches!(
+            sys.strict_deserialize_type("TestLib.Nominal", &data).unwrap_err(),
+            Error::InvalidLen { len: 0, min: 1, .. }
+        ));
+        data[0] = 4;
+        data[1] = 0x80;
+        assert!(matches!(
+            sys.strict_deserialize_type("TestLib.Nominal", &data).unwrap_err(),
+            Error::Decode(DecodeError::Ascii(_))
+        ));
+    }
+
+    #[test]
+    fn recursive() {
+        use encoding::StrictSerialize;
+
+        let sys = test_system();
+        let tree = Tree {
+            value: 1,
+            children: tiny_vec![Tree::default(), Tree {
+                value: 2,
+                children: tiny_vec![Tree::default()]
+            }],
+        };
+        let expr =
+            Expr::Sum(Box::new(Expr::Neg(Box::new(Expr::Lit(3)))), Box::new(Expr::Tree(tree)));
+        let data = expr.to_strict_serialized::<{ usize::MAX }>().unwrap().into_inner();
+        let loaded = sys.strict_deserialize_type("TestLib.Expr", &data).unwrap();
+        let reserialized = sys.as_types().strict_serialize_type::<{ usize::MAX }>(&loaded).unwrap();
+        assert_eq!(
+            reserialized.to_strict_serialized::<{ usize::MAX }>().unwrap().into_inner(),
+            data
+        );
+
+        // A chain of negations nested deeper than the decoder permits
+        let mut data = vec![0x00; 100];
+        data.extend([0x02, 0x07]);
+        assert_eq!(
+            sys.strict_deserialize_type("TestLib.Expr", &data).unwrap_err(),
+            Error::DepthLimit(64)
+        );
+
+        // The same limit taken from the caller
+        let mut data = vec![0x00; 10];
+        data.extend([0x02, 0x07]);
+        assert!(sys.strict_deserialize_type("TestLib.Expr", &data).is_ok());
+        let limits = DecodeLimits {
+            max_depth: 8,
+            ..default!()
+        };
+        assert_eq!(
+            sys.strict_deserialize_type_with("TestLib.Expr", &data, limits).unwrap_err(),
+            Error::DepthLimit(8)
+        );
+    }
+
+    #[test]
+    fn odd_width_ints() {
+        assert_eq!(int_from_le(&[0xFF, 0xFF, 0x7F]), 0x7F_FFFF);
+        assert_eq!(int_from_le(&[0x00, 0x00, 0x80]), -0x80_0000);
+        assert_eq!(int_from_le(&[0xFE, 0xFF, 0xFF, 0xFF, 0xFF]), -2);
+        assert_eq!(uint_from_le(&[0xFF; 20]), (u1024::ONE << 160) - u1024::ONE);
+    }
 
     #[test]
     fn typify() {
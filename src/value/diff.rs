@@ -0,0 +1,579 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Structural comparison of strict values and patches applying the found
+//! differences back.
+//!
+//! Changes are addressed with value [`Path`]s. The paths of changes inside
+//! lists and sets refer to the item positions at the moment the change is
+//! applied, such that a patch must be applied in the order of its changes.
+//! Since paths can't address the data inside union variants, any change of a
+//! union value is reported as a replacement of the whole union.
+//!
+//! Patches have a text representation, with a change per line:
+//! ```text
+//! .name ~ "Old name" -> "New name"
+//! .items[2] + 15
+//! .dict{"key"} - (a=1, b=2)
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use super::path::{parse_path_prefix, Location};
+use super::ston::{self, parse_ston_prefix};
+use super::typify::{self, TypedVal};
+use super::{KeyStep, Path, PathError, PathParseError, Step, StrictVal};
+use crate::TypeSystem;
+
+/// Maximal number of list item pairs compared when searching for the minimal
+/// set of insertions and removals; longer lists are compared item by item.
+const LCS_LIMIT: usize = 1 << 20;
+
+/// Change of a value found by [`StrictVal::diff`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Change {
+    /// Field, map entry, list item or set member is added.
+    Added(StrictVal),
+    /// Field, map entry, list item or set member is removed.
+    Removed(StrictVal),
+    /// Value is replaced with another one.
+    Replaced(StrictVal, StrictVal),
+    /// Enum or union variant is changed.
+    Variant(StrictVal, StrictVal),
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Added(val) => write!(f, "+ {val}"),
+            Change::Removed(val) => write!(f, "- {val}"),
+            Change::Replaced(old, new) | Change::Variant(old, new) => write!(f, "~ {old} -> {new}"),
+        }
+    }
+}
+
+impl Change {
+    /// Constructs replacement change, detecting changes of enum and union
+    /// variants.
+    fn replace(old: StrictVal, new: StrictVal) -> Change {
+        match (&old, &new) {
+            (StrictVal::Enum(a), StrictVal::Enum(b))
+            | (StrictVal::Union(a, _), StrictVal::Union(b, _))
+                if a != b =>
+            {
+                Change::Variant(old, new)
+            }
+            _ => Change::Replaced(old, new),
+        }
+    }
+}
+
+/// Edit operation on a list.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Edit {
+    Keep,
+    Remove(usize),
+    Insert(usize),
+}
+
+/// Computes the shortest sequence of item removals and insertions converting
+/// list `a` into list `b`, using the longest common subsequence.
+fn edits(a: &[StrictVal], b: &[StrictVal]) -> Vec<Edit> {
+    if a.len().saturating_mul(b.len()) > LCS_LIMIT {
+        let mut edits = (0..a.len()).map(Edit::Remove).collect::<Vec<_>>();
+        edits.extend((0..b.len()).map(Edit::Insert));
+        return edits;
+    }
+    // lcs[i][j] is the length of the longest common subsequence of a[i..] and b[j..]
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] =
+                if a[i] == b[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut edits = Vec::with_capacity(a.len() + b.len());
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            edits.push(Edit::Keep);
+            i += 1;
+            j += 1;
+        } else if j == b.len() || (i < a.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            edits.push(Edit::Remove(i));
+            i += 1;
+        } else {
+            edits.push(Edit::Insert(j));
+            j += 1;
+        }
+    }
+    edits
+}
+
+impl StrictVal {
+    /// Finds structural differences between this value and the `other` value.
+    ///
+    /// Applying the returned changes as a [`Patch`] to this value produces the
+    /// `other` value, with the exception of the order of map entries: new
+    /// entries are always added to the end of the map.
+    pub fn diff(&self, other: &StrictVal) -> Vec<(Path, Change)> {
+        let mut changes = vec![];
+        self.diff_into(other, &mut Location::new(), &mut changes);
+        changes
+    }
+
+    fn diff_into(&self, other: &StrictVal, loc: &mut Location, changes: &mut Vec<(Path, Change)>) {
+        match (self, other) {
+            (a, b) if a == b => {}
+            (StrictVal::Tuple(a), StrictVal::Tuple(b)) if a.len() == b.len() => {
+                for (no, (a, b)) in a.iter().zip(b).enumerate() {
+                    loc.nested(Step::UnnamedField(no as u8), |loc| a.diff_into(b, loc, changes));
+                }
+            }
+            (StrictVal::Struct(a), StrictVal::Struct(b)) => {
+                for (name, val) in a {
+                    let step = Step::NamedField(name.clone());
+                    match b.get(name) {
+                        Some(other) => loc.nested(step, |loc| val.diff_into(other, loc, changes)),
+                        None => loc.nested(step, |loc| {
+                            changes.push((loc.path(), Change::Removed(val.clone())))
+                        }),
+                    }
+                }
+                for (name, val) in b.iter().filter(|(name, _)| !a.contains_key(*name)) {
+                    loc.nested(Step::NamedField(name.clone()), |loc| {
+                        changes.push((loc.path(), Change::Added(val.clone())))
+                    });
+                }
+            }
+            (StrictVal::List(a), StrictVal::List(b)) => Self::diff_list(a, b, loc, changes),
+            (StrictVal::Set(a), StrictVal::Set(b)) => Self::diff_set(a, b, loc, changes),
+            (StrictVal::Map(a), StrictVal::Map(b))
+                if a.iter().chain(b).all(|(key, _)| KeyStep::with_key(key).is_some()) =>
+            {
+                for (key, val) in a {
+                    let step = Step::Key(KeyStep::with_key(key).expect("checked above"));
+                    match b.iter().find(|(k, _)| k == key) {
+                        Some((_, other)) => {
+                            loc.nested(step, |loc| val.diff_into(other, loc, changes))
+                        }
+                        None => loc.nested(step, |loc| {
+                            changes.push((loc.path(), Change::Removed(val.clone())))
+                        }),
+                    }
+                }
+                for (key, val) in b.iter().filter(|(key, _)| !a.iter().any(|(k, _)| k == key)) {
+                    let step = Step::Key(KeyStep::with_key(key).expect("checked above"));
+                    loc.nested(step, |loc| changes.push((loc.path(), Change::Added(val.clone()))));
+                }
+            }
+            (a, b) => changes.push((loc.path(), Change::replace(a.clone(), b.clone()))),
+        }
+    }
+
+    /// Sets are compared like lists, but their items are only added or removed
+    /// and never changed in place. The order of the set items is preserved.
+    fn diff_set(
+        a: &[StrictVal],
+        b: &[StrictVal],
+        loc: &mut Location,
+        changes: &mut Vec<(Path, Change)>,
+    ) {
+        let mut pos = 0usize;
+        for edit in edits(a, b) {
+            match edit {
+                Edit::Keep => pos += 1,
+                Edit::Remove(i) => loc.nested(Step::Index(pos as u32), |loc| {
+                    changes.push((loc.path(), Change::Removed(a[i].clone())))
+                }),
+                Edit::Insert(j) => {
+                    loc.nested(Step::Index(pos as u32), |loc| {
+                        changes.push((loc.path(), Change::Added(b[j].clone())))
+                    });
+                    pos += 1;
+                }
+            }
+        }
+    }
+
+    /// Lists are compared by finding the minimal number of item insertions
+    /// and removals; a removal followed by an insertion at the same position
+    /// is treated as a change of the item.
+    fn diff_list(
+        a: &[StrictVal],
+        b: &[StrictVal],
+        loc: &mut Location,
+        changes: &mut Vec<(Path, Change)>,
+    ) {
+        let mut removed = vec![];
+        let mut inserted = vec![];
+        let mut pos = 0usize;
+        let edits = edits(a, b);
+        for edit in edits.into_iter().chain([Edit::Keep]) {
+            match edit {
+                Edit::Remove(i) => removed.push(i),
+                Edit::Insert(j) => inserted.push(j),
+                Edit::Keep => {
+                    let paired = removed.len().min(inserted.len());
+                    for (i, j) in removed.iter().zip(&inserted) {
+                        loc.nested(Step::Index(pos as u32), |loc| {
+                            a[*i].diff_into(&b[*j], loc, changes)
+                        });
+                        pos += 1;
+                    }
+                    for i in &removed[paired..] {
+                        loc.nested(Step::Index(pos as u32), |loc| {
+                            changes.push((loc.path(), Change::Removed(a[*i].clone())))
+                        });
+                    }
+                    for j in &inserted[paired..] {
+                        loc.nested(Step::Index(pos as u32), |loc| {
+                            changes.push((loc.path(), Change::Added(b[*j].clone())))
+                        });
+                        pos += 1;
+                    }
+                    removed.clear();
+                    inserted.clear();
+                    pos += 1;
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum PatchError {
+    #[display(inner)]
+    #[from]
+    Path(PathError),
+
+    /// value at `{0}` is expected to be {1}, but it is {2}.
+    Mismatch(Path, StrictVal, StrictVal),
+
+    /// value can't be added at `{0}`, since the position is already taken or
+    /// is not a field, a map key or a collection index.
+    InvalidTarget(Path),
+
+    /// patched value doesn't match its type. Details: {0}
+    #[from]
+    Typify(typify::Error),
+}
+
+/// Errors parsing text representation of a patch.
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum PatchParseError {
+    /// invalid path at line {0}: {1}
+    Path(usize, PathParseError),
+
+    /// invalid value at line {0}: {1}
+    Value(usize, ston::ParseError),
+
+    /// line {0} doesn't contain a change, which must start with `+`, `-` or `~`.
+    NoChange(usize),
+
+    /// line {0} misses `->` separating the old value from the new one.
+    NoArrow(usize),
+
+    /// unexpected data `{1}` at the end of line {0}.
+    Trailing(usize, String),
+}
+
+/// Sequence of changes which can be applied to a value.
+#[derive(Wrapper, Clone, Eq, PartialEq, Debug, Default, From)]
+#[wrapper(Deref)]
+pub struct Patch(Vec<(Path, Change)>);
+
+impl Display for Patch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (path, change) in &self.0 {
+            if path.is_empty() {
+                writeln!(f, "{change}")?;
+            } else {
+                writeln!(f, "{path} {change}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Patch {
+    type Err = PatchParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut changes = vec![];
+        for (no, line) in s.lines().enumerate() {
+            let no = no + 1;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let value = |s| parse_ston_prefix(s).map_err(|err| PatchParseError::Value(no, err));
+            let (path, rest) =
+                parse_path_prefix(line).map_err(|err| PatchParseError::Path(no, err))?;
+            let rest = rest.trim_start();
+            let (change, rest) = match (rest.get(..1), rest.get(1..)) {
+                (Some("+"), Some(rest)) => {
+                    value(rest).map(|(val, rest)| (Change::Added(val), rest))?
+                }
+                (Some("-"), Some(rest)) => {
+                    value(rest).map(|(val, rest)| (Change::Removed(val), rest))?
+                }
+                (Some("~"), Some(rest)) => {
+                    let (old, rest) = value(rest)?;
+                    let rest =
+                        rest.trim_start().strip_prefix("->").ok_or(PatchParseError::NoArrow(no))?;
+                    let (new, rest) = value(rest)?;
+                    (Change::replace(old, new), rest)
+                }
+                _ => return Err(PatchParseError::NoChange(no)),
+            };
+            if !rest.trim().is_empty() {
+                return Err(PatchParseError::Trailing(no, rest.trim().to_owned()));
+            }
+            changes.push((path, change));
+        }
+        Ok(Patch(changes))
+    }
+}
+
+impl Patch {
+    /// Constructs patch converting value `old` into `new`.
+    pub fn with(old: &StrictVal, new: &StrictVal) -> Self { Patch(old.diff(new)) }
+
+    /// Applies changes to a value, checking that the value matches the state
+    /// before each of the changes. In case of an error, the value may be left
+    /// partially patched.
+    pub fn apply(&self, val: &mut StrictVal) -> Result<(), PatchError> {
+        for (path, change) in &self.0 {
+            val.apply_change(path, change)?;
+        }
+        Ok(())
+    }
+}
+
+impl StrictVal {
+    fn apply_change(&mut self, path: &Path, change: &Change) -> Result<(), PatchError> {
+        let mismatch = |expected: &StrictVal, found: &StrictVal| {
+            PatchError::Mismatch(path.clone(), expected.clone(), found.clone())
+        };
+        match change {
+            Change::Replaced(old, new) | Change::Variant(old, new) => {
+                let current = self.at_path_mut(path)?;
+                if current != old {
+                    return Err(mismatch(old, current));
+                }
+                *current = new.clone();
+            }
+            Change::Removed(old) => {
+                let current = self.at_path(path)?;
+                if current != old {
+                    return Err(mismatch(old, current));
+                }
+                self.remove_at_path(path)?;
+            }
+            Change::Added(new) => {
+                let Some((last, parent)) = path.split_last() else {
+                    return Err(PathError::EmptyPath.into());
+                };
+                match (self.at_path_mut(parent)?, last) {
+                    (StrictVal::Struct(fields), Step::NamedField(name))
+                        if !fields.contains_key(name) =>
+                    {
+                        fields.insert(name.clone(), new.clone());
+                    }
+                    (StrictVal::Map(items), Step::Key(key))
+                        if !items.iter().any(|(k, _)| key.has_match(k)) =>
+                    {
+                        items.push((key.to_strict_val(), new.clone()));
+                    }
+                    (StrictVal::List(items) | StrictVal::Set(items), Step::Index(idx))
+                        if *idx as usize <= items.len() =>
+                    {
+                        items.insert(*idx as usize, new.clone());
+                    }
+                    _ => return Err(PatchError::InvalidTarget(path.clone())),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TypeSystem {
+    /// Applies patch to a typed value, checking that the patched value
+    /// matches the value type.
+    pub fn apply_patch(&self, typed: TypedVal, patch: &Patch) -> Result<TypedVal, PatchError> {
+        let sem_id = typed.orig.id;
+        let mut val = typed.val;
+        patch.apply(&mut val)?;
+        Ok(self.typify(val, sem_id)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::typesys::TypeSymbol;
+    use crate::value::test_helpers::test_system;
+
+    fn path(s: &str) -> Path { Path::from_str(s).unwrap() }
+
+    fn check(old: StrictVal, new: StrictVal) -> Patch {
+        let patch = Patch::with(&old, &new);
+        let mut val = old;
+        patch.apply(&mut val).unwrap();
+        assert_eq!(val, new);
+        assert_eq!(Patch::from_str(&patch.to_string()).unwrap(), patch);
+        patch
+    }
+
+    #[test]
+    fn structs() {
+        let old = svstruct!(name => "Old", count => 5u8, extra => svnone!());
+        let new = svstruct!(name => "New", count => 5u8, added => svsome!(1u8));
+        let patch = check(old, new);
+        assert_eq!(patch.as_slice(), &[
+            (path(".name"), Change::Replaced(svstr!("Old"), svstr!("New"))),
+            (path(".extra"), Change::Removed(svnone!())),
+            (path(".added"), Change::Added(svsome!(1u8))),
+        ]);
+    }
+
+    #[test]
+    fn unions() {
+        let patch = check(svsome!(1u8), svsome!(2u8));
+        assert_eq!(patch.as_slice(), &[(
+            Path::new(),
+            Change::Replaced(svsome!(1u8), svsome!(2u8))
+        )]);
+        let patch = check(svtuple!([svsome!(1u8)]), svtuple!([svnone!()]));
+        assert_eq!(patch.as_slice(), &[(path(".0"), Change::Variant(svsome!(1u8), svnone!()))]);
+    }
+
+    #[test]
+    fn lists() {
+        let old = svlist!([1u8, 2, 3, 4, 5]);
+        let new = svlist!([0u8, 1, 3, 7, 5, 6]);
+        let patch = check(old, new);
+        assert_eq!(patch.as_slice(), &[
+            (path("[0]"), Change::Added(svnum!(0u8))),
+            (path("[2]"), Change::Removed(svnum!(2u8))),
+            (path("[3]"), Change::Replaced(svnum!(4u8), svnum!(7u8))),
+            (path("[5]"), Change::Added(svnum!(6u8))),
+        ]);
+
+        check(svlist!([1u8, 2, 3]), svlist!(Vec::<u8>::new()));
+        check(svlist!(Vec::<u8>::new()), svlist!([1u8, 2, 3]));
+        check(
+            svlist!([svstruct!(a => 1u8), svstruct!(a => 2u8)]),
+            svlist!([svstruct!(a => 1u8), svstruct!(a => 3u8)]),
+        );
+    }
+
+    #[test]
+    fn sets() {
+        let patch = check(svset!([1u8, 2, 3, 4]), svset!([2u8, 4, 5]));
+        assert_eq!(patch.as_slice(), &[
+            (path("[0]"), Change::Removed(svnum!(1u8))),
+            (path("[1]"), Change::Removed(svnum!(3u8))),
+            (path("[2]"), Change::Added(svnum!(5u8))),
+        ]);
+        let patch = check(svset!([1u8, 2, 3]), svset!([3u8, 1, 2]));
+        assert_eq!(patch.as_slice(), &[
+            (path("[0]"), Change::Added(svnum!(3u8))),
+            (path("[3]"), Change::Removed(svnum!(3u8))),
+        ]);
+    }
+
+    #[test]
+    fn maps() {
+        let old = StrictVal::map([(svstr!("a"), svnum!(1u8)), (svstr!("b"), svnum!(2u8))]);
+        let new = StrictVal::map([(svstr!("a"), svnum!(3u8)), (svstr!("c"), svnum!(4u8))]);
+        let patch = check(old, new);
+        assert_eq!(patch.as_slice(), &[
+            (path(r#"{"a"}"#), Change::Replaced(svnum!(1u8), svnum!(3u8))),
+            (path(r#"{"b"}"#), Change::Removed(svnum!(2u8))),
+            (path(r#"{"c"}"#), Change::Added(svnum!(4u8))),
+        ]);
+    }
+
+    #[test]
+    fn text() {
+        let patch = Patch::from_str(
+            r#"
+            .name ~ "Old name" -> "New name"
+            .items[2] + 15
+            .dict{"key"} - (a=1, b=2)
+            "#,
+        )
+        .unwrap();
+        assert_eq!(patch.len(), 3);
+        assert_eq!(
+            patch.to_string(),
+            ".name ~ \"Old name\" -> \"New name\"\n.items[2] + 15\n.dict{\"key\"} - (a=1, b=2)\n"
+        );
+
+        assert_eq!(Patch::from_str(".a = 1"), Err(PatchParseError::NoChange(1)));
+        assert_eq!(Patch::from_str("\n.a ~ 1 2"), Err(PatchParseError::NoArrow(2)));
+        assert!(matches!(Patch::from_str(".a + 1 2"), Err(PatchParseError::Trailing(1, _))));
+    }
+
+    #[test]
+    fn apply_errors() {
+        let mut val = svstruct!(name => "Name");
+        let patch = Patch::from_str(r#".name ~ "Other" -> "New""#).unwrap();
+        assert!(matches!(patch.apply(&mut val), Err(PatchError::Mismatch(..))));
+        let patch = Patch::from_str(r#".name + "New""#).unwrap();
+        assert_eq!(patch.apply(&mut val), Err(PatchError::InvalidTarget(path(".name"))));
+        let patch = Patch::from_str(r#"+ "New""#).unwrap();
+        assert_eq!(patch.apply(&mut val), Err(PatchError::Path(PathError::EmptyPath)));
+        assert_eq!(val, svstruct!(name => "Name"));
+    }
+
+    #[test]
+    fn typed() {
+        let sys = test_system();
+        let sem_id = sys.to_sem_id("TestLib.Nominal").unwrap();
+        let types = sys.as_types();
+        let val =
+            svstruct!(ticker => svnewtype!("TICK"), name => "Some name", precision => svenum!(2));
+        let typed = TypedVal {
+            orig: TypeSymbol::unnamed(sem_id),
+            val,
+        };
+
+        let patch = Patch::from_str(r#".name ~ "Some name" -> "Other name""#).unwrap();
+        let patched = types.apply_patch(typed.clone(), &patch).unwrap();
+        assert_eq!(patched.val.at_path(&path(".name")).unwrap(), &svstr!("Other name"));
+
+        let patch = Patch::from_str(r#".name ~ "Some name" -> 5"#).unwrap();
+        let res = types.apply_patch(typed.clone(), &patch);
+        assert!(matches!(res, Err(PatchError::Typify(_))), "{res:?}");
+
+        let patch = Patch::from_str(".supply + 100").unwrap();
+        let res = types.apply_patch(typed, &patch);
+        assert!(matches!(res, Err(PatchError::Typify(_))), "{res:?}");
+    }
+}
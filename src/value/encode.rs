@@ -19,19 +19,66 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
-
+use std::collections::BTreeSet;
 use std::io;
+use std::str::FromStr;
 
-use amplify::confinement::Confined;
+use amplify::confinement::{self, Confined};
+use amplify::num::apfloat::{ieee, Float, Status};
 use amplify::num::u24;
 use encoding::{
-    Primitive, SerializeError, Sizing, StrictEncode, StrictSerialize, StrictType, TypeName,
-    TypedWrite, WriteRaw,
+    FieldName, Primitive, Sizing, StrictEncode, StrictSerialize, StrictType, TypeName, TypedWrite,
+    WriteRaw,
 };
 
+use crate::ast::EnumVariants;
+use crate::typesys::UnknownType;
 use crate::typify::TypedVal;
-use crate::value::{EnumTag, StrictNum};
-use crate::{SemId, StrictVal, Ty, TypeSystem};
+use crate::value::path::Location;
+use crate::value::typify::PrimitiveValue;
+use crate::value::val::{float_to_le_bytes, int_for_prim};
+use crate::value::{EnumTag, KeyStep, Path, Step, StrictNum};
+use crate::{SemId, StrictVal, Ty, TypeRef, TypeSystem};
+
+#[derive(Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum Error {
+    /// unknown type `{0}`.
+    TypeAbsent(SemId),
+
+    #[display(inner)]
+    #[from]
+    UnknownType(UnknownType),
+
+    /// value {1} at `{0}` doesn't match type requirements `{2}`.
+    TypeMismatch(Path, StrictVal, Ty<SemId>),
+
+    /// number {1} at `{0}` is out of the range of type `{2}`.
+    NumberOutOfRange(Path, StrictNum, Primitive),
+
+    /// collection at `{0}` has size {1} which is out of type required bounds{2}.
+    InvalidLen(Path, usize, Sizing),
+
+    /// value "{1}" at `{0}` contains characters not allowed by its type.
+    InvalidChars(Path, String),
+
+    /// structure at `{0}` lacks field `{1}`.
+    MissingField(Path, FieldName),
+
+    /// structure at `{0}` has unexpected field `{1}`.
+    ExtraField(Path, FieldName),
+
+    /// invalid enum or union tag `{1}` at `{0}`; allowed variants are {2}.
+    InvalidTag(Path, EnumTag, EnumVariants),
+
+    #[display(inner)]
+    #[from]
+    Confinement(confinement::Error),
+
+    #[display(inner)]
+    #[from]
+    Io(io::Error),
+}
 
 #[derive(Clone, Debug)]
 pub struct SerializedType<const MAX_LEN: usize>(Confined<Vec<u8>, 0, MAX_LEN>);
@@ -54,158 +101,244 @@ impl TypeSystem {
     pub fn strict_serialize_type<const MAX_LEN: usize>(
         &self,
         typed: &TypedVal,
-    ) -> Result<SerializedType<MAX_LEN>, SerializeError> {
+    ) -> Result<SerializedType<MAX_LEN>, Error> {
         let mut buf = Vec::new();
         self.strict_write_type(typed, &mut buf)?;
-        Confined::try_from(buf).map(SerializedType).map_err(SerializeError::from)
+        Ok(SerializedType(Confined::try_from(buf)?))
     }
 
+    /// Writes strict-encoded value into the writer.
+    ///
+    /// The value is checked against its type during the encoding, such that
+    /// values which were not created with [`TypeSystem::typify`] are also
+    /// encoded safely. However, in case of an error, some data may be already
+    /// written to the writer.
     pub fn strict_write_type(
         &self,
         typed: &TypedVal,
         writer: &mut impl io::Write,
-    ) -> Result<(), io::Error> {
-        self.strict_write_value(&typed.val, typed.orig.id, writer)
+    ) -> Result<(), Error> {
+        self.strict_write_value(&typed.val, typed.orig.id, &mut Location::new(), writer)
     }
 
     fn strict_write_value(
         &self,
         val: &StrictVal,
         sem_id: SemId,
+        loc: &mut Location,
         writer: &mut impl io::Write,
-    ) -> Result<(), io::Error> {
-        let ty = self.find(sem_id).expect("typified with some other TypeSystem");
-        self.strict_write_ty(val, ty, writer)
+    ) -> Result<(), Error> {
+        let ty = self.find(sem_id).ok_or(Error::TypeAbsent(sem_id))?;
+        self.strict_write_ty(val, ty, loc, writer)
+    }
+
+    fn strict_write_len(
+        &self,
+        len: usize,
+        sizing: &Sizing,
+        loc: &Location,
+        writer: &mut impl io::Write,
+    ) -> Result<(), Error> {
+        if (len as u64) < sizing.min || (len as u64) > sizing.max {
+            return Err(Error::InvalidLen(loc.path(), len, *sizing));
+        }
+        let bytes_count = sizing.byte_size();
+        writer.write_all(&len.to_le_bytes()[0..bytes_count])?;
+        Ok(())
+    }
+
+    /// Checks that the string contains only characters from the given
+    /// character enumeration, or is a valid unicode string.
+    fn check_chars(&self, s: &str, char_id: SemId, loc: &Location) -> Result<(), Error> {
+        match self.find(char_id).ok_or(Error::TypeAbsent(char_id))? {
+            Ty::UnicodeChar => Ok(()),
+            Ty::Enum(variants) if s.bytes().all(|c| variants.has_tag(c)) => Ok(()),
+            ty if ty.is_byte() => Ok(()),
+            _ => Err(Error::InvalidChars(loc.path(), s.to_owned())),
+        }
     }
 
     fn strict_write_ty(
         &self,
         val: &StrictVal,
         ty: &Ty<SemId>,
+        loc: &mut Location,
         writer: &mut impl io::Write,
-    ) -> Result<(), io::Error> {
+    ) -> Result<(), Error> {
         match (val, ty) {
-            (StrictVal::Unit, Ty::Primitive(prim)) => {
-                debug_assert_eq!(*prim, Primitive::UNIT);
+            (StrictVal::Unit, Ty::Primitive(prim)) if *prim == Primitive::UNIT => {
                 // Do nothing
             }
-            (StrictVal::Number(StrictNum::Uint(num)), Ty::Primitive(prim)) => {
-                let bytes_count = prim.byte_size() as usize;
-                let le_bytes = &num.to_le_bytes()[0..bytes_count];
-                writer.write_all(le_bytes)?;
-            }
-            (StrictVal::Number(StrictNum::BigUint(num)), Ty::Primitive(prim)) => {
-                let bytes_count = prim.byte_size() as usize;
-                let le_bytes = &num.to_le_bytes()[0..bytes_count];
-                writer.write_all(le_bytes)?;
-            }
-            (StrictVal::Number(StrictNum::Int(num)), Ty::Primitive(prim)) => {
-                let bytes_count = prim.byte_size() as usize;
-                let le_bytes = &num.to_le_bytes()[0..bytes_count];
-                writer.write_all(le_bytes)?;
+            (StrictVal::Number(num), Ty::Primitive(prim)) if prim.is_float() => {
+                let float = match num {
+                    StrictNum::Float(float) => *float,
+                    int => ieee::Oct::from_str(&int.to_string())
+                        .expect("decimal integer is always a valid float"),
+                };
+                let le_bytes = float_to_le_bytes(float, *prim);
+                if le_bytes.status.contains(Status::OVERFLOW) && !float.is_infinite() {
+                    return Err(Error::NumberOutOfRange(loc.path(), *num, *prim));
+                }
+                writer.write_all(&le_bytes.value)?;
             }
-            (StrictVal::Number(StrictNum::BigInt(num)), Ty::Primitive(prim)) => {
+            (StrictVal::Number(num), Ty::Primitive(prim))
+                if prim.is_int() && !matches!(num, StrictNum::Float(_)) =>
+            {
                 let bytes_count = prim.byte_size() as usize;
-                let le_bytes = &num.to_le_bytes()[0..bytes_count];
-                writer.write_all(le_bytes)?;
+                let le_bytes = match int_for_prim(*num, *prim) {
+                    Some(StrictNum::Uint(num)) => num.to_le_bytes().to_vec(),
+                    Some(StrictNum::Int(num)) => num.to_le_bytes().to_vec(),
+                    Some(StrictNum::BigUint(num)) => num.to_le_bytes().to_vec(),
+                    Some(StrictNum::BigInt(num)) => num.to_le_bytes().to_vec(),
+                    Some(StrictNum::Float(_)) => unreachable!("integer conversion"),
+                    None => return Err(Error::NumberOutOfRange(loc.path(), *num, *prim)),
+                };
+                writer.write_all(&le_bytes[0..bytes_count])?;
             }
 
-            (StrictVal::String(s), Ty::UnicodeChar) => {
-                debug_assert_eq!(s.chars().count(), 1);
+            (StrictVal::String(s), Ty::UnicodeChar) if s.chars().count() == 1 => {
                 writer.write_all(s.as_bytes())?;
             }
-            (StrictVal::Bytes(vec), Ty::Array(_, len)) => {
-                debug_assert_eq!(vec.len(), *len as usize);
+            (StrictVal::Bytes(vec), Ty::Array(sem_id, len)) if sem_id.is_byte() => {
+                if vec.len() != *len as usize {
+                    return Err(Error::InvalidLen(
+                        loc.path(),
+                        vec.len(),
+                        Sizing::fixed(*len as u64),
+                    ));
+                }
                 writer.write_all(vec)?;
             }
-            (StrictVal::String(s), Ty::Array(_, len)) => {
-                debug_assert_eq!(s.len(), *len as usize);
+            (StrictVal::String(s), Ty::Array(sem_id, len)) => {
+                if s.len() != *len as usize {
+                    return Err(Error::InvalidLen(loc.path(), s.len(), Sizing::fixed(*len as u64)));
+                }
+                self.check_chars(s, *sem_id, loc)?;
                 writer.write_all(s.as_bytes())?;
             }
+            (StrictVal::List(vals), Ty::Array(sem_id, len)) => {
+                if vals.len() != *len as usize {
+                    return Err(Error::InvalidLen(
+                        loc.path(),
+                        vals.len(),
+                        Sizing::fixed(*len as u64),
+                    ));
+                }
+                for (idx, val) in vals.iter().enumerate() {
+                    loc.nested(Step::Index(idx as u32), |loc| {
+                        self.strict_write_value(val, *sem_id, loc, writer)
+                    })?;
+                }
+            }
 
             (StrictVal::Tuple(vals), Ty::Tuple(fields)) => {
-                debug_assert_eq!(vals.len(), fields.len());
-                for (val, sem_id) in vals.iter().zip(fields) {
-                    self.strict_write_value(val, *sem_id, writer)?;
+                if vals.len() != fields.len() {
+                    let sizing = Sizing::fixed(fields.len() as u64);
+                    return Err(Error::InvalidLen(loc.path(), vals.len(), sizing));
+                }
+                for (no, (val, sem_id)) in vals.iter().zip(fields).enumerate() {
+                    loc.nested(Step::UnnamedField(no as u8), |loc| {
+                        self.strict_write_value(val, *sem_id, loc, writer)
+                    })?;
                 }
             }
             (StrictVal::Struct(vals), Ty::Struct(fields)) => {
-                debug_assert_eq!(vals.len(), fields.len());
-                for (val, field) in vals.values().zip(fields) {
-                    self.strict_write_value(val, field.ty, writer)?;
+                if let Some(name) =
+                    vals.keys().find(|name| !fields.iter().any(|field| &field.name == *name))
+                {
+                    return Err(Error::ExtraField(loc.path(), name.clone()));
+                }
+                // Fields must be written in the order of their declaration in the type, and not
+                // in the order they are present in the value.
+                for field in fields {
+                    let val = vals
+                        .get(&field.name)
+                        .ok_or_else(|| Error::MissingField(loc.path(), field.name.clone()))?;
+                    loc.nested(Step::NamedField(field.name.clone()), |loc| {
+                        self.strict_write_value(val, field.ty, loc, writer)
+                    })?;
                 }
             }
-            (StrictVal::Enum(EnumTag::Ord(tag)), Ty::Enum(variants)) => {
-                debug_assert!(variants.has_tag(*tag));
-                writer.write_all(&[*tag])?;
-            }
-            (StrictVal::Enum(EnumTag::Name(tag)), Ty::Enum(variants)) => {
-                let tag = variants.tag_by_name(tag).expect("Type::System::typify guarantees");
+            (StrictVal::Enum(tag), Ty::Enum(variants)) => {
+                let found = match tag {
+                    EnumTag::Ord(tag) => Some(*tag).filter(|tag| variants.has_tag(*tag)),
+                    EnumTag::Name(name) => variants.tag_by_name(name),
+                };
+                let Some(tag) = found else {
+                    return Err(Error::InvalidTag(loc.path(), tag.clone(), variants.clone()));
+                };
                 writer.write_all(&[tag])?;
             }
-            (StrictVal::Union(EnumTag::Ord(tag), val), Ty::Union(variants)) => {
-                let sem_id = variants.ty_by_tag(*tag).expect("Type::System::typify guarantees");
-                writer.write_all(&[*tag])?;
-                self.strict_write_value(val, *sem_id, writer)?;
-            }
-            (StrictVal::Union(EnumTag::Name(tag), val), Ty::Union(variants)) => {
-                let (variant, sem_id) =
-                    variants.by_name(tag).expect("Type::System::typify guarantees");
-                writer.write_all(&[variant.tag])?;
-                self.strict_write_value(val, *sem_id, writer)?;
+            (StrictVal::Union(tag, val), Ty::Union(variants)) => {
+                let found = match tag {
+                    EnumTag::Ord(tag) => variants.ty_by_tag(*tag).map(|sem_id| (*tag, sem_id)),
+                    EnumTag::Name(name) => {
+                        variants.by_name(name).map(|(variant, sem_id)| (variant.tag, sem_id))
+                    }
+                };
+                let Some((tag, sem_id)) = found else {
+                    return Err(Error::InvalidTag(
+                        loc.path(),
+                        tag.clone(),
+                        EnumVariants::try_from(variants.keys().cloned().collect::<BTreeSet<_>>())
+                            .expect("same collection size"),
+                    ));
+                };
+                writer.write_all(&[tag])?;
+                self.strict_write_value(val, *sem_id, loc, writer)?;
             }
 
-            (StrictVal::String(s), Ty::List(_, sizing)) => {
-                let bytes_count = sizing.byte_size();
-                let le_bytes = &s.len().to_le_bytes()[0..bytes_count];
-                writer.write_all(le_bytes)?;
+            (StrictVal::String(s), Ty::List(sem_id, sizing)) => {
+                self.check_chars(s, *sem_id, loc)?;
+                self.strict_write_len(s.len(), sizing, loc, writer)?;
                 writer.write_all(s.as_bytes())?;
             }
-            (StrictVal::Bytes(s), Ty::List(_, sizing)) => {
-                let bytes_count = sizing.byte_size();
-                let le_bytes = &s.len().to_le_bytes()[0..bytes_count];
-                writer.write_all(le_bytes)?;
+            (StrictVal::Bytes(s), Ty::List(sem_id, sizing)) if sem_id.is_byte() => {
+                self.strict_write_len(s.len(), sizing, loc, writer)?;
                 writer.write_all(s)?;
             }
             (StrictVal::List(list), Ty::List(sem_id, sizing))
             | (StrictVal::Set(list), Ty::Set(sem_id, sizing)) => {
-                let bytes_count = sizing.byte_size();
-                let le_bytes = &list.len().to_le_bytes()[0..bytes_count];
-                writer.write_all(le_bytes)?;
-                for val in list {
-                    self.strict_write_value(val, *sem_id, writer)?;
+                self.strict_write_len(list.len(), sizing, loc, writer)?;
+                for (idx, val) in list.iter().enumerate() {
+                    loc.nested(Step::Index(idx as u32), |loc| {
+                        self.strict_write_value(val, *sem_id, loc, writer)
+                    })?;
                 }
             }
             (StrictVal::Map(list), Ty::Map(key_id, sem_id, sizing)) => {
-                let bytes_count = sizing.byte_size();
-                let le_bytes = &list.len().to_le_bytes()[0..bytes_count];
-                writer.write_all(le_bytes)?;
-                for (key, val) in list {
-                    self.strict_write_value(key, *key_id, writer)?;
-                    self.strict_write_value(val, *sem_id, writer)?;
+                self.strict_write_len(list.len(), sizing, loc, writer)?;
+                for (idx, (key, val)) in list.iter().enumerate() {
+                    loc.nested(Step::Index(idx as u32), |loc| {
+                        self.strict_write_value(key, *key_id, loc, writer)
+                    })?;
+                    let step =
+                        KeyStep::with_key(key).map(Step::Key).unwrap_or(Step::Index(idx as u32));
+                    loc.nested(step, |loc| self.strict_write_value(val, *sem_id, loc, writer))?;
                 }
             }
 
-            (StrictVal::String(s), Ty::Tuple(fields))
-                if s.is_ascii() && self.is_rstring(fields).expect("type absent") =>
-            {
-                let (_, sizing) =
-                    self.rstring_sizing(fields).expect("type absent").expect("checked above");
-                let bytes_count = sizing.byte_size();
-                debug_assert!(s.len() <= sizing.max as usize);
-                let le_bytes = &s.len().to_le_bytes()[0..bytes_count];
-                writer.write_all(le_bytes)?;
+            (StrictVal::String(s), Ty::Tuple(fields)) if self.is_rstring(fields)? => {
+                let (rest_id, sizing) = self.rstring_sizing(fields)?.expect("checked above");
+                let mut chars = s.chars();
+                let first = chars.next().map(String::from).unwrap_or_default();
+                self.check_chars(&first, fields[0], loc)
+                    .and_then(|_| self.check_chars(chars.as_str(), rest_id, loc))
+                    .map_err(|err| match err {
+                        Error::InvalidChars(path, _) => Error::InvalidChars(path, s.clone()),
+                        err => err,
+                    })?;
+                self.strict_write_len(s.len(), &sizing, loc, writer)?;
                 writer.write_all(s.as_bytes())?;
             }
 
-            (a, b) => panic!("bug in business logic of type system. Details:\n{a:#?}\n{b:#?}"),
+            (val, ty) => return Err(Error::TypeMismatch(loc.path(), val.clone(), ty.clone())),
         }
 
         Ok(())
     }
 }
-
-trait SizingExt {
+pub(crate) trait SizingExt {
     fn byte_size(&self) -> usize;
 }
 
@@ -220,3 +353,117 @@ impl SizingExt for Sizing {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use encoding::StrictSerialize;
+
+    use super::super::test_helpers::*;
+    use super::*;
+    use crate::typesys::TypeSymbol;
+
+    fn typed(val: StrictVal, name: &'static str) -> (TypeSystem, TypedVal) {
+        let sys = test_system();
+        let sem_id = sys.to_sem_id(name).unwrap();
+        let typed = TypedVal {
+            orig: TypeSymbol::unnamed(sem_id),
+            val,
+        };
+        (sys.as_types().clone(), typed)
+    }
+
+    #[test]
+    fn field_order() {
+        let (types, val) = typed(
+            svstruct!(precision => svenum!(2), name => "Some name", ticker => svnewtype!("TICK")),
+            "TestLib.Nominal",
+        );
+        let nominal = Nominal::with("TICK", "Some name", 2);
+        let data = nominal.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        let serialized = types.strict_serialize_type::<{ usize::MAX }>(&val).unwrap();
+        assert_eq!(serialized.0, data);
+    }
+
+    #[test]
+    fn invalid_values() {
+        let check = |val: StrictVal, name: &'static str| {
+            let (types, val) = typed(val, name);
+            types.strict_write_type(&val, &mut vec![]).unwrap_err()
+        };
+
+        let err = check(
+            svstruct!(ticker => svnewtype!("TICK"), name => "Some name", precision => svenum!(5)),
+            "TestLib.Nominal",
+        );
+        assert!(
+            matches!(&err, Error::InvalidTag(path, EnumTag::Ord(5), _) if path.to_string() == ".precision"),
+            "{err}"
+        );
+        assert_eq!(
+            check(
+                svstruct!(ticker => svnewtype!("T-K"), name => "Some name", precision => svenum!(2)),
+                "TestLib.Nominal"
+            )
+            .to_string(),
+            r#"value "T-K" at `.ticker.0` contains characters not allowed by its type."#
+        );
+        assert_eq!(
+            check(
+                svstruct!(ticker => svnewtype!("TICK"), name => "", precision => svenum!(2)),
+                "TestLib.Nominal"
+            )
+            .to_string(),
+            "collection at `.name` has size 0 which is out of type required bounds ^ 1..0x20."
+        );
+        assert_eq!(
+            check(svstruct!(ticker => svnewtype!("TICK"), name => "Name"), "TestLib.Nominal")
+                .to_string(),
+            "structure at `` lacks field `precision`."
+        );
+        assert_eq!(
+            check(
+                svstruct!(small => 0x1000000u32, medium => 0u8, large => 0u8, signed => 0u8, huge => 0u8, negative => 0u8),
+                "TestLib.Amounts"
+            )
+            .to_string(),
+            "number 16777216 at `.small` is out of the range of type `U24`."
+        );
+        assert_eq!(
+            check(
+                svstruct!(small => 0u8, medium => -1i8, large => 0u8, signed => 0u8, huge => 0u8, negative => 0u8),
+                "TestLib.Amounts"
+            )
+            .to_string(),
+            "number -1 at `.medium` is out of the range of type `U128`."
+        );
+        let err = check(
+            svstruct!(small => 0u8, medium => StrictVal::Number(StrictNum::from(1.5f64)), large => 0u8, signed => 0u8, huge => 0u8, negative => 0u8),
+            "TestLib.Amounts",
+        );
+        assert!(
+            matches!(&err, Error::TypeMismatch(path, _, Ty::Primitive(Primitive::U128)) if path.to_string() == ".medium"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn int_as_float() {
+        let (types, val) = typed(
+            svstruct!(brain => 1u8, half => 1u8, single => 1u8, double => -1i8, extended => 1u8, quad => 1u8, oct => 1u8),
+            "TestLib.Reading",
+        );
+        let reading = Reading {
+            brain: BFloat(half::bf16::ONE),
+            half: ieee::Half::from_str("1").unwrap(),
+            single: ieee::Single::from_str("1").unwrap(),
+            double: ieee::Double::from_str("-1").unwrap(),
+            extended: ieee::X87DoubleExtended::from_str("1").unwrap(),
+            quad: ieee::Quad::from_str("1").unwrap(),
+            oct: ieee::Oct::from_str("1").unwrap(),
+        };
+        let data = reading.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        let serialized = types.strict_serialize_type::<{ usize::MAX }>(&val).unwrap();
+        assert_eq!(serialized.0, data);
+        assert_eq!(&data[2..8], &[0x00, 0x3C, 0x00, 0x00, 0x80, 0x3F]);
+    }
+}
@@ -23,7 +23,11 @@
 //! Strict values: schema-less representation of strict types. The module includes:
 //! - [`path`]: path accessors/introspects into strict values;
 //! - [STON][ston]: strict type object notation, a JSON-like representation of strict types;
-//! - [`decode`]: conversion between strict encoding and strict values;
+//! - [`decode`]: conversion from strict encoding into strict values;
+//! - [`encode`]: conversion of strict values into strict encoding;
+//! - [`visit`]: streaming decoding of strict encoding with a visitor;
+//! - [`random`]: generation of random values valid for a strict type;
+//! - [`diff`]: structural comparison of strict values and patches;
 //! - [`typify`]: checks of strict values against strict type schema;
 //! - [`convert`]: conversion between strict values and other text representations (JSON, YAML,
 //!   TOML, etc).
@@ -34,17 +38,28 @@ mod path;
 pub mod ston;
 pub mod typify;
 pub mod decode;
+pub mod encode;
+pub mod visit;
+pub mod random;
+pub mod diff;
 #[cfg(feature = "serde")]
 pub mod convert;
-mod encode;
 
-pub use path::{KeyStep, Path, PathError, Step};
+pub use diff::{Change, Patch};
+pub use path::{KeyStep, Path, PathError, PathParseError, Step};
 pub use val::{EnumTag, StrictNum, StrictVal};
 
 #[cfg(test)]
-mod test_helpers {
-    use amplify::confinement::Confined;
-    use encoding::{Ident, StrictDeserialize, StrictSerialize};
+pub(crate) mod test_helpers {
+    use std::io;
+
+    use amplify::confinement::{Confined, TinyVec};
+    use amplify::num::apfloat::ieee;
+    use amplify::num::{i1024, i256, u24, u256, u512};
+    use encoding::{
+        DecodeError, Ident, Primitive, ReadRaw, StrictDecode, StrictDeserialize, StrictEncode,
+        StrictSerialize, StrictType, TypeName, TypedRead, TypedWrite, WriteRaw, LIB_EMBEDDED,
+    };
 
     use crate::stl::{std_stl, strict_types_stl};
     use crate::typesys::{SymbolicSys, SystemBuilder};
@@ -73,6 +88,97 @@ mod test_helpers {
     impl StrictSerialize for Nominal {}
     impl StrictDeserialize for Nominal {}
 
+    /// Brain float, which has no strict encoding in the embedded library.
+    #[derive(Copy, Clone, PartialEq, Debug, Default)]
+    pub struct BFloat(pub half::bf16);
+
+    impl StrictType for BFloat {
+        const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+        fn strict_name() -> Option<TypeName> { Some(tn!("F16b")) }
+    }
+
+    impl StrictEncode for BFloat {
+        fn strict_encode<W: TypedWrite>(&self, mut writer: W) -> io::Result<W> {
+            unsafe {
+                writer = writer.register_primitive(Primitive::F16B);
+                writer.raw_writer().write_raw_array(self.0.to_le_bytes())?;
+            }
+            Ok(writer)
+        }
+    }
+
+    impl StrictDecode for BFloat {
+        fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+            let buf = unsafe { reader.raw_reader().read_raw_array::<2>()? };
+            Ok(BFloat(half::bf16::from_le_bytes(buf)))
+        }
+    }
+
+    #[derive(Clone, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = "TestLib")]
+    pub struct Reading {
+        pub brain: BFloat,
+        pub half: ieee::Half,
+        pub single: ieee::Single,
+        pub double: ieee::Double,
+        pub extended: ieee::X87DoubleExtended,
+        pub quad: ieee::Quad,
+        pub oct: ieee::Oct,
+    }
+
+    impl StrictSerialize for Reading {}
+
+    #[derive(Clone, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = "TestLib")]
+    pub struct Amounts {
+        pub small: u24,
+        pub medium: u128,
+        pub large: u256,
+        pub signed: i256,
+        pub huge: u512,
+        pub negative: i1024,
+    }
+
+    impl StrictSerialize for Amounts {}
+
+    #[derive(Clone, Eq, PartialEq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = "TestLib")]
+    pub struct Tree {
+        pub value: u8,
+        pub children: TinyVec<Tree>,
+    }
+
+    impl StrictSerialize for Tree {}
+    impl StrictDeserialize for Tree {}
+
+    #[derive(Clone, Eq, PartialEq, Debug)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = "TestLib", tags = order)]
+    pub enum Expr {
+        Neg(Box<Expr>),
+        Sum(Box<Expr>, Box<Expr>),
+        Lit(u8),
+        Tree(Tree),
+    }
+
+    /// Byte string with a length prefix wider than 32 bits.
+    #[derive(Clone, Eq, PartialEq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = "TestLib")]
+    pub struct Blob(pub Confined<Vec<u8>, 0, { usize::MAX }>);
+
+    impl StrictSerialize for Blob {}
+
+    impl Default for Expr {
+        fn default() -> Self { Expr::Neg(Box::new(Expr::Lit(0))) }
+    }
+
+    impl StrictSerialize for Expr {}
+    impl StrictDeserialize for Expr {}
+
     impl Nominal {
         pub fn with(ticker: &'static str, name: &'static str, precision: u8) -> Self {
             Nominal {
@@ -88,6 +194,10 @@ mod test_helpers {
         let st = strict_types_stl();
         let lib = LibBuilder::new("TestLib", [std.to_dependency(), st.to_dependency()])
             .transpile::<Nominal>()
+            .transpile::<Reading>()
+            .transpile::<Amounts>()
+            .transpile::<Expr>()
+            .transpile::<Blob>()
             .compile()
             .unwrap();
         SystemBuilder::new()
@@ -22,10 +22,13 @@
 
 //! Path accessors into strict values.
 
-use std::fmt::{self, Display, Formatter};
+use std::fmt::{self, Display, Formatter, Write};
+use std::mem;
+use std::str::FromStr;
 
 use amplify::confinement::{SmallVec, TinyBlob, TinyString};
-use encoding::{FieldName, STRICT_TYPES_LIB};
+use amplify::hex::FromHex;
+use encoding::{FieldName, InvalidRString, STRICT_TYPES_LIB};
 
 use crate::value::{EnumTag, StrictNum};
 use crate::StrictVal;
@@ -52,6 +55,27 @@ pub enum KeyStep {
 }
 
 impl KeyStep {
+    /// Constructs step addressing a map key, if the key value can be
+    /// represented as a step.
+    pub fn with_key(key: &StrictVal) -> Option<KeyStep> {
+        Some(match key {
+            StrictVal::Number(StrictNum::Uint(num)) => KeyStep::Number(*num),
+            StrictVal::Enum(EnumTag::Ord(tag)) => KeyStep::Number(*tag as u128),
+            StrictVal::Bytes(blob) => KeyStep::TinyBlob(TinyBlob::try_from(blob.clone()).ok()?),
+            StrictVal::String(s) => KeyStep::TinyString(TinyString::try_from(s.clone()).ok()?),
+            _ => return None,
+        })
+    }
+
+    /// Constructs strict value for a map key matching this step.
+    pub fn to_strict_val(&self) -> StrictVal {
+        match self {
+            KeyStep::Number(num) => StrictVal::num(*num),
+            KeyStep::TinyBlob(blob) => StrictVal::bytes(blob),
+            KeyStep::TinyString(s) => StrictVal::str(s),
+        }
+    }
+
     pub fn has_match(&self, val: &StrictVal) -> bool {
         match (self, val) {
             (KeyStep::Number(no), StrictVal::Enum(EnumTag::Ord(tag))) if *tag as u128 == *no => {
@@ -83,8 +107,14 @@ impl Display for KeyStep {
                 Ok(())
             }
             KeyStep::TinyString(s) => {
-                let s = s.replace('"', "\\\"");
-                f.write_str(&s)
+                f.write_char('"')?;
+                for c in s.chars() {
+                    if c == '"' || c == '\\' {
+                        f.write_char('\\')?;
+                    }
+                    f.write_char(c)?;
+                }
+                f.write_char('"')
             }
         }
     }
@@ -132,7 +162,7 @@ impl Path {
 
     pub fn with(step: Step) -> Path { Path(small_vec!(step)) }
 
-    pub fn iter(&self) -> std::slice::Iter<Step> { self.0.iter() }
+    pub fn iter(&self) -> std::slice::Iter<'_, Step> { self.0.iter() }
 }
 
 impl<'path> IntoIterator for &'path Path {
@@ -151,7 +181,182 @@ impl Display for Path {
     }
 }
 
-// TODO: Implement FromStr for value Path expressions
+/// Tracks path to the value which is being processed while traversing nested
+/// values, for error reporting and change addressing.
+pub(super) struct Location(Vec<Step>);
+
+impl Location {
+    pub fn new() -> Self { Location(vec![]) }
+
+    pub fn path(&self) -> Path {
+        let steps = SmallVec::try_from(self.0.clone()).expect("value nesting exceeds u16::MAX");
+        Path::from(steps)
+    }
+
+    pub fn nested<T>(&mut self, step: Step, f: impl FnOnce(&mut Self) -> T) -> T {
+        self.0.push(step);
+        let res = f(self);
+        self.0.pop();
+        res
+    }
+}
+
+/// Errors parsing value path expression.
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum PathParseError {
+    /// unexpected end of the path expression.
+    UnexpectedEnd,
+
+    /// unexpected character `{1}` at position {0}.
+    UnexpectedChar(usize, char),
+
+    /// invalid field name at position {0}: {1}
+    InvalidFieldName(usize, InvalidRString),
+
+    /// number at position {0} is out of the allowed range.
+    NumberOverflow(usize),
+
+    /// invalid hexadecimal blob at position {0}.
+    InvalidHex(usize),
+
+    /// key at position {0} exceeds 255 bytes.
+    KeyTooLong(usize),
+
+    /// path has too many steps.
+    TooLong,
+}
+
+impl FromStr for Path {
+    type Err = PathParseError;
+
+    /// Parses path expressions in the same format as produced by the path
+    /// `Display`, like `.field.0[3]{"key"}{0hDEAD}{42}`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = PathParser { s, pos: 0 };
+        let mut path = Path::new();
+        while let Some(step) = parser.step()? {
+            path.push(step).map_err(|_| PathParseError::TooLong)?;
+        }
+        Ok(path)
+    }
+}
+
+/// Parses path expression from the beginning of the string, returning the
+/// rest of the string starting with the first character which can't start a
+/// path step.
+pub(super) fn parse_path_prefix(s: &str) -> Result<(Path, &str), PathParseError> {
+    let mut parser = PathParser { s, pos: 0 };
+    let mut path = Path::new();
+    while matches!(parser.peek(), Some('.' | '[' | '{')) {
+        let step = parser.step()?.expect("step start is checked");
+        path.push(step).map_err(|_| PathParseError::TooLong)?;
+    }
+    Ok((path, &s[parser.pos..]))
+}
+
+struct PathParser<'s> {
+    s: &'s str,
+    pos: usize,
+}
+
+impl<'s> PathParser<'s> {
+    fn peek(&self) -> Option<char> { self.s[self.pos..].chars().next() }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), PathParseError> {
+        match self.bump() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(PathParseError::UnexpectedChar(self.pos - c.len_utf8(), c)),
+            None => Err(PathParseError::UnexpectedEnd),
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'s str {
+        let start = self.pos;
+        while self.peek().is_some_and(&f) {
+            self.bump();
+        }
+        &self.s[start..self.pos]
+    }
+
+    fn number<T: FromStr>(&mut self) -> Result<T, PathParseError> {
+        let start = self.pos;
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        if digits.is_empty() {
+            return match self.peek() {
+                Some(c) => Err(PathParseError::UnexpectedChar(start, c)),
+                None => Err(PathParseError::UnexpectedEnd),
+            };
+        }
+        digits.parse().map_err(|_| PathParseError::NumberOverflow(start))
+    }
+
+    fn step(&mut self) -> Result<Option<Step>, PathParseError> {
+        let start = self.pos;
+        let step = match self.bump() {
+            None => return Ok(None),
+            Some('.') if self.peek().is_some_and(|c| c.is_ascii_digit()) => {
+                Step::UnnamedField(self.number()?)
+            }
+            Some('.') => {
+                let start = self.pos;
+                let name = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+                let name = FieldName::from_str(name)
+                    .map_err(|err| PathParseError::InvalidFieldName(start, err))?;
+                Step::NamedField(name)
+            }
+            Some('[') => {
+                let idx = self.number()?;
+                self.expect(']')?;
+                Step::Index(idx)
+            }
+            Some('{') => {
+                let key = self.key()?;
+                self.expect('}')?;
+                Step::Key(key)
+            }
+            Some(c) => return Err(PathParseError::UnexpectedChar(start, c)),
+        };
+        Ok(Some(step))
+    }
+
+    fn key(&mut self) -> Result<KeyStep, PathParseError> {
+        let start = self.pos;
+        if self.s[self.pos..].starts_with("0h") {
+            self.pos += 2;
+            let hex = self.take_while(|c| c.is_ascii_hexdigit());
+            let blob = Vec::<u8>::from_hex(hex).map_err(|_| PathParseError::InvalidHex(start))?;
+            let blob = TinyBlob::try_from(blob).map_err(|_| PathParseError::KeyTooLong(start))?;
+            return Ok(KeyStep::TinyBlob(blob));
+        }
+        let s = match self.peek() {
+            None => return Err(PathParseError::UnexpectedEnd),
+            Some(c) if c.is_ascii_digit() => return self.number().map(KeyStep::Number),
+            Some('"') => {
+                self.bump();
+                let mut s = String::new();
+                loop {
+                    match self.bump().ok_or(PathParseError::UnexpectedEnd)? {
+                        '"' => break s,
+                        '\\' => s.push(self.bump().ok_or(PathParseError::UnexpectedEnd)?),
+                        c => s.push(c),
+                    }
+                }
+            }
+            // Unquoted string keys
+            Some(_) => self.take_while(|c| c != '}').to_owned(),
+        };
+        TinyString::try_from(s)
+            .map(KeyStep::TinyString)
+            .map_err(|_| PathParseError::KeyTooLong(start))
+    }
+}
 
 #[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
 #[display(doc_comments)]
@@ -166,40 +371,218 @@ pub enum PathError {
     UnknownKey(KeyStep),
     /// path doesn't match value at step {0}.
     TypeMismatch(Step, StrictVal),
+    /// path must contain at least one step.
+    EmptyPath,
+    /// tuple field {0} can't be removed.
+    TupleFieldRemoval(u8),
 }
 
 impl StrictVal {
+    fn at_step(&self, step: &Step) -> Result<&StrictVal, PathError> {
+        match (self, step) {
+            (StrictVal::Tuple(fields), Step::UnnamedField(no)) => {
+                fields.get(*no as usize).ok_or(PathError::FieldNoOutOfBounds(*no, fields.len()))
+            }
+            (StrictVal::Struct(fields), Step::NamedField(name)) => {
+                fields.get(name).ok_or(PathError::UnknownFieldName(name.clone()))
+            }
+            (StrictVal::List(items) | StrictVal::Set(items), Step::Index(idx)) => items
+                .get(*idx as usize)
+                .ok_or(PathError::CollectionIndexOutOfBounds(*idx, items.len())),
+            (StrictVal::Map(items), Step::Key(idx)) => items
+                .iter()
+                .find(|(key, _)| idx.has_match(key))
+                .map(|(_, val)| val)
+                .ok_or(PathError::UnknownKey(idx.clone())),
+            (_, step) => Err(PathError::TypeMismatch(step.clone(), self.clone())),
+        }
+    }
+
+    fn at_step_mut(&mut self, step: &Step) -> Result<&mut StrictVal, PathError> {
+        match (self, step) {
+            (StrictVal::Tuple(fields), Step::UnnamedField(no)) => {
+                let len = fields.len();
+                fields.get_mut(*no as usize).ok_or(PathError::FieldNoOutOfBounds(*no, len))
+            }
+            (StrictVal::Struct(fields), Step::NamedField(name)) => {
+                fields.get_mut(name).ok_or(PathError::UnknownFieldName(name.clone()))
+            }
+            (StrictVal::List(items) | StrictVal::Set(items), Step::Index(idx)) => {
+                let len = items.len();
+                items.get_mut(*idx as usize).ok_or(PathError::CollectionIndexOutOfBounds(*idx, len))
+            }
+            (StrictVal::Map(items), Step::Key(idx)) => items
+                .iter_mut()
+                .find(|(key, _)| idx.has_match(key))
+                .map(|(_, val)| val)
+                .ok_or(PathError::UnknownKey(idx.clone())),
+            (val, step) => Err(PathError::TypeMismatch(step.clone(), val.clone())),
+        }
+    }
+
     pub fn at_path<'p>(
         &self,
         path: impl IntoIterator<Item = &'p Step>,
     ) -> Result<&StrictVal, PathError> {
-        let mut iter = path.into_iter();
-        match (self, iter.next()) {
-            (val, None) => Ok(val),
-            (StrictVal::Tuple(fields), Some(Step::UnnamedField(no)))
-                if *no as usize >= fields.len() =>
-            {
-                Err(PathError::FieldNoOutOfBounds(*no, fields.len()))
+        path.into_iter().try_fold(self, |val, step| val.at_step(step))
+    }
+
+    pub fn at_path_mut<'p>(
+        &mut self,
+        path: impl IntoIterator<Item = &'p Step>,
+    ) -> Result<&mut StrictVal, PathError> {
+        path.into_iter().try_fold(self, |val, step| val.at_step_mut(step))
+    }
+
+    /// Replaces value at the given path, returning the previous value.
+    ///
+    /// If the last step of the path addresses a structure field or a map key
+    /// which doesn't exist, it is added and `None` is returned.
+    pub fn set_at_path(
+        &mut self,
+        path: &Path,
+        val: StrictVal,
+    ) -> Result<Option<StrictVal>, PathError> {
+        let Some((last, parent)) = path.split_last() else {
+            return Ok(Some(mem::replace(self, val)));
+        };
+        match (self.at_path_mut(parent)?, last) {
+            (StrictVal::Struct(fields), Step::NamedField(name)) => {
+                Ok(fields.insert(name.clone(), val))
             }
-            (StrictVal::Tuple(fields), Some(Step::UnnamedField(no))) => Ok(&fields[*no as usize]),
-            (StrictVal::Struct(fields), Some(Step::NamedField(name))) => {
-                fields.get(name).ok_or(PathError::UnknownFieldName(name.clone()))
+            (StrictVal::Map(items), Step::Key(idx)) => {
+                match items.iter_mut().find(|(key, _)| idx.has_match(key)) {
+                    Some((_, old)) => Ok(Some(mem::replace(old, val))),
+                    None => {
+                        items.push((idx.to_strict_val(), val));
+                        Ok(None)
+                    }
+                }
             }
-            (StrictVal::List(items) | StrictVal::Set(items), Some(Step::Index(idx)))
-                if *idx as usize >= items.len() =>
+            (parent, step) => parent.at_step_mut(step).map(|old| Some(mem::replace(old, val))),
+        }
+    }
+
+    /// Removes value at the given path from its parent structure, collection
+    /// or map, returning the removed value.
+    pub fn remove_at_path(&mut self, path: &Path) -> Result<StrictVal, PathError> {
+        let Some((last, parent)) = path.split_last() else {
+            return Err(PathError::EmptyPath);
+        };
+        match (self.at_path_mut(parent)?, last) {
+            (StrictVal::Struct(fields), Step::NamedField(name)) => {
+                fields.shift_remove(name).ok_or(PathError::UnknownFieldName(name.clone()))
+            }
+            (StrictVal::List(items) | StrictVal::Set(items), Step::Index(idx))
+                if (*idx as usize) < items.len() =>
             {
-                Err(PathError::CollectionIndexOutOfBounds(*idx, items.len()))
+                Ok(items.remove(*idx as usize))
             }
-            (StrictVal::List(items) | StrictVal::Set(items), Some(Step::Index(idx))) => {
-                Ok(&items[*idx as usize])
+            (StrictVal::List(items) | StrictVal::Set(items), Step::Index(idx)) => {
+                Err(PathError::CollectionIndexOutOfBounds(*idx, items.len()))
             }
-            (StrictVal::Map(items), Some(Step::Key(idx))) => items
+            (StrictVal::Map(items), Step::Key(idx)) => items
                 .iter()
-                .find(|(key, _)| idx.has_match(key))
-                .map(|(_, val)| val)
+                .position(|(key, _)| idx.has_match(key))
+                .map(|pos| items.remove(pos).1)
                 .ok_or(PathError::UnknownKey(idx.clone())),
+            (StrictVal::Tuple(_), Step::UnnamedField(no)) => Err(PathError::TupleFieldRemoval(*no)),
+            (val, step) => Err(PathError::TypeMismatch(step.clone(), val.clone())),
+        }
+    }
+}
 
-            (_, Some(step)) => Err(PathError::TypeMismatch(step.clone(), self.clone())),
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sample() -> StrictVal {
+        svstruct!(
+            name => "Some name",
+            pair => svtuple!([sv!(1u8), svlist!([10u8, 20u8, 30u8])]),
+            dict => StrictVal::map([
+                (svstr!("key \"q\""), sv!(1u8)),
+                (svbytes!([0xde, 0xad]), sv!(2u8)),
+                (svnum!(7u8), sv!(3u8))
+            ])
+        )
+    }
+
+    #[test]
+    fn parse() {
+        for s in [
+            "",
+            ".name",
+            ".pair.1[2]",
+            r#".dict{"key \"q\""}"#,
+            ".dict{0hDEAD}",
+            ".dict{7}",
+            r#"{"a\\b"}[0].x_1"#,
+        ] {
+            let path = Path::from_str(s).unwrap();
+            assert_eq!(path.to_string(), s);
         }
+        assert_eq!(
+            Path::from_str(".dict{key}").unwrap(),
+            Path::from_str(r#".dict{"key"}"#).unwrap()
+        );
+        assert_eq!(Path::from_str(".pair.1[").unwrap_err(), PathParseError::UnexpectedEnd);
+        assert_eq!(Path::from_str(".pair.256").unwrap_err(), PathParseError::NumberOverflow(6));
+        assert_eq!(Path::from_str("[1)").unwrap_err(), PathParseError::UnexpectedChar(2, ')'));
+        assert_eq!(Path::from_str("{0hABC}").unwrap_err(), PathParseError::InvalidHex(1));
+        assert!(matches!(
+            Path::from_str(".Name").unwrap_err(),
+            PathParseError::InvalidFieldName(1, _)
+        ));
+    }
+
+    #[test]
+    fn access() {
+        let val = sample();
+        let at = |s: &str| val.at_path(&Path::from_str(s).unwrap()).cloned();
+        assert_eq!(at(".pair.1[2]").unwrap(), sv!(30u8));
+        assert_eq!(at(r#".dict{"key \"q\""}"#).unwrap(), sv!(1u8));
+        assert_eq!(at(".dict{0hDEAD}").unwrap(), sv!(2u8));
+        assert_eq!(at(".dict{7}").unwrap(), sv!(3u8));
+        assert_eq!(at(".pair.1[3]").unwrap_err(), PathError::CollectionIndexOutOfBounds(3, 3));
+        assert_eq!(at(".pair.2").unwrap_err(), PathError::FieldNoOutOfBounds(2, 2));
+        assert_eq!(at(".other").unwrap_err(), PathError::UnknownFieldName(fname!("other")));
+        assert!(matches!(at(".name[0]").unwrap_err(), PathError::TypeMismatch(Step::Index(0), _)));
+    }
+
+    #[test]
+    fn modify() {
+        let mut val = sample();
+        let path = |s: &str| Path::from_str(s).unwrap();
+
+        *val.at_path_mut(&path(".pair.0")).unwrap() = sv!(2u8);
+        assert_eq!(val.at_path(&path(".pair.0")).unwrap(), &sv!(2u8));
+
+        assert_eq!(val.set_at_path(&path(".pair.1[0]"), sv!(11u8)).unwrap(), Some(sv!(10u8)));
+        assert_eq!(val.set_at_path(&path(".dict{new}"), sv!(4u8)).unwrap(), None);
+        assert_eq!(val.at_path(&path(r#".dict{"new"}"#)).unwrap(), &sv!(4u8));
+        assert_eq!(val.set_at_path(&path(".extra"), sv!(())).unwrap(), None);
+        assert_eq!(
+            val.set_at_path(&path(".pair.1[5]"), sv!(0u8)).unwrap_err(),
+            PathError::CollectionIndexOutOfBounds(5, 3)
+        );
+
+        assert_eq!(val.remove_at_path(&path(".pair.1[1]")).unwrap(), sv!(20u8));
+        assert_eq!(val.at_path(&path(".pair.1")).unwrap(), &svlist!([11u8, 30u8]));
+        assert_eq!(val.remove_at_path(&path(".dict{0hDEAD}")).unwrap(), sv!(2u8));
+        assert_eq!(val.remove_at_path(&path(".extra")).unwrap(), StrictVal::Unit);
+        assert_eq!(
+            val.remove_at_path(&path(".pair.0")).unwrap_err(),
+            PathError::TupleFieldRemoval(0)
+        );
+        assert_eq!(val.remove_at_path(&path("")).unwrap_err(), PathError::EmptyPath);
+        assert_eq!(
+            val.remove_at_path(&path(".dict{0hDEAD}")).unwrap_err(),
+            PathError::UnknownKey(KeyStep::TinyBlob(TinyBlob::try_from(vec![0xde, 0xad]).unwrap()))
+        );
+
+        let old = val.clone();
+        assert_eq!(val.set_at_path(&path(""), sv!(1u8)).unwrap(), Some(old));
+        assert_eq!(val, sv!(1u8));
     }
 }
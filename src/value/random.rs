@@ -0,0 +1,469 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of random values, which are valid for a given strict type.
+//!
+//! The generator is deterministic: the same seed, size budget and type always
+//! produce the same value. The size budget limits the total number of
+//! collection items, string characters and bytes added on top of the minimal
+//! number required by the type [`Sizing`] constraints.
+//!
+//! The same generator also constructs minimal ("dumb") values with
+//! [`TypeSystem::dumb_value`].
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use encoding::{NumCls, Primitive, Sizing};
+use indexmap::IndexMap;
+
+use super::decode;
+use super::visit::Layout;
+use crate::{SemId, StrictVal, Ty, TypeSystem};
+
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum Error {
+    #[display(inner)]
+    #[from]
+    #[from(crate::typesys::UnknownType)]
+    Decode(decode::Error),
+
+    /// type {0} doesn't have enough distinct values to fill a collection of
+    /// {1} unique items.
+    NotUnique(SemId, usize),
+}
+
+/// Deterministic pseudo-random number generator (SplitMix64).
+#[derive(Clone, Eq, PartialEq, Debug)]
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random number in range `0..=max`.
+    fn up_to(&mut self, max: u64) -> u64 {
+        match max.checked_add(1) {
+            Some(bound) => self.next_u64() % bound,
+            None => self.next_u64(),
+        }
+    }
+
+    fn bytes(&mut self, len: usize) -> Vec<u8> { (0..len).map(|_| self.next_u64() as u8).collect() }
+
+    fn char(&mut self) -> char {
+        loop {
+            if let Some(c) = char::from_u32(self.up_to(char::MAX as u64) as u32) {
+                return c;
+            }
+        }
+    }
+}
+
+/// Types of a system which may be nested into themselves.
+#[derive(Clone, Debug)]
+struct Recursion {
+    /// Minimal depth of the type values.
+    depths: BTreeMap<SemId, usize>,
+    /// Types which are members of some recursion group.
+    recursive: BTreeSet<SemId>,
+}
+
+/// Generator of random values valid for types of a type system.
+#[derive(Clone, Debug)]
+pub struct ValueGen<'sys> {
+    sys: &'sys TypeSystem,
+    rng: Rng,
+    budget: usize,
+    dumb: bool,
+    /// Analysis of the recursive types, performed once the first union is met.
+    recursion: Option<Recursion>,
+}
+
+impl<'sys> ValueGen<'sys> {
+    pub const DEFAULT_BUDGET: usize = 256;
+
+    pub fn new(sys: &'sys TypeSystem, seed: u64) -> Self {
+        ValueGen {
+            sys,
+            rng: Rng(seed),
+            budget: Self::DEFAULT_BUDGET,
+            dumb: false,
+            recursion: None,
+        }
+    }
+
+    /// Constructs generator of minimal values instead of random ones.
+    fn dumb(sys: &'sys TypeSystem) -> Self {
+        ValueGen {
+            sys,
+            rng: Rng(0),
+            budget: 0,
+            dumb: true,
+            recursion: None,
+        }
+    }
+
+    /// Sets the size budget of the generated values. With zero budget all
+    /// collections and strings have the minimal allowed length.
+    pub fn budget(mut self, budget: usize) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Generates a random value of the type `sem_id`. Each subsequent call
+    /// produces a new value, spending the remaining size budget.
+    pub fn generate(&mut self, sem_id: SemId) -> Result<StrictVal, Error> {
+        Ok(match self.sys.data_layout(sem_id)? {
+            Layout::Scalar(ty) => self.scalar(sem_id, ty)?,
+            Layout::Ascii(first, rest, sizing) => {
+                let len = self.len(&sizing);
+                let mut s = String::with_capacity(len);
+                for pos in 0..len {
+                    s.push(self.ascii_char(if pos == 0 { first } else { rest })?);
+                }
+                StrictVal::String(s)
+            }
+            Layout::Unicode(sizing) => {
+                let len = self.len(&sizing);
+                StrictVal::String(self.unicode(len))
+            }
+            Layout::FixedUnicode(len) => StrictVal::String(self.unicode(len)),
+            Layout::Bytes(sizing) => {
+                let len = self.len(&sizing);
+                StrictVal::Bytes(self.bytes(len))
+            }
+            Layout::FixedBytes(len) => StrictVal::Bytes(self.bytes(len)),
+            Layout::Union(variants) => {
+                let tys = variants.values().copied().collect::<Vec<_>>();
+                let pos = self.pick_variant(sem_id, &tys);
+                let (variant, ty) = variants.iter().nth(pos).expect("position within the range");
+                StrictVal::union(variant.tag, self.generate(*ty)?)
+            }
+            Layout::Tuple(fields) => StrictVal::Tuple(
+                fields.iter().map(|ty| self.generate(*ty)).collect::<Result<_, _>>()?,
+            ),
+            Layout::Struct(fields) => {
+                let mut vals = IndexMap::with_capacity(fields.len());
+                for field in fields {
+                    vals.insert(field.name.clone(), self.generate(field.ty)?);
+                }
+                StrictVal::Struct(vals)
+            }
+            Layout::Array(ty, len) => {
+                StrictVal::List((0..len).map(|_| self.generate(ty)).collect::<Result<_, _>>()?)
+            }
+            Layout::List(ty, sizing) => {
+                let len = self.len(&sizing);
+                StrictVal::List((0..len).map(|_| self.generate(ty)).collect::<Result<_, _>>()?)
+            }
+            Layout::Set(ty, sizing) => {
+                let len = self.len(&sizing);
+                let items =
+                    self.unique(ty, len, sizing.min as usize, |gen| Ok((gen.generate(ty)?, ())))?;
+                StrictVal::Set(items.into_iter().map(|(item, _)| item).collect())
+            }
+            Layout::Map(key_ty, ty, sizing) => {
+                let len = self.len(&sizing);
+                let entries = self.unique(key_ty, len, sizing.min as usize, |gen| {
+                    Ok((gen.generate(key_ty)?, gen.generate(ty)?))
+                })?;
+                StrictVal::Map(entries)
+            }
+        })
+    }
+
+    fn scalar(&mut self, sem_id: SemId, ty: &Ty<SemId>) -> Result<StrictVal, Error> {
+        Ok(match ty {
+            Ty::Primitive(prim) if *prim == Primitive::UNIT => StrictVal::Unit,
+            Ty::Primitive(prim) => {
+                let mut bytes = self.bytes(prim.byte_size() as usize);
+                if prim.info().ty == NumCls::NonZero && bytes.iter().all(|b| *b == 0) {
+                    bytes[0] = 1;
+                }
+                // Reading the value back from its binary representation ensures we produce
+                // exactly the same value form as the decoder does.
+                self.sys.strict_deserialize_type(sem_id, &bytes)?.val
+            }
+            Ty::Enum(variants) => {
+                let pos = self.pick(variants.len());
+                StrictVal::enumer(variants.iter().nth(pos).expect("position within the range").tag)
+            }
+            Ty::UnicodeChar => StrictVal::String(self.char().to_string()),
+            _ => unreachable!("non-scalar type"),
+        })
+    }
+
+    fn pick(&mut self, count: usize) -> usize {
+        if self.dumb {
+            return 0;
+        }
+        self.rng.up_to(count as u64 - 1) as usize
+    }
+
+    /// Picks a union variant. Variants of recursive unions which don't lead to the shallowest
+    /// values are picked only while there is some size budget, spending it, and never for the
+    /// minimal values. This guarantees that the generation of recursive values terminates.
+    fn pick_variant(&mut self, sem_id: SemId, variants: &[SemId]) -> usize {
+        let sys = self.sys;
+        let recursion = self.recursion.get_or_insert_with(|| Recursion {
+            depths: sys.value_depths(),
+            recursive: sys.recursion_groups().into_iter().flatten().collect(),
+        });
+        if !recursion.recursive.contains(&sem_id) {
+            return self.pick(variants.len());
+        }
+        let depths = variants
+            .iter()
+            .map(|ty| recursion.depths.get(ty).copied().unwrap_or(usize::MAX))
+            .collect::<Vec<_>>();
+        let min = depths.iter().copied().min().unwrap_or_default();
+        let shallowest = depths.iter().position(|depth| *depth == min).unwrap_or_default();
+        if self.dumb || self.budget == 0 {
+            return shallowest;
+        }
+        let pos = self.pick(variants.len());
+        if depths[pos] > min {
+            self.budget -= 1;
+        }
+        pos
+    }
+
+    fn bytes(&mut self, len: usize) -> Vec<u8> {
+        if self.dumb {
+            return vec![0; len];
+        }
+        self.rng.bytes(len)
+    }
+
+    fn char(&mut self) -> char {
+        if self.dumb {
+            return 'a';
+        }
+        self.rng.char()
+    }
+
+    /// Picks a length for a collection, spending the size budget on the items
+    /// above the minimal number.
+    fn len(&mut self, sizing: &Sizing) -> usize {
+        let extra = (sizing.max - sizing.min).min(self.budget as u64);
+        let extra = self.rng.up_to(extra) as usize;
+        self.budget -= extra;
+        sizing.min as usize + extra
+    }
+
+    fn ascii_char(&mut self, char_id: SemId) -> Result<char, Error> {
+        let ty = self.sys.find(char_id).ok_or_else(|| decode::Error::TypeAbsent(char_id.into()))?;
+        let Ty::Enum(variants) = ty else {
+            unreachable!("ASCII strings are always made of character enums")
+        };
+        let pos = self.pick(variants.len());
+        Ok(variants.iter().nth(pos).expect("position within the range").tag as char)
+    }
+
+    /// Generates a unicode string which has exactly `len` bytes.
+    fn unicode(&mut self, len: usize) -> String {
+        let mut s = String::with_capacity(len);
+        while s.len() < len {
+            let mut c = self.char();
+            if c.len_utf8() > len - s.len() {
+                c = (b' ' + self.rng.up_to(94) as u8) as char;
+            }
+            s.push(c);
+        }
+        s
+    }
+
+    /// Generates up to `len` entries with distinct keys, giving up after a
+    /// number of attempts to produce a new unique key. Fails if less than `min`
+    /// entries were generated.
+    fn unique<T>(
+        &mut self,
+        ty: SemId,
+        len: usize,
+        min: usize,
+        mut gen: impl FnMut(&mut Self) -> Result<(StrictVal, T), Error>,
+    ) -> Result<Vec<(StrictVal, T)>, Error> {
+        let mut entries = Vec::<(StrictVal, T)>::with_capacity(len);
+        let mut attempts = 0usize;
+        let dumb = self.dumb;
+        while entries.len() < len && attempts < len * 4 + 16 {
+            // Minimal value may be used only once, the rest of the keys have to be random
+            self.dumb = dumb && entries.is_empty();
+            let res = gen(self);
+            self.dumb = dumb;
+            let (key, val) = res?;
+            if entries.iter().all(|(k, _)| k != &key) {
+                entries.push((key, val));
+            } else {
+                attempts += 1;
+            }
+        }
+        if entries.len() < min {
+            return Err(Error::NotUnique(ty, min));
+        }
+        Ok(entries)
+    }
+}
+
+impl TypeSystem {
+    /// Constructs the minimal valid value of type `sem_id`: zero numbers (or one
+    /// for the non-zero ones), first variants of enums and unions, collections
+    /// of the minimal length filled with the minimal values and strings made of
+    /// the first character of their charset (`a` for unicode strings). For the
+    /// recursive unions the first of the variants having the shallowest values
+    /// is used.
+    ///
+    /// Since sets and maps can't contain repeated items, the items following
+    /// the first one are generated randomly, with a fixed seed.
+    pub fn dumb_value(&self, sem_id: SemId) -> Result<StrictVal, Error> {
+        ValueGen::dumb(self).generate(sem_id)
+    }
+
+    /// Generates a random value of type `sem_id` from the `seed`, using the
+    /// default size budget.
+    pub fn random_val(&self, sem_id: SemId, seed: u64) -> Result<StrictVal, Error> {
+        ValueGen::new(self, seed).generate(sem_id)
+    }
+
+    /// Constructs [`proptest`] strategy generating values of type `sem_id`.
+    ///
+    /// The strategy shrinks generated values by reducing their size budget,
+    /// down to the values with the minimal size of all collections.
+    #[cfg(feature = "proptest")]
+    pub fn val_strategy(
+        &self,
+        sem_id: SemId,
+        budget: usize,
+    ) -> impl proptest::strategy::Strategy<Value = StrictVal> + '_ {
+        use proptest::prelude::*;
+
+        (any::<u64>(), 0..=budget)
+            .prop_filter_map("type has not enough distinct values", move |(seed, budget)| {
+                ValueGen::new(self, seed).budget(budget).generate(sem_id).ok()
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::stl::{std_stl, strict_types_stl};
+    use crate::typesys::{SymbolicSys, SystemBuilder};
+
+    fn stl_system() -> SymbolicSys {
+        SystemBuilder::new()
+            .import(std_stl())
+            .unwrap()
+            .import(strict_types_stl())
+            .unwrap()
+            .finalize()
+            .unwrap()
+    }
+
+    fn roundtrip(sys: &TypeSystem, sem_id: SemId, val: StrictVal) {
+        let typed = sys.typify(val.clone(), sem_id).unwrap();
+        let mut data = Vec::new();
+        sys.strict_write_type(&typed, &mut data).unwrap();
+        let decoded = sys.strict_deserialize_type(sem_id, &data).unwrap();
+        assert_eq!(decoded.val, val, "type {sem_id}");
+    }
+
+    #[test]
+    fn deterministic() {
+        let sys = stl_system();
+        let sem_id = sys.to_sem_id("StrictTypes.TypeLib").unwrap();
+        let types = sys.as_types();
+        assert_eq!(types.random_val(sem_id, 42).unwrap(), types.random_val(sem_id, 42).unwrap());
+        assert_ne!(types.random_val(sem_id, 42).unwrap(), types.random_val(sem_id, 43).unwrap());
+
+        let val = ValueGen::new(types, 42).budget(0).generate(sem_id).unwrap();
+        assert_eq!(val.unwrap_struct("dependencies"), &StrictVal::Set(vec![]));
+    }
+
+    #[test]
+    fn dumb() {
+        let sys = crate::value::test_helpers::test_system();
+        let nominal = sys.to_sem_id("TestLib.Nominal").unwrap();
+        assert_eq!(
+            sys.as_types().dumb_value(nominal).unwrap(),
+            svstruct!(ticker => svnewtype!("A"), name => "a", precision => svenum!(0))
+        );
+        let amounts = sys.to_sem_id("TestLib.Amounts").unwrap();
+        let val = sys.as_types().dumb_value(amounts).unwrap();
+        assert_eq!(val.unwrap_struct("small"), &svnum!(0u8));
+        let expr = sys.to_sem_id("TestLib.Expr").unwrap();
+        assert_eq!(sys.as_types().dumb_value(expr).unwrap(), svunion!(2 => svnewtype!(0u8)));
+
+        let sys = stl_system();
+        let types = sys.as_types();
+        for sem_id in types.as_inner().keys() {
+            roundtrip(types, *sem_id, types.dumb_value(*sem_id).unwrap());
+        }
+    }
+
+    #[test]
+    fn all_types() {
+        let sys = stl_system();
+        let types = sys.as_types();
+        for sem_id in types.as_inner().keys() {
+            for seed in 0..4 {
+                let val = ValueGen::new(types, seed).budget(32).generate(*sem_id).unwrap();
+                roundtrip(types, *sem_id, val);
+            }
+        }
+    }
+
+    #[test]
+    fn recursive() {
+        let sys = crate::value::test_helpers::test_system();
+        let types = sys.as_types();
+        for name in ["TestLib.Expr", "TestLib.Tree"] {
+            let sem_id = sys.to_sem_id(name).unwrap();
+            for seed in 0..16 {
+                let val = ValueGen::new(types, seed).budget(32).generate(sem_id).unwrap();
+                roundtrip(types, sem_id, val);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "proptest")]
+    fn strategy() {
+        use proptest::test_runner::TestRunner;
+
+        let sys = stl_system();
+        let sem_id = sys.to_sem_id("StrictTypes.TypeLib").unwrap();
+        let types = sys.as_types();
+        TestRunner::default()
+            .run(&types.val_strategy(sem_id, 64), |val| {
+                roundtrip(types, sem_id, val);
+                Ok(())
+            })
+            .unwrap();
+    }
+}
@@ -22,12 +22,17 @@
 
 //! Strict type object notation (STON): JSON-like text serialization for stric types.
 
-use std::fmt::{self, Display, Formatter};
+use std::fmt::{self, Display, Formatter, Write};
+use std::str::FromStr;
 
-use amplify::hex::ToHex;
+use amplify::hex::{FromHex, ToHex};
+use amplify::num::apfloat::{ieee, Float};
+use amplify::num::{i1024, u1024};
+use encoding::{FieldName, InvalidRString, VariantName};
+use indexmap::IndexMap;
 
 use super::StrictVal;
-use crate::value::EnumTag;
+use crate::value::{EnumTag, StrictNum};
 
 impl StrictVal {
     fn needs_braces(&self) -> bool {
@@ -44,7 +49,22 @@ impl Display for StrictVal {
         match self {
             StrictVal::Unit => f.write_str("()"),
             StrictVal::Number(n) => Display::fmt(n, f),
-            StrictVal::String(s) => write!(f, r#""{s}""#),
+            StrictVal::String(s) => {
+                f.write_char('"')?;
+                for c in s.chars() {
+                    match c {
+                        '"' => f.write_str(r#"\""#)?,
+                        '\\' => f.write_str(r"\\")?,
+                        '\n' => f.write_str(r"\n")?,
+                        '\r' => f.write_str(r"\r")?,
+                        '\t' => f.write_str(r"\t")?,
+                        '\0' => f.write_str(r"\0")?,
+                        c if c.is_control() => write!(f, r"\u{{{:x}}}", c as u32)?,
+                        c => f.write_char(c)?,
+                    }
+                }
+                f.write_char('"')
+            }
             StrictVal::Bytes(blob) => write!(f, "0x{}", blob.to_hex()),
             StrictVal::Tuple(fields) => {
                 let mut iter = fields.iter();
@@ -88,7 +108,7 @@ impl Display for StrictVal {
                 }
                 Display::fmt(val, f)?;
                 if val.needs_braces() {
-                    f.write_str("(")?;
+                    f.write_str(")")?;
                 }
                 Ok(())
             }
@@ -137,13 +157,514 @@ impl Display for StrictVal {
     }
 }
 
+/// Errors parsing STON representation of a strict value.
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum ParseError {
+    /// unexpected end of STON data.
+    UnexpectedEnd,
+
+    /// unexpected character `{1}` at position {0}.
+    UnexpectedChar(usize, char),
+
+    /// invalid escape sequence in a string at position {0}.
+    InvalidEscape(usize),
+
+    /// invalid hexadecimal byte string at position {0}.
+    InvalidHex(usize),
+
+    /// number at position {0} doesn't fit into 1024 bits.
+    NumberOverflow(usize),
+
+    /// union tag {1} at position {0} exceeds 255.
+    TagOverflow(usize, u128),
+
+    /// invalid name at position {0}: {1}
+    InvalidName(usize, InvalidRString),
+
+    /// field `{1}` at position {0} is repeated.
+    RepeatedField(usize, FieldName),
+
+    /// value at position {0} is nested deeper than {1} levels.
+    TooDeep(usize, usize),
+}
+
+/// Maximal nesting depth of a parsed value, protecting the recursive parser
+/// from stack overflow on untrusted input.
+pub const MAX_PARSE_DEPTH: usize = 128;
+
+impl FromStr for StrictVal {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { StrictVal::parse_ston(s) }
+}
+
+impl StrictVal {
+    /// Parses STON representation of a strict value, as produced by its
+    /// `Display` implementation.
+    pub fn parse_ston(s: &str) -> Result<StrictVal, ParseError> {
+        let mut parser = Parser {
+            s,
+            pos: 0,
+            depth: 0,
+        };
+        let val = parser.value()?;
+        parser.skip_ws();
+        match parser.peek() {
+            None => Ok(val),
+            Some(c) => Err(ParseError::UnexpectedChar(parser.pos, c)),
+        }
+    }
+}
+
+/// Parses STON representation of a strict value from the beginning of the
+/// string, returning the rest of the string after the value.
+pub(super) fn parse_ston_prefix(s: &str) -> Result<(StrictVal, &str), ParseError> {
+    let mut parser = Parser {
+        s,
+        pos: 0,
+        depth: 0,
+    };
+    let val = parser.value()?;
+    Ok((val, &s[parser.pos..]))
+}
+
+struct Parser<'s> {
+    s: &'s str,
+    pos: usize,
+    depth: usize,
+}
+
+impl<'s> Parser<'s> {
+    fn peek(&self) -> Option<char> { self.s[self.pos..].chars().next() }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+            self.bump();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        self.skip_ws();
+        match self.bump() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(ParseError::UnexpectedChar(self.pos - c.len_utf8(), c)),
+            None => Err(ParseError::UnexpectedEnd),
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'s str {
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if f(c)) {
+            self.bump();
+        }
+        &self.s[start..self.pos]
+    }
+
+    /// Parses comma-separated items until the `close` character.
+    fn items<T>(
+        &mut self,
+        close: char,
+        mut item: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut items = vec![];
+        self.skip_ws();
+        if self.peek() == Some(close) {
+            self.bump();
+            return Ok(items);
+        }
+        loop {
+            items.push(item(self)?);
+            self.skip_ws();
+            match self.bump() {
+                Some(',') => {}
+                Some(c) if c == close => return Ok(items),
+                Some(c) => return Err(ParseError::UnexpectedChar(self.pos - c.len_utf8(), c)),
+                None => return Err(ParseError::UnexpectedEnd),
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<StrictVal, ParseError> {
+        self.skip_ws();
+        if self.depth >= MAX_PARSE_DEPTH {
+            return Err(ParseError::TooDeep(self.pos, MAX_PARSE_DEPTH));
+        }
+        self.depth += 1;
+        let val = self.value_inner();
+        self.depth -= 1;
+        val
+    }
+
+    fn value_inner(&mut self) -> Result<StrictVal, ParseError> {
+        let start = self.pos;
+        match self.peek() {
+            None => Err(ParseError::UnexpectedEnd),
+            Some('(') => self.group(),
+            Some('[') => {
+                self.bump();
+                self.items(']', Self::value).map(StrictVal::List)
+            }
+            Some('{') => self.braces(),
+            Some('"') => self.string().map(StrictVal::String),
+            Some('~') => {
+                self.bump();
+                Ok(StrictVal::none())
+            }
+            Some('0') if self.s[self.pos..].starts_with("0x") => {
+                self.pos += 2;
+                let hex = self.take_while(|c| c.is_ascii_hexdigit());
+                Vec::<u8>::from_hex(hex)
+                    .map(StrictVal::Bytes)
+                    .map_err(|_| ParseError::InvalidHex(start))
+            }
+            Some(c) if c == '-' || c == '+' || c.is_ascii_digit() => {
+                let num = self.number()?;
+                match num {
+                    StrictNum::Uint(tag) if self.is_union_payload() => {
+                        let tag =
+                            u8::try_from(tag).map_err(|_| ParseError::TagOverflow(start, tag))?;
+                        Ok(StrictVal::Union(EnumTag::Ord(tag), Box::new(self.union_payload()?)))
+                    }
+                    num => Ok(StrictVal::Number(num)),
+                }
+            }
+            Some('N') if self.s[self.pos..].starts_with("NaN") => {
+                self.pos += 3;
+                Ok(StrictVal::Number(StrictNum::Float(ieee::Oct::NAN)))
+            }
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+                let name = self.name::<VariantName>()?;
+                if self.is_union_payload() {
+                    Ok(StrictVal::Union(EnumTag::Name(name), Box::new(self.union_payload()?)))
+                } else {
+                    Ok(StrictVal::Enum(EnumTag::Name(name)))
+                }
+            }
+            Some(c) => Err(ParseError::UnexpectedChar(start, c)),
+        }
+    }
+
+    fn name<T: FromStr<Err = InvalidRString>>(&mut self) -> Result<T, ParseError> {
+        let start = self.pos;
+        let name = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+        T::from_str(name).map_err(|err| ParseError::InvalidName(start, err))
+    }
+
+    fn is_union_payload(&mut self) -> bool {
+        self.skip_ws();
+        matches!(self.peek(), Some('(' | '[' | '{'))
+    }
+
+    fn union_payload(&mut self) -> Result<StrictVal, ParseError> {
+        match self.value()? {
+            // Union values which are not collections are put into braces
+            StrictVal::Tuple(mut fields) if fields.len() == 1 => Ok(fields.remove(0)),
+            val => Ok(val),
+        }
+    }
+
+    fn group(&mut self) -> Result<StrictVal, ParseError> {
+        self.expect('(')?;
+        self.skip_ws();
+        if self.peek() == Some(')') {
+            self.bump();
+            return Ok(StrictVal::Unit);
+        }
+
+        let rest = &self.s[self.pos..];
+        let ident_len =
+            rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(rest.len());
+        let is_struct = ident_len > 0 && rest[ident_len..].trim_start().starts_with('=');
+        if !is_struct {
+            return self.items(')', Self::value).map(StrictVal::Tuple);
+        }
+
+        let mut fields = IndexMap::new();
+        for (start, name, val) in self.items(')', |p| {
+            p.skip_ws();
+            let start = p.pos;
+            let name = p.name::<FieldName>()?;
+            p.expect('=')?;
+            Ok((start, name, p.value()?))
+        })? {
+            if fields.contains_key(&name) {
+                return Err(ParseError::RepeatedField(start, name));
+            }
+            fields.insert(name, val);
+        }
+        Ok(StrictVal::Struct(fields))
+    }
+
+    fn braces(&mut self) -> Result<StrictVal, ParseError> {
+        self.expect('{')?;
+        self.skip_ws();
+        if self.peek() == Some('}') {
+            self.bump();
+            return Ok(StrictVal::Set(vec![]));
+        }
+        let first = self.value()?;
+        self.skip_ws();
+        if !self.s[self.pos..].starts_with("->") {
+            let mut items = vec![first];
+            match self.bump() {
+                Some('}') => return Ok(StrictVal::Set(items)),
+                Some(',') => {}
+                Some(c) => return Err(ParseError::UnexpectedChar(self.pos - c.len_utf8(), c)),
+                None => return Err(ParseError::UnexpectedEnd),
+            }
+            items.extend(self.items('}', Self::value)?);
+            return Ok(StrictVal::Set(items));
+        }
+
+        self.pos += 2;
+        let mut items = vec![(first, self.value()?)];
+        self.skip_ws();
+        match self.bump() {
+            Some('}') => return Ok(StrictVal::Map(items)),
+            Some(',') => {}
+            Some(c) => return Err(ParseError::UnexpectedChar(self.pos - c.len_utf8(), c)),
+            None => return Err(ParseError::UnexpectedEnd),
+        }
+        items.extend(self.items('}', |p| {
+            let key = p.value()?;
+            p.expect('-')?;
+            p.expect('>')?;
+            Ok((key, p.value()?))
+        })?);
+        Ok(StrictVal::Map(items))
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        self.expect('"')?;
+        let mut s = String::new();
+        loop {
+            let start = self.pos;
+            match self.bump().ok_or(ParseError::UnexpectedEnd)? {
+                '"' => return Ok(s),
+                '\\' => match self.bump().ok_or(ParseError::UnexpectedEnd)? {
+                    '"' => s.push('"'),
+                    '\\' => s.push('\\'),
+                    'n' => s.push('\n'),
+                    'r' => s.push('\r'),
+                    't' => s.push('\t'),
+                    '0' => s.push('\0'),
+                    'u' => {
+                        self.expect('{')?;
+                        let hex = self.take_while(|c| c.is_ascii_hexdigit());
+                        let c = u32::from_str_radix(hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or(ParseError::InvalidEscape(start))?;
+                        self.expect('}')?;
+                        s.push(c);
+                    }
+                    _ => return Err(ParseError::InvalidEscape(start)),
+                },
+                c => s.push(c),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<StrictNum, ParseError> {
+        let start = self.pos;
+        let sign = self.peek().filter(|c| *c == '-' || *c == '+');
+        if sign.is_some() {
+            self.bump();
+        }
+        if self.s[self.pos..].starts_with("Inf") {
+            self.pos += 3;
+            let inf = ieee::Oct::INFINITY;
+            return Ok(StrictNum::Float(if sign == Some('-') { -inf } else { inf }));
+        }
+        if sign == Some('+') {
+            return Err(ParseError::UnexpectedChar(start, '+'));
+        }
+        let neg = sign.is_some();
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        if digits.is_empty() {
+            return match self.peek() {
+                Some(c) => Err(ParseError::UnexpectedChar(self.pos, c)),
+                None => Err(ParseError::UnexpectedEnd),
+            };
+        }
+        if self.float_tail()? {
+            let literal = &self.s[start..self.pos];
+            return ieee::Oct::from_str(literal)
+                .map(StrictNum::Float)
+                .map_err(|_| ParseError::NumberOverflow(start));
+        }
+        let literal = &self.s[start..self.pos];
+        if neg {
+            if let Ok(val) = i128::from_str(literal) {
+                return Ok(StrictNum::Int(val));
+            }
+        } else if let Ok(val) = u128::from_str(literal) {
+            return Ok(StrictNum::Uint(val));
+        }
+
+        let mut abs = u1024::ZERO;
+        for digit in digits.bytes() {
+            abs = abs
+                .checked_mul(10u64)
+                .and_then(|abs| abs.checked_add((digit - b'0') as u64))
+                .ok_or(ParseError::NumberOverflow(start))?;
+        }
+        if !neg {
+            return Ok(StrictNum::BigUint(abs));
+        }
+        let val = i1024::from_le_bytes(abs.wrapping_neg().to_le_bytes());
+        if !val.is_negative() {
+            return Err(ParseError::NumberOverflow(start));
+        }
+        Ok(StrictNum::BigInt(val))
+    }
+
+    /// Consumes fractional part and exponent of a float number, if present.
+    /// Returns whether the number is a float.
+    fn float_tail(&mut self) -> Result<bool, ParseError> {
+        let mut is_float = false;
+        let rest = &self.s[self.pos..];
+        if rest.starts_with('.') && rest[1..].starts_with(|c: char| c.is_ascii_digit()) {
+            self.bump();
+            self.take_while(|c| c.is_ascii_digit());
+            is_float = true;
+        }
+        if matches!(self.peek(), Some('e' | 'E')) {
+            self.bump();
+            if matches!(self.peek(), Some('-' | '+')) {
+                self.bump();
+            }
+            if self.take_while(|c| c.is_ascii_digit()).is_empty() {
+                return match self.peek() {
+                    Some(c) => Err(ParseError::UnexpectedChar(self.pos, c)),
+                    None => Err(ParseError::UnexpectedEnd),
+                };
+            }
+            is_float = true;
+        }
+        Ok(is_float)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    //use super::*;
+    use super::*;
 
     #[test]
     fn serialize() {
         let strct = svstruct!(name => "Some name", ticker => "TICK", precision => svenum!(8));
         assert_eq!(format!("{strct}"), r#"(name="Some name", ticker="TICK", precision=8)"#)
     }
+
+    fn roundtrip(val: StrictVal) {
+        let s = val.to_string();
+        assert_eq!(StrictVal::from_str(&s).unwrap(), val, "{s}");
+    }
+
+    #[test]
+    fn parse() {
+        roundtrip(StrictVal::Unit);
+        roundtrip(svnum!(0u8));
+        roundtrip(svnum!(-42i64));
+        roundtrip(svstr!(""));
+        roundtrip(svstr!("with \"quotes\", \\ and\nnew line\u{1} ∑"));
+        roundtrip(svbytes!([0xde, 0xad, 0xbe, 0xef]));
+        roundtrip(svnewtype!("TICK"));
+        roundtrip(svtuple!([sv!(1u8), sv!("some"), svnone!()]));
+        roundtrip(
+            svstruct!(name => "Some name", ticker => svnewtype!("TICK"), precision => svenum!(twoDecimals)),
+        );
+        roundtrip(svunion!(some => svstruct!(a => 1u8, b => svlist!([1u8, 2u8]))));
+        roundtrip(svunion!(some => 5u8));
+        roundtrip(svunion!(some => "str"));
+        roundtrip(svunion!(unit => ()));
+        roundtrip(StrictVal::union(3, svlist!([svstr!("a")])));
+        roundtrip(svlist!([svlist!([1u8]), svlist!(Vec::<StrictVal>::new())]));
+        roundtrip(svset!([svtuple!([1u8, 2u8]), svtuple!([3u8, 4u8])]));
+        roundtrip(StrictVal::map([
+            (svstr!("key"), svset!([1u8])),
+            (svstr!("other"), svset!([2u8])),
+        ]));
+        roundtrip(StrictVal::map([(svenum!(a), svnone!())]));
+    }
+
+    #[test]
+    fn parse_big_numbers() {
+        roundtrip(svnum!(u128::MAX));
+        roundtrip(svnum!(i128::MIN));
+        roundtrip(svnum!(u1024::MAX));
+        roundtrip(svnum!(u1024::from(u128::MAX) * u1024::from(u64::MAX)));
+        roundtrip(svnum!(i1024::MIN));
+        roundtrip(svnum!(i1024::from(i128::MIN) * i1024::from(u64::MAX)));
+        assert_eq!(
+            StrictVal::from_str("340282366920938463463374607431768211456").unwrap(),
+            svnum!(u1024::from(u128::MAX) + u1024::from(1u64))
+        );
+        let too_big = format!("{}0", StrictNum::BigUint(u1024::MAX));
+        assert_eq!(StrictVal::from_str(&too_big).unwrap_err(), ParseError::NumberOverflow(0));
+    }
+
+    #[test]
+    fn parse_floats() {
+        roundtrip(svnum!(1.5f64));
+        roundtrip(svnum!(-0.1f32));
+        roundtrip(svnum!(3.0f64));
+        roundtrip(svnum!(1e300f64));
+        roundtrip(svnum!(f64::INFINITY));
+        roundtrip(svnum!(f64::NEG_INFINITY));
+        roundtrip(svlist!([svnum!(f32::MIN_POSITIVE), svnum!(-0.0f64)]));
+        assert_eq!(svnum!(3.0f64).to_string(), "3.0");
+        assert_eq!(svnum!(f32::INFINITY).to_string(), "+Inf");
+        assert_eq!(svnum!(f32::NEG_INFINITY).to_string(), "-Inf");
+        assert_eq!(svnum!(f32::NAN).to_string(), "NaN");
+        assert!(matches!(
+            StrictVal::from_str("NaN").unwrap(),
+            StrictVal::Number(StrictNum::Float(f)) if f.is_nan()
+        ));
+        assert_eq!(
+            StrictVal::from_str("2.5e-3").unwrap(),
+            svnum!(ieee::Oct::from_str("0.0025").unwrap())
+        );
+        assert_eq!(StrictVal::from_str("1e").unwrap_err(), ParseError::UnexpectedEnd);
+        assert_eq!(StrictVal::from_str("+1").unwrap_err(), ParseError::UnexpectedChar(0, '+'));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            StrictVal::from_str("(a=1, a=2)").unwrap_err(),
+            ParseError::RepeatedField(6, fname!("a"))
+        );
+        assert_eq!(StrictVal::from_str("[1, 2").unwrap_err(), ParseError::UnexpectedEnd);
+        assert_eq!(StrictVal::from_str("[1; 2]").unwrap_err(), ParseError::UnexpectedChar(2, ';'));
+        assert_eq!(StrictVal::from_str(r#""\q""#).unwrap_err(), ParseError::InvalidEscape(1));
+        assert_eq!(StrictVal::from_str("0xabc").unwrap_err(), ParseError::InvalidHex(0));
+        assert_eq!(StrictVal::from_str("300(1)").unwrap_err(), ParseError::TagOverflow(0, 300));
+        assert_eq!(StrictVal::from_str("() ()").unwrap_err(), ParseError::UnexpectedChar(3, '('));
+    }
+
+    #[test]
+    fn parse_depth() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(StrictVal::from_str(&nested(MAX_PARSE_DEPTH)).is_ok());
+        assert_eq!(
+            StrictVal::from_str(&nested(MAX_PARSE_DEPTH + 1)).unwrap_err(),
+            ParseError::TooDeep(MAX_PARSE_DEPTH, MAX_PARSE_DEPTH)
+        );
+        assert_eq!(
+            "[".repeat(200_000).parse::<StrictVal>().unwrap_err(),
+            ParseError::TooDeep(MAX_PARSE_DEPTH, MAX_PARSE_DEPTH)
+        );
+        assert!(matches!(
+            StrictVal::from_str(&"a(".repeat(1000)).unwrap_err(),
+            ParseError::TooDeep(..)
+        ));
+    }
 }
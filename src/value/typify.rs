@@ -23,15 +23,18 @@
 //! Checks strict values against provied strict type specification.
 
 use std::collections::BTreeSet;
+use std::str::FromStr;
 
 use amplify::ascii::{AsAsciiStrError, AsciiString};
+use amplify::num::apfloat::{ieee, Float, Status};
 use amplify::Wrapper;
-use encoding::{FieldName, InvalidRString, Primitive, Sizing, VariantName};
+use encoding::{FieldName, InvalidRString, NumCls, Primitive, Sizing, VariantName};
 use indexmap::IndexMap;
 
-use super::StrictVal;
+use super::{ston, StrictVal};
 use crate::ast::EnumVariants;
-use crate::typesys::{SymbolicSys, TypeFqn, TypeSymbol};
+use crate::typesys::{FqnParseError, SymbolicSys, TypeFqn, TypeSymbol};
+use crate::value::val::{float_from_le_bytes, float_to_le_bytes, int_for_prim};
 use crate::value::{EnumTag, StrictNum};
 use crate::{SemId, Ty, TypeRef, TypeSystem};
 
@@ -47,6 +50,18 @@ pub enum TypeSpec {
     Fqn(TypeFqn /* , Option<CheckWords> */),
 }
 
+impl FromStr for TypeSpec {
+    type Err = FqnParseError;
+
+    /// Parses either a semantic type id or a fully qualified type name.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match SemId::from_str(s) {
+            Ok(sem_id) => Ok(TypeSpec::SemId(sem_id)),
+            Err(_) => TypeFqn::from_str(s).map(TypeSpec::Fqn),
+        }
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Debug, Display)]
 #[display("{val}@{orig}")]
 pub struct TypedVal {
@@ -89,6 +104,9 @@ pub enum Error {
     /// unexpected field `{0}`.
     ExtraField(FieldName),
 
+    /// missing field `{0}`.
+    MissingField(FieldName),
+
     /// value `{value}` doesn't match type requirements `{expected}`.
     TypeMismatch {
         value: StrictVal,
@@ -107,20 +125,64 @@ pub enum Error {
 
     /// mapping found where a structure value was expected.
     MapNotStructure,
+
+    /// value {1} is out of the range of integer type `{0}`.
+    IntOverflow(TypeSpec, StrictNum),
+
+    /// value {1} is out of the range of float type `{0}`.
+    FloatOverflow(TypeSpec, StrictNum),
+
+    /// invalid STON value: {0}
+    #[from]
+    Ston(ston::ParseError),
+
+    /// invalid byte string "{0}", which doesn't match the expected byte encoding (hex, unless
+    /// base64 was requested).
+    InvalidBytes(String),
+
+    /// value {1} can't be represented in {0}.
+    Unrepresentable(String, StrictVal),
+
+    /// invalid value at `{0}`: {1}
+    Located(String, Box<Error>),
 }
 
-trait PrimitiveValue {
-    fn is_small_unsigned(&self) -> bool;
-    fn is_large_unsigned(&self) -> bool;
-    fn is_small_signed(&self) -> bool;
-    fn is_large_signed(&self) -> bool;
+pub(crate) trait PrimitiveValue {
+    fn is_int(&self) -> bool;
+    fn is_float(&self) -> bool;
 }
 
 impl PrimitiveValue for Primitive {
-    fn is_small_unsigned(&self) -> bool { self.into_code() <= 16 }
-    fn is_large_unsigned(&self) -> bool { self.into_code() > 16 && self.into_code() < 0x40 }
-    fn is_small_signed(&self) -> bool { self.into_code() >= 0x40 && self.into_code() <= 0x4f }
-    fn is_large_signed(&self) -> bool { self.into_code() > 0x4f && self.into_code() < 0x80 }
+    fn is_int(&self) -> bool {
+        *self != Primitive::UNIT && matches!(self.info().ty, NumCls::Unsigned | NumCls::Signed)
+    }
+    fn is_float(&self) -> bool {
+        matches!(
+            *self,
+            Primitive::F16B
+                | Primitive::F16
+                | Primitive::F32
+                | Primitive::F64
+                | Primitive::F80
+                | Primitive::F128
+                | Primitive::F256
+        )
+    }
+}
+
+/// Checks that an integer value fits into the range of the integer primitive
+/// `prim`.
+fn check_int(num: StrictNum, prim: Primitive, spec: TypeSpec) -> Result<StrictVal, Error> {
+    int_for_prim(num, prim).map(StrictVal::Number).ok_or(Error::IntOverflow(spec, num))
+}
+
+/// Rounds float value to the precision of the float primitive `prim`.
+fn round_float(val: ieee::Oct, prim: Primitive, spec: TypeSpec) -> Result<StrictVal, Error> {
+    let rounded = float_to_le_bytes(val, prim);
+    if rounded.status.contains(Status::OVERFLOW) && !val.is_infinite() {
+        return Err(Error::FloatOverflow(spec, StrictNum::Float(val)));
+    }
+    Ok(StrictVal::Number(float_from_le_bytes(&rounded.value, prim)))
 }
 
 impl SymbolicSys {
@@ -129,6 +191,12 @@ impl SymbolicSys {
         let sem_id = self.to_sem_id(spec.clone()).ok_or(Error::TypeAbsent(spec))?;
         self.as_types().typify(val, sem_id)
     }
+
+    /// Parses value from its STON representation and checks it against the
+    /// provided type.
+    pub fn typify_ston(&self, ston: &str, spec: impl Into<TypeSpec>) -> Result<TypedVal, Error> {
+        self.typify(StrictVal::parse_ston(ston)?, spec)
+    }
 }
 
 impl TypeSystem {
@@ -136,36 +204,34 @@ impl TypeSystem {
         self.as_inner().iter().find(|(my_id, _)| **my_id == sem_id).map(|(_, ty)| ty)
     }
 
+    /// Parses value from its STON representation and checks it against the
+    /// type with the provided id.
+    pub fn typify_ston(&self, ston: &str, sem_id: SemId) -> Result<TypedVal, Error> {
+        self.typify(StrictVal::parse_ston(ston)?, sem_id)
+    }
+
     pub fn typify(&self, val: StrictVal, sem_id: SemId) -> Result<TypedVal, Error> {
         let spec = TypeSpec::from(sem_id);
         let ty = self.find(sem_id).ok_or_else(|| Error::TypeAbsent(spec.clone()))?;
         let val = match (val, ty) {
             // Primitive direct matches:
             (val @ StrictVal::Unit, Ty::Primitive(prim)) if *prim == Primitive::UNIT => val,
-            (val @ StrictVal::Number(StrictNum::Uint(_)), Ty::Primitive(prim))
-                if prim.is_small_unsigned() =>
-            {
-                val
-            }
-            (StrictVal::Number(StrictNum::Uint(val)), Ty::Primitive(prim))
-                if prim.is_small_signed() && (val & 0x8000_0000_0000_0000) == 0 =>
-            {
-                StrictVal::Number(StrictNum::Int(val as i128))
-            }
-            (val @ StrictVal::Number(StrictNum::BigUint(_)), Ty::Primitive(prim))
-                if prim.is_large_unsigned() =>
-            {
-                val
-            }
-            (val @ StrictVal::Number(StrictNum::Int(_)), Ty::Primitive(prim))
-                if prim.is_small_signed() =>
-            {
-                val
-            }
-            (val @ StrictVal::Number(StrictNum::BigInt(_)), Ty::Primitive(prim))
-                if prim.is_large_signed() =>
-            {
-                val
+            (
+                StrictVal::Number(
+                    num @ (StrictNum::Uint(_)
+                    | StrictNum::Int(_)
+                    | StrictNum::BigUint(_)
+                    | StrictNum::BigInt(_)),
+                ),
+                Ty::Primitive(prim),
+            ) if prim.is_int() => check_int(num, *prim, spec.clone())?,
+            (StrictVal::Number(StrictNum::Float(val)), Ty::Primitive(prim)) if prim.is_float() => {
+                round_float(val, *prim, spec.clone())?
+            }
+            (StrictVal::Number(num), Ty::Primitive(prim)) if prim.is_float() => {
+                let val = ieee::Oct::from_str(&num.to_string())
+                    .expect("decimal integer is always a valid float");
+                round_float(val, *prim, spec.clone())?
             }
 
             // Collection bounds check:
@@ -256,6 +322,8 @@ impl TypeSystem {
                 }
                 StrictVal::Set(new)
             }
+            // STON doesn't distinguish empty sets from empty maps
+            (StrictVal::Set(s), Ty::Map(..)) if s.is_empty() => StrictVal::Map(vec![]),
             (StrictVal::Map(s), Ty::Map(key_id, id, _)) => {
                 let mut new = Vec::<(StrictVal, StrictVal)>::with_capacity(s.len());
                 for (key, item) in s {
@@ -282,11 +350,10 @@ impl TypeSystem {
             }
             (StrictVal::String(s), Ty::Enum(variants)) => {
                 if let Ok(vname) = VariantName::try_from(s.clone()) {
-                    let tag = variants.tag_by_name(&vname);
-                    match tag {
-                        None => return Err(Error::EnumTagInvalid(vname.into(), variants.clone())),
-                        Some(name) => StrictVal::enumer(name),
+                    if variants.tag_by_name(&vname).is_none() {
+                        return Err(Error::EnumTagInvalid(vname.into(), variants.clone()));
                     }
+                    StrictVal::enumer(vname)
                 } else {
                     return Err(Error::TypeMismatch {
                         value: StrictVal::String(s),
@@ -409,10 +476,11 @@ impl TypeSystem {
 #[cfg(test)]
 mod test {
     use amplify::confinement::U32 as MAX32;
+    use amplify::num::{i1024, i256, u24, u256, u512};
     use encoding::{StreamReader, StrictSerialize};
 
     use super::super::test_helpers::*;
-    // use super::*;
+    use super::*;
 
     #[test]
     fn load() {
@@ -426,4 +494,122 @@ mod test {
         let loaded = sys.strict_read_type("TestLib.Nominal", &mut reader).unwrap();
         assert_eq!(loaded.val, value);
     }
+
+    #[test]
+    fn type_spec() {
+        let sys = test_system();
+        let sem_id = sys.to_sem_id("TestLib.Nominal").unwrap();
+        assert_eq!(TypeSpec::from_str("TestLib.Nominal"), Ok(TypeSpec::from("TestLib.Nominal")));
+        assert_eq!(TypeSpec::from_str(&sem_id.to_string()), Ok(TypeSpec::SemId(sem_id)));
+        assert!(matches!(TypeSpec::from_str("Nominal"), Err(FqnParseError::NoLib(_))));
+        assert!(matches!(
+            TypeSpec::from_str("TestLib.1Nominal"),
+            Err(FqnParseError::InvalidName(..))
+        ));
+    }
+
+    #[test]
+    fn ston() {
+        let sys = test_system();
+        let val = sys
+            .typify_ston(
+                r#"(ticker=("TICK"), name="Some name", precision=twoDecimals)"#,
+                "TestLib.Nominal",
+            )
+            .unwrap();
+        let nominal = Nominal::with("TICK", "Some name", 2);
+        let data = nominal.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        let mut serialized = vec![];
+        sys.as_types().strict_write_type(&val, &mut serialized).unwrap();
+        assert_eq!(serialized, data.as_slice());
+
+        let err = sys.typify_ston(r#"(name="Some name", ticker="#, "TestLib.Nominal").unwrap_err();
+        assert_eq!(err, Error::Ston(ston::ParseError::UnexpectedEnd));
+    }
+
+    #[test]
+    fn floats() {
+        let sys = test_system();
+        let val = sys
+            .typify_ston(
+                "(brain=1.1, half=-2.5, single=0.1, double=1e300, extended=+Inf, quad=NaN, oct=42)",
+                "TestLib.Reading",
+            )
+            .unwrap();
+        let reading = Reading {
+            brain: BFloat(half::bf16::from_f32(1.1)),
+            half: ieee::Half::from_str("-2.5").unwrap(),
+            single: ieee::Single::from_str("0.1").unwrap(),
+            double: ieee::Double::from_str("1e300").unwrap(),
+            extended: ieee::X87DoubleExtended::INFINITY,
+            quad: ieee::Quad::NAN,
+            oct: ieee::Oct::from_str("42").unwrap(),
+        };
+        let data = reading.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        let mut serialized = vec![];
+        sys.as_types().strict_write_type(&val, &mut serialized).unwrap();
+        assert_eq!(serialized, data.as_slice());
+
+        let loaded = sys.strict_deserialize_type("TestLib.Reading", &data).unwrap();
+        assert_eq!(loaded.val, val.val);
+        assert_eq!(loaded.val.to_string(), val.val.to_string());
+
+        let err = sys
+            .typify_ston(
+                "(brain=0, half=1e10, single=0, double=0, extended=0, quad=0, oct=0)",
+                "TestLib.Reading",
+            )
+            .unwrap_err();
+        assert!(matches!(err, Error::FloatOverflow(..)), "{err}");
+    }
+
+    #[test]
+    fn wide_ints() {
+        let sys = test_system();
+        let amounts = Amounts {
+            small: u24::MAX,
+            medium: 5,
+            large: u256::MAX,
+            signed: i256::MIN,
+            huge: u512::from(u128::MAX) * u512::from(u128::MAX),
+            negative: i1024::from(-1i64),
+        };
+        let ston = format!(
+            "(small={}, medium=5, large={}, signed={}, huge={}, negative=-1)",
+            u24::MAX,
+            StrictNum::from(u256::MAX),
+            StrictNum::from(i256::MIN),
+            StrictNum::from(amounts.huge)
+        );
+        let val = sys.typify_ston(&ston, "TestLib.Amounts").unwrap();
+        assert_eq!(val.val.to_string(), ston);
+
+        let data = amounts.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        let mut serialized = vec![];
+        sys.as_types().strict_write_type(&val, &mut serialized).unwrap();
+        assert_eq!(serialized, data.as_slice());
+
+        let loaded = sys.strict_deserialize_type("TestLib.Amounts", &data).unwrap();
+        assert_eq!(loaded.val, val.val);
+
+        let overflow = |small: &str, large: &str, signed: &str| {
+            let ston = format!(
+                "(small={small}, medium=0, large={large}, signed={signed}, huge=0, negative=0)"
+            );
+            sys.typify_ston(&ston, "TestLib.Amounts").unwrap_err()
+        };
+        let max_i256 = StrictNum::from(i256::MAX).to_string();
+        assert!(matches!(overflow("16777216", "0", "0"), Error::IntOverflow(..)));
+        assert!(matches!(overflow("0", "-1", "0"), Error::IntOverflow(..)));
+        assert!(matches!(
+            overflow("0", &format!("{}0", StrictNum::from(u256::MAX)), "0"),
+            Error::IntOverflow(..)
+        ));
+        assert!(matches!(overflow("0", "0", &format!("{max_i256}0")), Error::IntOverflow(..)));
+        sys.typify_ston(
+            &format!("(small=0, medium=0, large=0, signed={max_i256}, huge=0, negative=0)"),
+            "TestLib.Amounts",
+        )
+        .unwrap();
+    }
 }
@@ -22,11 +22,13 @@
 
 //! Strict value core types.
 
-use std::fmt::Debug;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Display, Formatter, Write};
+use std::hash::{Hash, Hasher};
 
-// use amplify::num::apfloat::ieee;
-use amplify::num::{i1024, u1024, u24, u40, u48, u56};
-use encoding::{FieldName, StrictEnum, VariantName};
+use amplify::num::apfloat::{ieee, ExpInt, Float, FloatConvert, StatusAnd};
+use amplify::num::{i1024, i256, i512, u1024, u24, u256, u40, u48, u512, u56};
+use encoding::{FieldName, NumCls, Primitive, StrictEnum, VariantName};
 use indexmap::IndexMap;
 
 #[macro_export]
@@ -135,8 +137,7 @@ macro_rules! svtable {
     };
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Display, From)]
-#[display(inner)]
+#[derive(Copy, Clone, Debug, From)]
 #[non_exhaustive]
 pub enum StrictNum {
     #[from(u8)]
@@ -150,9 +151,6 @@ pub enum StrictNum {
     #[from]
     Uint(u128),
 
-    // TODO: Do conversion of number types in to amplify_num
-    //#[from(u256)]
-    //#[from(u512)]
     #[from]
     BigUint(u1024),
 
@@ -163,27 +161,291 @@ pub enum StrictNum {
     #[from]
     Int(i128),
 
-    // TODO: Do conversion of number types in to amplify_num
-    //#[from(i256)]
-    //#[from(i512)]
     #[from]
     BigInt(i1024),
-    // TODO: Do conversion of number types in to amplify_num
-    /*
-    #[from(half::bf16)]
-    #[from(ieee::Half)]
-    #[from(ieee::Single)]
-    #[from(ieee::Double)]
-    #[from(ieee::Quad)]
-    #[from(ieee::Oct]
-    #[from(f32)]
-    #[from(f64)]
+
+    /// Floating-point number of any precision. Since all smaller IEEE formats
+    /// are losslessly representable in octuple precision, we use it as the
+    /// common representation.
+    #[from]
     Float(ieee::Oct),
-    */
     // TODO: Addnon-zero
 }
 
-// TODO: Do conversion of number types in to amplify_num
+macro_rules! from_ieee {
+    ($($ty:ty),+) => {
+        $( impl From<$ty> for StrictNum {
+            fn from(val: $ty) -> Self {
+                let mut loses_info = false;
+                StrictNum::Float(val.convert(&mut loses_info).value)
+            }
+        } )+
+    };
+}
+
+from_ieee!(ieee::Half, ieee::Single, ieee::Double, ieee::X87DoubleExtended, ieee::Quad);
+
+impl From<f32> for StrictNum {
+    fn from(val: f32) -> Self { ieee::Single::from_bits(u256::from(val.to_bits())).into() }
+}
+
+impl From<f64> for StrictNum {
+    fn from(val: f64) -> Self { ieee::Double::from_bits(u256::from(val.to_bits())).into() }
+}
+
+impl From<half::bf16> for StrictNum {
+    fn from(val: half::bf16) -> Self { val.to_f32().into() }
+}
+
+impl From<half::f16> for StrictNum {
+    fn from(val: half::f16) -> Self { ieee::Half::from_bits(u256::from(val.to_bits())).into() }
+}
+
+/// Semantics of the bfloat16 format: single-precision exponent range with an
+/// 8-bit significand.
+struct BFloatS;
+
+impl ieee::Semantics for BFloatS {
+    const BITS: usize = 16;
+    const PRECISION: usize = 8;
+    const MAX_EXP: ExpInt = (1 << 7) - 1;
+}
+
+type BFloat = ieee::IeeeFloat<BFloatS>;
+
+/// Converts a float into the precision of a float primitive `prim`, returning
+/// the little-endian bytes of its binary representation together with the
+/// conversion status.
+///
+/// # Panics
+///
+/// If `prim` is not a float primitive.
+pub(crate) fn float_to_le_bytes(val: ieee::Oct, prim: Primitive) -> StatusAnd<Vec<u8>> {
+    fn convert<F: Float>(val: ieee::Oct, len: usize) -> StatusAnd<Vec<u8>>
+    where ieee::Oct: FloatConvert<F> {
+        let mut loses_info = false;
+        val.convert(&mut loses_info).map(|f: F| f.to_bits().to_le_bytes()[..len].to_vec())
+    }
+
+    match prim {
+        Primitive::F16B => convert::<BFloat>(val, 2),
+        Primitive::F16 => convert::<ieee::Half>(val, 2),
+        Primitive::F32 => convert::<ieee::Single>(val, 4),
+        Primitive::F64 => convert::<ieee::Double>(val, 8),
+        Primitive::F80 => convert::<ieee::X87DoubleExtended>(val, 10),
+        Primitive::F128 => convert::<ieee::Quad>(val, 16),
+        Primitive::F256 => convert::<ieee::Oct>(val, 32),
+        other => unreachable!("{other} is not a float primitive"),
+    }
+}
+
+/// Reads a float of the precision of a float primitive `prim` from its
+/// little-endian binary representation.
+///
+/// # Panics
+///
+/// If `prim` is not a float primitive or the number of bytes doesn't match
+/// the size of the primitive.
+pub(crate) fn float_from_le_bytes(bytes: &[u8], prim: Primitive) -> StrictNum {
+    assert_eq!(bytes.len(), prim.byte_size() as usize, "invalid float length");
+    let mut buf = [0u8; 32];
+    buf[..bytes.len()].copy_from_slice(bytes);
+    let bits = u256::from_le_bytes(buf);
+    match prim {
+        Primitive::F16B => half::bf16::from_bits(bits.low_u32() as u16).into(),
+        Primitive::F16 => ieee::Half::from_bits(bits).into(),
+        Primitive::F32 => ieee::Single::from_bits(bits).into(),
+        Primitive::F64 => ieee::Double::from_bits(bits).into(),
+        Primitive::F80 => {
+            // `X87DoubleExtended::from_bits` drops the explicit integer bit of the significand,
+            // thus we re-pack the value into a quad-precision float, which has the same exponent
+            // width and bias and holds the 63-bit fraction without loss of precision.
+            let sign = (bits >> 79) & u256::ONE;
+            let exp = (bits >> 64) & u256::from(0x7FFFu16);
+            let fraction = bits & u256::from(0x7FFF_FFFF_FFFF_FFFFu64);
+            ieee::Quad::from_bits((sign << 127) | (exp << 112) | (fraction << 49)).into()
+        }
+        Primitive::F128 => ieee::Quad::from_bits(bits).into(),
+        Primitive::F256 => ieee::Oct::from_bits(bits).into(),
+        other => unreachable!("{other} is not a float primitive"),
+    }
+}
+
+/// Checks that an integer value fits into the range of the integer primitive
+/// `prim` and converts it into the representation used for that primitive:
+/// integers up to 128 bits are kept as `Uint`/`Int`, larger ones as
+/// `BigUint`/`BigInt`. Returns `None` if the value is out of the range.
+///
+/// # Panics
+///
+/// If the value is a float.
+pub(crate) fn int_for_prim(num: StrictNum, prim: Primitive) -> Option<StrictNum> {
+    let (signed, bits) = match prim {
+        // Byte is encoded with a signed class code, but is an unsigned value
+        Primitive::BYTE => (false, 8),
+        _ => (prim.info().ty == NumCls::Signed, prim.byte_size() as usize * 8),
+    };
+    let (neg, abs) = match num {
+        StrictNum::Uint(val) => (false, u1024::from(val)),
+        StrictNum::Int(val) => (val < 0, u1024::from(val.unsigned_abs())),
+        StrictNum::BigUint(val) => (false, val),
+        StrictNum::BigInt(val) => {
+            let bits = u1024::from_le_bytes(val.to_le_bytes());
+            if val.is_negative() {
+                (true, bits.wrapping_neg())
+            } else {
+                (false, bits)
+            }
+        }
+        StrictNum::Float(_) => unreachable!("float values are not integers"),
+    };
+    let fits = match (signed, neg) {
+        (false, true) => false,
+        (false, false) => bits >= 1024 || abs < u1024::ONE << bits,
+        (true, false) => abs < u1024::ONE << (bits - 1),
+        (true, true) => abs <= u1024::ONE << (bits - 1),
+    };
+    if !fits {
+        return None;
+    }
+    let le_bytes = if neg { abs.wrapping_neg() } else { abs }.to_le_bytes();
+    let mut small = [0u8; 16];
+    small.copy_from_slice(&le_bytes[..16]);
+    Some(match (signed, bits <= 128) {
+        (false, true) => StrictNum::Uint(u128::from_le_bytes(small)),
+        (false, false) => StrictNum::BigUint(abs),
+        (true, true) => StrictNum::Int(i128::from_le_bytes(small)),
+        (true, false) => StrictNum::BigInt(i1024::from_le_bytes(le_bytes)),
+    })
+}
+
+impl StrictNum {
+    fn rank(&self) -> u8 {
+        match self {
+            StrictNum::Uint(_) => 0,
+            StrictNum::BigUint(_) => 1,
+            StrictNum::Int(_) => 2,
+            StrictNum::BigInt(_) => 3,
+            StrictNum::Float(_) => 4,
+        }
+    }
+}
+
+/// Maps float bits onto a key with total ordering (negative numbers go first,
+/// NaNs are ordered by their sign and payload).
+fn float_order_key(val: ieee::Oct) -> u256 {
+    let bits = val.to_bits();
+    if val.is_negative() {
+        !bits
+    } else {
+        bits | (u256::ONE << 255)
+    }
+}
+
+// Floats do not have total equivalence and ordering, thus we have to compare
+// them by their bit representation.
+impl PartialEq for StrictNum {
+    fn eq(&self, other: &Self) -> bool { self.cmp(other) == Ordering::Equal }
+}
+
+impl Eq for StrictNum {}
+
+impl PartialOrd for StrictNum {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for StrictNum {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (StrictNum::Uint(a), StrictNum::Uint(b)) => a.cmp(b),
+            (StrictNum::BigUint(a), StrictNum::BigUint(b)) => a.cmp(b),
+            (StrictNum::Int(a), StrictNum::Int(b)) => a.cmp(b),
+            (StrictNum::BigInt(a), StrictNum::BigInt(b)) => a.cmp(b),
+            (StrictNum::Float(a), StrictNum::Float(b)) => {
+                float_order_key(*a).cmp(&float_order_key(*b))
+            }
+            (a, b) => a.rank().cmp(&b.rank()),
+        }
+    }
+}
+
+impl Hash for StrictNum {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rank().hash(state);
+        match self {
+            StrictNum::Uint(v) => v.hash(state),
+            StrictNum::BigUint(v) => v.hash(state),
+            StrictNum::Int(v) => v.hash(state),
+            StrictNum::BigInt(v) => v.hash(state),
+            StrictNum::Float(v) => v.to_bits().hash(state),
+        }
+    }
+}
+
+macro_rules! from_bigint {
+    ($($ty:ty),+ => $variant:ident($big:ty)) => {
+        $( impl From<$ty> for StrictNum {
+            fn from(val: $ty) -> Self {
+                // Negative numbers must be sign-extended
+                let mut buf = [if val < <$ty>::ZERO { 0xFF } else { 0x00 }; 128];
+                let le_bytes = val.to_le_bytes();
+                buf[..le_bytes.len()].copy_from_slice(&le_bytes);
+                StrictNum::$variant(<$big>::from_le_bytes(buf))
+            }
+        } )+
+    };
+}
+
+from_bigint!(u256, u512 => BigUint(u1024));
+from_bigint!(i256, i512 => BigInt(i1024));
+
+/// Big integers use hexadecimal representation in their `Display`, so we
+/// convert them to decimals manually.
+fn big_to_decimal(mut val: u1024) -> String {
+    const CHUNK: u64 = 10_000_000_000_000_000_000;
+    let mut chunks = vec![];
+    loop {
+        chunks.push((val % CHUNK).low_u64());
+        val /= CHUNK;
+        if val.is_zero() {
+            break;
+        }
+    }
+    let mut s = chunks.pop().expect("at least one chunk").to_string();
+    for chunk in chunks.into_iter().rev() {
+        write!(s, "{chunk:019}").expect("writing to string");
+    }
+    s
+}
+
+impl Display for StrictNum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            StrictNum::Uint(v) => Display::fmt(v, f),
+            StrictNum::Int(v) => Display::fmt(v, f),
+            StrictNum::BigUint(v) => f.pad_integral(true, "", &big_to_decimal(*v)),
+            StrictNum::BigInt(v) if v.is_negative() => {
+                let abs = u1024::from_le_bytes(v.to_le_bytes()).wrapping_neg();
+                f.pad_integral(false, "", &big_to_decimal(abs))
+            }
+            StrictNum::BigInt(v) => {
+                f.pad_integral(true, "", &big_to_decimal(u1024::from_le_bytes(v.to_le_bytes())))
+            }
+            StrictNum::Float(v) if v.is_nan() => f.pad("NaN"),
+            StrictNum::Float(v) if v.is_infinite() && v.is_negative() => f.pad("-Inf"),
+            StrictNum::Float(v) if v.is_infinite() => f.pad("+Inf"),
+            StrictNum::Float(v) => {
+                let mut s = v.to_string();
+                // We always keep the decimal point so the value can't be confused with an
+                // integer when parsed back.
+                if !s.contains(['.', 'e', 'E']) {
+                    s.push_str(".0");
+                }
+                f.pad(&s)
+            }
+        }
+    }
+}
 
 impl StrictNum {
     pub fn unwrap_uint<N: TryFrom<u128>>(self) -> N
@@ -223,29 +485,29 @@ pub enum StrictVal {
 
     #[from(u8)]
     #[from(u16)]
-    //#[from(u24)]
+    #[from(u24)]
     #[from(u32)]
     #[from(u64)]
     #[from(u128)]
-    //#[from(u256)]
-    //#[from(u512)]
-    //#[from(u1024)]
+    #[from(u256)]
+    #[from(u512)]
+    #[from(u1024)]
     #[from(i8)]
     #[from(i16)]
     #[from(i32)]
     #[from(i64)]
     #[from(i128)]
-    //#[from(i256)]
-    //#[from(i512)]
-    //#[from(i1024)]
-    //#[from(f32)]
-    //#[from(f64)]
-    //#[from(half::bf16)]
-    //#[from(ieee::Half)]
-    //#[from(ieee::Single)]
-    //#[from(ieee::Double)]
-    //#[from(ieee::Quad)]
-    //#[from(ieee::Oct)]
+    #[from(i256)]
+    #[from(i512)]
+    #[from(i1024)]
+    #[from(f32)]
+    #[from(f64)]
+    #[from(half::bf16)]
+    #[from(ieee::Half)]
+    #[from(ieee::Single)]
+    #[from(ieee::Double)]
+    #[from(ieee::Quad)]
+    #[from(ieee::Oct)]
     Number(StrictNum),
 
     // Covers unicode & ascii strings and characters
@@ -463,7 +725,7 @@ impl<T: Into<StrictVal>> From<Option<T>> for StrictVal {
 
 #[cfg(test)]
 mod test {
-    // use super::*;
+    use super::*;
 
     #[test]
     fn construct() {
@@ -480,4 +742,52 @@ mod test {
             r#"Struct({FieldName("name"): String("Some name"), FieldName("ticker"): String("TICK"), FieldName("precision"): Number(Uint(8))})"#
         )
     }
+
+    #[test]
+    fn float_precision() {
+        let val = "1.1".parse::<ieee::Oct>().unwrap();
+        let bf16 = float_to_le_bytes(val, Primitive::F16B);
+        assert_eq!(bf16.value, half::bf16::from_f32(1.1).to_le_bytes());
+        assert_eq!(
+            float_from_le_bytes(&bf16.value, Primitive::F16B),
+            StrictNum::from(half::bf16::from_f32(1.1))
+        );
+
+        // 1 + 2^-8 + 2^-30 is just above the midpoint between two adjacent brain floats, but
+        // rounding it to single precision first would hit the midpoint exactly and round to even.
+        let val = "1.000000000931322574615478515625".parse::<ieee::Oct>().unwrap()
+            + "0.00390625".parse::<ieee::Oct>().unwrap();
+        let bf16 = float_to_le_bytes(val.value, Primitive::F16B);
+        assert_eq!(bf16.value, half::bf16::from_bits(0x3F81).to_le_bytes());
+        assert!(bf16.status.contains(amplify::num::apfloat::Status::INEXACT));
+
+        let big = StrictNum::from(1e300f64);
+        let StrictNum::Float(big) = big else {
+            unreachable!()
+        };
+        let overflow = float_to_le_bytes(big, Primitive::F16B);
+        assert!(overflow.status.contains(amplify::num::apfloat::Status::OVERFLOW));
+
+        assert_eq!(StrictNum::from(f64::NAN), StrictNum::from(f32::NAN));
+        assert_ne!(StrictNum::from(0.0f32), StrictNum::from(-0.0f32));
+        assert!(StrictNum::from(-1.0f32) < StrictNum::from(-0.5f32));
+        assert!(StrictNum::from(f64::NEG_INFINITY) < StrictNum::from(f64::MIN));
+    }
+
+    #[test]
+    fn extended_float() {
+        for s in ["1.5", "-0.1", "0", "1e-4940", "1e4000"] {
+            let val = s.parse::<ieee::Oct>().unwrap();
+            let bytes = float_to_le_bytes(val, Primitive::F80).value;
+            let ext = s.parse::<ieee::X87DoubleExtended>().unwrap();
+            assert_eq!(bytes, ext.to_bits().to_le_bytes()[..10]);
+            assert_eq!(float_from_le_bytes(&bytes, Primitive::F80), StrictNum::from(ext));
+        }
+        let inf = ieee::X87DoubleExtended::INFINITY;
+        let StrictNum::Float(val) = StrictNum::from(inf) else {
+            unreachable!()
+        };
+        let bytes = float_to_le_bytes(val, Primitive::F80).value;
+        assert_eq!(float_from_le_bytes(&bytes, Primitive::F80), StrictNum::from(inf));
+    }
 }
@@ -0,0 +1,776 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Streaming decoder, which reads strict-encoded data from [`io::Read`] and
+//! reports the decoded values to a [`StrictVisitor`] instead of building a
+//! complete [`StrictVal`] tree.
+//!
+//! Only leaf values (primitives, enums, strings and byte strings) are
+//! materialized; composite values are reported as a sequence of `enter_*`
+//! and [`StrictVisitor::leave`] callbacks. A visitor may skip any subtree, in
+//! which case the decoder consumes its data without allocating and without
+//! calling the visitor. The amount of memory buffered for a single leaf value
+//! and the nesting depth are bound by [`DecodeLimits`].
+
+use std::io;
+
+use amplify::ascii::AsciiString;
+use amplify::confinement::U32 as MAX32;
+use encoding::{DecodeError, FieldName, Primitive, Sizing, StreamReader, VariantName};
+
+use super::decode::{self, strict_read_len};
+use crate::ast::{NamedFields, UnionVariants, UnnamedFields};
+use crate::typesys::SymbolicSys;
+use crate::typify::TypeSpec;
+use crate::{SemId, StrictVal, Ty, TypeRef, TypeSystem};
+
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum Error {
+    #[display(inner)]
+    #[from]
+    #[from(DecodeError)]
+    #[from(crate::typesys::UnknownType)]
+    Decode(decode::Error),
+
+    /// value nesting exceeds the depth limit of {0}.
+    DepthLimit(usize),
+
+    /// value of {0} bytes exceeds the memory limit of {1} bytes.
+    MemoryLimit(usize, usize),
+
+    /// decoding is aborted by the visitor: {0}.
+    Aborted(String),
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self { Error::Decode(DecodeError::from(err).into()) }
+}
+
+/// Limits applied by the streaming decoder to untrusted data.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct DecodeLimits {
+    /// Maximal nesting depth of composite values and collections.
+    pub max_depth: usize,
+    /// Maximal number of bytes buffered for a single leaf value (a string or
+    /// a byte string). Skipped values are not buffered and not limited.
+    pub max_alloc: usize,
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        DecodeLimits {
+            max_depth: 64,
+            max_alloc: 1 << 20,
+        }
+    }
+}
+
+/// Decision of a visitor whether the decoder has to descend into a value.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum Visit {
+    /// Decode the value reporting it to the visitor.
+    #[default]
+    Enter,
+    /// Consume the value data without reporting it to the visitor.
+    Skip,
+}
+
+/// Callbacks of the streaming decoder.
+///
+/// Each `enter_*` callback returning [`Visit::Enter`] is paired with a call to
+/// [`StrictVisitor::leave`] once the value is decoded; returning
+/// [`Visit::Skip`] skips the value and the `leave` callback is not called.
+/// All callbacks except [`StrictVisitor::visit_value`] enter all values by
+/// default.
+pub trait StrictVisitor {
+    /// Reports a leaf value: unit, number, enum, unicode character, string or
+    /// a byte string. The value has the same form as the one produced by
+    /// [`TypeSystem::strict_deserialize_type`].
+    fn visit_value(&mut self, sem_id: SemId, val: StrictVal) -> Result<(), Error>;
+
+    /// Called before decoding a named field of a structure.
+    fn enter_field(&mut self, name: &FieldName, sem_id: SemId) -> Result<Visit, Error> {
+        let _ = (name, sem_id);
+        Ok(Visit::Enter)
+    }
+
+    /// Called before decoding an unnamed field of a tuple.
+    fn enter_tuple_field(&mut self, pos: u8, sem_id: SemId) -> Result<Visit, Error> {
+        let _ = (pos, sem_id);
+        Ok(Visit::Enter)
+    }
+
+    /// Called before decoding the data of a union variant.
+    fn enter_variant(
+        &mut self,
+        tag: u8,
+        name: &VariantName,
+        sem_id: SemId,
+    ) -> Result<Visit, Error> {
+        let _ = (tag, name, sem_id);
+        Ok(Visit::Enter)
+    }
+
+    /// Called before decoding items of an array, list, set or entries of a
+    /// map, providing the number of items or entries.
+    fn enter_collection(&mut self, sem_id: SemId, len: usize) -> Result<Visit, Error> {
+        let _ = (sem_id, len);
+        Ok(Visit::Enter)
+    }
+
+    /// Called before decoding an item of an array, list or set.
+    fn enter_item(&mut self, index: usize, sem_id: SemId) -> Result<Visit, Error> {
+        let _ = (index, sem_id);
+        Ok(Visit::Enter)
+    }
+
+    /// Called before decoding a key of a map entry.
+    fn enter_key(&mut self, index: usize, sem_id: SemId) -> Result<Visit, Error> {
+        let _ = (index, sem_id);
+        Ok(Visit::Enter)
+    }
+
+    /// Called before decoding a value of a map entry.
+    fn enter_map_value(&mut self, index: usize, sem_id: SemId) -> Result<Visit, Error> {
+        let _ = (index, sem_id);
+        Ok(Visit::Enter)
+    }
+
+    /// Called once a value entered with one of `enter_*` callbacks is
+    /// completely decoded.
+    fn leave(&mut self) -> Result<(), Error> { Ok(()) }
+}
+
+impl SymbolicSys {
+    pub fn strict_visit_type(
+        &self,
+        spec: impl Into<TypeSpec>,
+        reader: impl io::Read,
+        limits: DecodeLimits,
+        visitor: &mut impl StrictVisitor,
+    ) -> Result<usize, Error> {
+        let spec = spec.into();
+        let sem_id = self.to_sem_id(spec.clone()).ok_or(decode::Error::TypeAbsent(spec))?;
+        self.as_types().strict_visit_type(sem_id, reader, limits, visitor)
+    }
+}
+
+impl TypeSystem {
+    /// Decodes a value of type `sem_id` from the `reader`, reporting it to the
+    /// `visitor`. Returns the number of bytes consumed from the reader.
+    ///
+    /// Unlike [`TypeSystem::strict_deserialize_type`], doesn't check that the
+    /// reader is exhausted after the value is decoded.
+    pub fn strict_visit_type(
+        &self,
+        sem_id: SemId,
+        reader: impl io::Read,
+        limits: DecodeLimits,
+        visitor: &mut impl StrictVisitor,
+    ) -> Result<usize, Error> {
+        let mut stream = Stream::new(self, reader, limits);
+        stream.visit(sem_id, visitor)?;
+        Ok(stream.reader.count)
+    }
+
+    /// Consumes data of a value of type `sem_id` from the `reader` without
+    /// decoding it, returning the number of consumed bytes.
+    ///
+    /// Only the length prefixes of collections and the tags of enums and
+    /// unions are read and checked; the rest of the data is discarded without
+    /// allocating memory. The nesting of the value is limited by the default
+    /// [`DecodeLimits`].
+    pub fn skip_type(&self, sem_id: SemId, reader: impl io::Read) -> Result<usize, Error> {
+        self.skip_type_with(sem_id, reader, DecodeLimits::default())
+    }
+
+    /// Consumes data of a value of type `sem_id` from the `reader` like
+    /// [`Self::skip_type`], limiting the nesting of the value by
+    /// [`DecodeLimits::max_depth`].
+    pub fn skip_type_with(
+        &self,
+        sem_id: SemId,
+        reader: impl io::Read,
+        limits: DecodeLimits,
+    ) -> Result<usize, Error> {
+        let mut stream = Stream::new(self, reader, limits);
+        stream.skip(sem_id)?;
+        Ok(stream.reader.count)
+    }
+
+    /// Measures the length of the serialized value of type `sem_id` which
+    /// starts at the beginning of `data`. The data may contain other values
+    /// after the measured one.
+    pub fn serialized_len(&self, sem_id: SemId, data: &[u8]) -> Result<usize, Error> {
+        self.serialized_len_with(sem_id, data, DecodeLimits::default())
+    }
+
+    /// Measures the length of the serialized value of type `sem_id` like
+    /// [`Self::serialized_len`], limiting the nesting of the value by
+    /// [`DecodeLimits::max_depth`].
+    pub fn serialized_len_with(
+        &self,
+        sem_id: SemId,
+        data: &[u8],
+        limits: DecodeLimits,
+    ) -> Result<usize, Error> {
+        self.skip_type_with(sem_id, data, limits)
+    }
+}
+
+/// Reader counting the number of consumed bytes.
+struct Counter<R: io::Read> {
+    inner: R,
+    count: usize,
+}
+
+impl<R: io::Read> io::Read for Counter<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        self.count += len;
+        Ok(len)
+    }
+}
+
+/// Classification of types by the way their data are encoded.
+pub(super) enum Layout<'sys> {
+    /// Primitives, enums and unicode characters.
+    Scalar(&'sys Ty<SemId>),
+    /// ASCII string with the type of the first character and the rest of
+    /// characters.
+    Ascii(SemId, SemId, Sizing),
+    Unicode(Sizing),
+    Bytes(Sizing),
+    FixedUnicode(usize),
+    FixedBytes(usize),
+    Union(&'sys UnionVariants<SemId>),
+    Tuple(&'sys UnnamedFields<SemId>),
+    Struct(&'sys NamedFields<SemId>),
+    Array(SemId, usize),
+    List(SemId, Sizing),
+    Set(SemId, Sizing),
+    Map(SemId, SemId, Sizing),
+}
+
+impl TypeSystem {
+    /// Classifies type the same way as it is done by the decoder when
+    /// producing a [`StrictVal`].
+    pub(super) fn data_layout(&self, sem_id: SemId) -> Result<Layout<'_>, decode::Error> {
+        let find = |id: SemId| self.find(id).ok_or_else(|| decode::Error::TypeAbsent(id.into()));
+        let ty = find(sem_id)?;
+        Ok(match ty {
+            Ty::Primitive(_) | Ty::Enum(_) | Ty::UnicodeChar => Layout::Scalar(ty),
+            Ty::List(id, sizing) if find(*id)?.is_char_enum() => Layout::Ascii(*id, *id, *sizing),
+            Ty::Tuple(fields) if self.is_rstring(fields)? => {
+                let (rest, sizing) = self.rstring_sizing(fields)?.expect("checked in match");
+                Layout::Ascii(fields[0], rest, sizing)
+            }
+            Ty::Union(variants) => Layout::Union(variants),
+            Ty::Tuple(fields) => Layout::Tuple(fields),
+            Ty::Struct(fields) => Layout::Struct(fields),
+            Ty::Array(id, len) if find(*id)?.is_unicode_char() => {
+                Layout::FixedUnicode(*len as usize)
+            }
+            Ty::Array(id, len) if id.is_byte() => Layout::FixedBytes(*len as usize),
+            Ty::Array(id, len) => Layout::Array(*id, *len as usize),
+            Ty::List(id, sizing) if id.is_byte() => Layout::Bytes(*sizing),
+            Ty::List(id, sizing) if find(*id)?.is_unicode_char() => Layout::Unicode(*sizing),
+            Ty::List(id, sizing) => Layout::List(*id, *sizing),
+            Ty::Set(id, sizing) => Layout::Set(*id, *sizing),
+            Ty::Map(key_id, id, sizing) => Layout::Map(*key_id, *id, *sizing),
+        })
+    }
+}
+
+struct Stream<'sys, R: io::Read> {
+    sys: &'sys TypeSystem,
+    reader: Counter<R>,
+    limits: DecodeLimits,
+    depth: usize,
+}
+
+impl<'sys, R: io::Read> Stream<'sys, R> {
+    fn new(sys: &'sys TypeSystem, reader: R, limits: DecodeLimits) -> Self {
+        Stream {
+            sys,
+            reader: Counter {
+                inner: reader,
+                count: 0,
+            },
+            limits,
+            depth: 0,
+        }
+    }
+
+    fn layout(&self, sem_id: SemId) -> Result<Layout<'sys>, Error> {
+        Ok(self.sys.data_layout(sem_id)?)
+    }
+
+    fn descend(&mut self) -> Result<(), Error> {
+        self.depth += 1;
+        if self.depth > self.limits.max_depth {
+            return Err(Error::DepthLimit(self.limits.max_depth));
+        }
+        Ok(())
+    }
+
+    fn ascend(&mut self) { self.depth -= 1; }
+
+    fn read_len(&mut self, sizing: &Sizing) -> Result<usize, Error> {
+        let mut reader = StreamReader::new::<MAX32>(&mut self.reader);
+        Ok(strict_read_len(sizing, &mut reader)?)
+    }
+
+    fn read_tag(&mut self) -> Result<u8, Error> {
+        let mut buf = [0u8; 1];
+        io::Read::read_exact(&mut self.reader, &mut buf)?;
+        Ok(buf[0])
+    }
+
+    /// Reads `len` bytes, ensuring they fit into the memory limit. Doesn't
+    /// allocate more memory than there are data actually read.
+    fn read_bytes(&mut self, len: usize) -> Result<Vec<u8>, Error> {
+        if len > self.limits.max_alloc {
+            return Err(Error::MemoryLimit(len, self.limits.max_alloc));
+        }
+        let mut buf = Vec::new();
+        io::Read::read_to_end(&mut io::Read::take(&mut self.reader, len as u64), &mut buf)?;
+        if buf.len() != len {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+        Ok(buf)
+    }
+
+    fn skip_bytes(&mut self, len: usize) -> Result<(), Error> {
+        let copied = io::copy(&mut io::Read::take(&mut self.reader, len as u64), &mut io::sink())?;
+        if copied != len as u64 {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+        Ok(())
+    }
+
+    fn read_ascii(&mut self, len: usize) -> Result<StrictVal, Error> {
+        let ascii = AsciiString::from_ascii(self.read_bytes(len)?)
+            .map_err(|err| DecodeError::Ascii(err.ascii_error()))?;
+        Ok(StrictVal::String(ascii.to_string()))
+    }
+
+    fn read_unicode(&mut self, len: usize) -> Result<StrictVal, Error> {
+        let s = String::from_utf8(self.read_bytes(len)?)
+            .map_err(|err| decode::Error::InvalidUtf8(err.utf8_error()))?;
+        Ok(StrictVal::String(s))
+    }
+
+    /// Enters a child value unless the visitor decides to skip it.
+    fn child(
+        &mut self,
+        sem_id: SemId,
+        visit: Visit,
+        visitor: &mut impl StrictVisitor,
+    ) -> Result<(), Error> {
+        match visit {
+            Visit::Enter => {
+                self.visit(sem_id, visitor)?;
+                visitor.leave()
+            }
+            Visit::Skip => self.skip(sem_id),
+        }
+    }
+
+    fn visit(&mut self, sem_id: SemId, visitor: &mut impl StrictVisitor) -> Result<(), Error> {
+        self.descend()?;
+        match self.layout(sem_id)? {
+            Layout::Scalar(_) => {
+                let mut reader = StreamReader::new::<MAX32>(&mut self.reader);
+                let val = self.sys.strict_read_type(sem_id, &mut reader)?.val;
+                visitor.visit_value(sem_id, val)?;
+            }
+            Layout::Ascii(_, _, sizing) => {
+                let len = self.read_len(&sizing)?;
+                let val = self.read_ascii(len)?;
+                visitor.visit_value(sem_id, val)?;
+            }
+            Layout::Unicode(sizing) => {
+                let len = self.read_len(&sizing)?;
+                let val = self.read_unicode(len)?;
+                visitor.visit_value(sem_id, val)?;
+            }
+            Layout::FixedUnicode(len) => {
+                let val = self.read_unicode(len)?;
+                visitor.visit_value(sem_id, val)?;
+            }
+            Layout::Bytes(sizing) => {
+                let len = self.read_len(&sizing)?;
+                let val = StrictVal::Bytes(self.read_bytes(len)?);
+                visitor.visit_value(sem_id, val)?;
+            }
+            Layout::FixedBytes(len) => {
+                let val = StrictVal::Bytes(self.read_bytes(len)?);
+                visitor.visit_value(sem_id, val)?;
+            }
+            Layout::Union(variants) => {
+                let tag = self.read_tag()?;
+                let (Some(ty), Some(name)) = (variants.ty_by_tag(tag), variants.name_by_tag(tag))
+                else {
+                    return Err(DecodeError::UnionTagNotKnown(sem_id.to_string(), tag).into());
+                };
+                let visit = visitor.enter_variant(tag, name, *ty)?;
+                self.child(*ty, visit, visitor)?;
+            }
+            Layout::Tuple(fields) => {
+                for (pos, ty) in fields.iter().enumerate() {
+                    let visit = visitor.enter_tuple_field(pos as u8, *ty)?;
+                    self.child(*ty, visit, visitor)?;
+                }
+            }
+            Layout::Struct(fields) => {
+                for field in fields {
+                    let visit = visitor.enter_field(&field.name, field.ty)?;
+                    self.child(field.ty, visit, visitor)?;
+                }
+            }
+            Layout::Array(ty, len) => self.visit_items(sem_id, ty, len, visitor)?,
+            Layout::List(ty, sizing) | Layout::Set(ty, sizing) => {
+                let len = self.read_len(&sizing)?;
+                self.visit_items(sem_id, ty, len, visitor)?;
+            }
+            Layout::Map(key_ty, ty, sizing) => {
+                let len = self.read_len(&sizing)?;
+                match visitor.enter_collection(sem_id, len)? {
+                    Visit::Enter => {
+                        for index in 0..len {
+                            let visit = visitor.enter_key(index, key_ty)?;
+                            self.child(key_ty, visit, visitor)?;
+                            let visit = visitor.enter_map_value(index, ty)?;
+                            self.child(ty, visit, visitor)?;
+                        }
+                        visitor.leave()?;
+                    }
+                    Visit::Skip => {
+                        for _ in 0..len {
+                            self.skip(key_ty)?;
+                            self.skip(ty)?;
+                        }
+                    }
+                }
+            }
+        }
+        self.ascend();
+        Ok(())
+    }
+
+    fn visit_items(
+        &mut self,
+        sem_id: SemId,
+        ty: SemId,
+        len: usize,
+        visitor: &mut impl StrictVisitor,
+    ) -> Result<(), Error> {
+        match visitor.enter_collection(sem_id, len)? {
+            Visit::Enter => {
+                for index in 0..len {
+                    let visit = visitor.enter_item(index, ty)?;
+                    self.child(ty, visit, visitor)?;
+                }
+                visitor.leave()
+            }
+            Visit::Skip => {
+                for _ in 0..len {
+                    self.skip(ty)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Consumes data of a value without decoding it. Checks only that the union
+    /// and enum tags are valid; the data of leaf values are not checked.
+    fn skip(&mut self, sem_id: SemId) -> Result<(), Error> {
+        self.descend()?;
+        match self.layout(sem_id)? {
+            Layout::Scalar(ty) => match ty {
+                Ty::Primitive(prim) if *prim == Primitive::UNIT => {}
+                Ty::Primitive(prim) => self.skip_bytes(prim.byte_size() as usize)?,
+                Ty::Enum(variants) => {
+                    let tag = self.read_tag()?;
+                    if !variants.has_tag(tag) {
+                        return Err(DecodeError::EnumTagNotKnown(sem_id.to_string(), tag).into());
+                    }
+                }
+                Ty::UnicodeChar => {
+                    let len = match self.read_tag()? {
+                        0xC0..=0xDF => 1,
+                        0xE0..=0xEF => 2,
+                        0xF0..=0xF7 => 3,
+                        _ => 0,
+                    };
+                    self.skip_bytes(len)?;
+                }
+                _ => unreachable!("layout mismatches type"),
+            },
+            Layout::Ascii(_, _, sizing) | Layout::Unicode(sizing) | Layout::Bytes(sizing) => {
+                let len = self.read_len(&sizing)?;
+                self.skip_bytes(len)?;
+            }
+            Layout::FixedUnicode(len) | Layout::FixedBytes(len) => self.skip_bytes(len)?,
+            Layout::Union(variants) => {
+                let tag = self.read_tag()?;
+                let Some(ty) = variants.ty_by_tag(tag) else {
+                    return Err(DecodeError::UnionTagNotKnown(sem_id.to_string(), tag).into());
+                };
+                self.skip(*ty)?;
+            }
+            Layout::Tuple(fields) => {
+                for ty in fields {
+                    self.skip(*ty)?;
+                }
+            }
+            Layout::Struct(fields) => {
+                for field in fields {
+                    self.skip(field.ty)?;
+                }
+            }
+            Layout::Array(ty, len) => {
+                for _ in 0..len {
+                    self.skip(ty)?;
+                }
+            }
+            Layout::List(ty, sizing) | Layout::Set(ty, sizing) => {
+                let len = self.read_len(&sizing)?;
+                for _ in 0..len {
+                    self.skip(ty)?;
+                }
+            }
+            Layout::Map(key_ty, ty, sizing) => {
+                let len = self.read_len(&sizing)?;
+                for _ in 0..len {
+                    self.skip(key_ty)?;
+                    self.skip(ty)?;
+                }
+            }
+        }
+        self.ascend();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::Confined;
+    use encoding::StrictSerialize;
+
+    use super::super::test_helpers::*;
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder {
+        skip: Option<&'static str>,
+        events: Vec<String>,
+    }
+
+    impl StrictVisitor for Recorder {
+        fn visit_value(&mut self, _: SemId, val: StrictVal) -> Result<(), Error> {
+            self.events.push(val.to_string());
+            Ok(())
+        }
+
+        fn enter_field(&mut self, name: &FieldName, _: SemId) -> Result<Visit, Error> {
+            if self.skip == Some(name.as_str()) {
+                return Ok(Visit::Skip);
+            }
+            self.events.push(format!(".{name}"));
+            Ok(Visit::Enter)
+        }
+
+        fn enter_tuple_field(&mut self, pos: u8, _: SemId) -> Result<Visit, Error> {
+            self.events.push(format!(".{pos}"));
+            Ok(Visit::Enter)
+        }
+
+        fn leave(&mut self) -> Result<(), Error> {
+            self.events.push(s!("<"));
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct Values(Vec<StrictVal>);
+
+    impl StrictVisitor for Values {
+        fn visit_value(&mut self, _: SemId, val: StrictVal) -> Result<(), Error> {
+            self.0.push(val);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn visit() {
+        let sys = test_system();
+        let data =
+            Nominal::with("TICK", "Some name", 2).to_strict_serialized::<{ usize::MAX }>().unwrap();
+
+        let mut recorder = Recorder::default();
+        let len = sys
+            .strict_visit_type("TestLib.Nominal", data.as_slice(), default!(), &mut recorder)
+            .unwrap();
+        assert_eq!(len, data.len());
+        assert_eq!(recorder.events, [
+            ".ticker",
+            ".0",
+            "\"TICK\"",
+            "<",
+            "<",
+            ".name",
+            "\"Some name\"",
+            "<",
+            ".precision",
+            "2",
+            "<"
+        ]);
+
+        let mut recorder = Recorder {
+            skip: Some("name"),
+            ..default!()
+        };
+        let len = sys
+            .strict_visit_type("TestLib.Nominal", data.as_slice(), default!(), &mut recorder)
+            .unwrap();
+        assert_eq!(len, data.len());
+        assert_eq!(recorder.events, [
+            ".ticker",
+            ".0",
+            "\"TICK\"",
+            "<",
+            "<",
+            ".precision",
+            "2",
+            "<"
+        ]);
+    }
+
+    #[test]
+    fn wide_bytes() {
+        let sys = test_system();
+        let blob = Blob(Confined::try_from(vec![0xCA, 0xFE]).unwrap());
+        let data = blob.to_strict_serialized::<{ usize::MAX }>().unwrap();
+
+        let decoded = sys.strict_deserialize_type("TestLib.Blob", &data).unwrap();
+        let mut values = Values::default();
+        let len = sys
+            .strict_visit_type("TestLib.Blob", data.as_slice(), default!(), &mut values)
+            .unwrap();
+        assert_eq!(len, data.len());
+        assert_eq!(decoded.val, StrictVal::Tuple(values.0));
+        assert_eq!(decoded.val, StrictVal::Tuple(vec![StrictVal::Bytes(vec![0xCA, 0xFE])]));
+    }
+
+    #[test]
+    fn limits() {
+        let sys = test_system();
+        let data =
+            Nominal::with("TICK", "Some name", 2).to_strict_serialized::<{ usize::MAX }>().unwrap();
+
+        let limits = DecodeLimits {
+            max_depth: 2,
+            ..default!()
+        };
+        let err = sys
+            .strict_visit_type("TestLib.Nominal", data.as_slice(), limits, &mut Recorder::default())
+            .unwrap_err();
+        assert_eq!(err, Error::DepthLimit(2));
+
+        let limits = DecodeLimits {
+            max_alloc: 4,
+            ..default!()
+        };
+        let err = sys
+            .strict_visit_type("TestLib.Nominal", data.as_slice(), limits, &mut Recorder::default())
+            .unwrap_err();
+        assert_eq!(err, Error::MemoryLimit(9, 4));
+
+        let mut recorder = Recorder {
+            skip: Some("name"),
+            ..default!()
+        };
+        sys.strict_visit_type("TestLib.Nominal", data.as_slice(), limits, &mut recorder).unwrap();
+
+        let err = sys
+            .strict_visit_type(
+                "TestLib.Nominal",
+                &data[..data.len() - 1],
+                default!(),
+                &mut Recorder::default(),
+            )
+            .unwrap_err();
+        assert!(matches!(err, Error::Decode(decode::Error::Decode(DecodeError::Io(_)))));
+    }
+
+    #[test]
+    fn skip() {
+        let sys = test_system();
+        let types = sys.as_types();
+        let nominal = sys.to_sem_id("TestLib.Nominal").unwrap();
+        let mut data = Nominal::with("TICK", "Some name", 2)
+            .to_strict_serialized::<{ usize::MAX }>()
+            .unwrap()
+            .into_inner();
+        let len = data.len();
+        data.extend([0xFF; 4]);
+        assert_eq!(types.serialized_len(nominal, &data).unwrap(), len);
+        assert_eq!(types.skip_type(nominal, &data[..]).unwrap(), len);
+
+        let amounts = sys.to_sem_id("TestLib.Amounts").unwrap();
+        let data = Amounts::default().to_strict_serialized::<{ usize::MAX }>().unwrap();
+        assert_eq!(types.serialized_len(amounts, &data).unwrap(), data.len());
+        assert_eq!(types.static_size(amounts).unwrap(), crate::Size::Fixed(data.len()));
+
+        // Invalid enum tag
+        let mut data = Nominal::with("TICK", "Some name", 2)
+            .to_strict_serialized::<{ usize::MAX }>()
+            .unwrap()
+            .into_inner();
+        *data.last_mut().unwrap() = 0xFF;
+        assert!(matches!(
+            types.serialized_len(nominal, &data).unwrap_err(),
+            Error::Decode(decode::Error::Decode(DecodeError::EnumTagNotKnown(_, 0xFF)))
+        ));
+
+        // A chain of negations nested deeper than permitted by the limits
+        let expr = sys.to_sem_id("TestLib.Expr").unwrap();
+        let mut data = vec![0x00; 10];
+        data.extend([0x02, 0x07]);
+        assert_eq!(types.serialized_len(expr, &data).unwrap(), data.len());
+        let limits = DecodeLimits {
+            max_depth: 8,
+            ..default!()
+        };
+        assert!(matches!(
+            types.serialized_len_with(expr, &data, limits).unwrap_err(),
+            Error::DepthLimit(8)
+        ));
+        assert!(matches!(
+            types.skip_type_with(expr, &data[..], limits).unwrap_err(),
+            Error::DepthLimit(8)
+        ));
+    }
+}
@@ -0,0 +1,219 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::{env, fs};
+
+const STL_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/stl");
+const STD_LIB: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/stl/Std@0.1.0.stl");
+const ST_LIB: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/stl/StrictTypes@0.2.0.stl");
+
+fn run(args: &[&str]) -> Output { run_with_input(args, b"") }
+
+fn run_with_input(args: &[&str], input: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_strict-types"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    assert_eq!(output.status.code(), Some(0), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn failure(output: &Output, code: i32) -> String {
+    assert_eq!(output.status.code(), Some(code));
+    assert!(output.stdout.is_empty());
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+fn tmp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("strict-types-cli-{}-{name}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn path_str(path: &Path) -> &str { path.to_str().unwrap() }
+
+#[test]
+fn help() {
+    let out = stdout(&run(&["--help"]));
+    assert!(out.starts_with("Usage: strict-types <COMMAND> [ARGS]"));
+    assert!(!out.contains('\\'));
+}
+
+#[test]
+fn inspect() {
+    let out = stdout(&run(&["inspect", ST_LIB]));
+    assert!(out.starts_with("Id: stl:"));
+    assert!(out.contains("Name: StrictTypes\n"));
+    assert!(out.contains("Dependencies:\n  Std#"));
+    assert!(out.contains("\n  TypeLib "));
+}
+
+#[test]
+fn convert() {
+    let dir = tmp_dir("convert");
+    let sty = dir.join("Std.sty");
+    stdout(&run(&["convert", STD_LIB, path_str(&sty)]));
+    assert!(fs::read_to_string(&sty).unwrap().contains("typelib Std"));
+    assert_eq!(stdout(&run(&["inspect", path_str(&sty)])), stdout(&run(&["inspect", STD_LIB])));
+
+    let err = failure(&run(&["convert", STD_LIB, path_str(&dir.join("Std.txt"))]), 2);
+    assert!(err.starts_with("Error: unknown name for the file format 'txt'"), "{err}");
+}
+
+#[test]
+fn link() {
+    let out = stdout(&run(&["link", STL_DIR]));
+    assert!(out.contains("StrictTypes.TypeLib"));
+    assert_eq!(stdout(&run(&["link", ST_LIB])), out);
+
+    let sts = tmp_dir("link").join("types.sts");
+    stdout(&run(&["link", STL_DIR, path_str(&sts)]));
+    assert_eq!(stdout(&run(&["link", path_str(&sts)])), out);
+}
+
+#[test]
+fn tree() {
+    let out = stdout(&run(&["tree", STL_DIR, "StrictTypes.TypeName"]));
+    assert_eq!(out, "_ ascii aka=TypeName first=AlphaCapsLodash rest=AlphaNumLodash len=1..100\n");
+    assert_eq!(stdout(&run(&["vesper", STL_DIR, "StrictTypes.TypeName"])), out);
+
+    let err = failure(&run(&["tree", STL_DIR, "StrictTypes.Absent"]), 1);
+    assert_eq!(err, "Error: unknown type `StrictTypes.Absent`\n");
+}
+
+#[test]
+fn encode_decode() {
+    let data = stdout(&run_with_input(&["encode", STL_DIR, "Std.Bool"], b"true"));
+    assert_eq!(data.as_bytes(), &[1]);
+    let out = run_with_input(&["encode", STL_DIR, "StrictTypes.TypeName"], br#""Name""#);
+    assert_eq!(out.stdout, b"\x04Name");
+    let out = stdout(&run_with_input(&["decode", STL_DIR, "StrictTypes.TypeName"], &out.stdout));
+    assert_eq!(out, "(\"Name\")\n");
+
+    let dir = tmp_dir("encode");
+    let input = dir.join("value.ston");
+    let output = dir.join("value.bin");
+    fs::write(&input, "false").unwrap();
+    stdout(&run(&["encode", STL_DIR, "Std.Bool", path_str(&input), path_str(&output)]));
+    assert_eq!(fs::read(&output).unwrap(), [0]);
+    assert_eq!(stdout(&run(&["decode", STL_DIR, "Std.Bool", path_str(&output)])), "0\n");
+
+    let err = failure(&run_with_input(&["decode", STL_DIR, "Std.Bool"], &[2]), 1);
+    assert!(err.starts_with("Error: unable to decode data. Details: "), "{err}");
+    let err = failure(&run_with_input(&["encode", STL_DIR, "Std.Bool"], b"maybe"), 1);
+    assert!(err.starts_with("Error: invalid input value. Details: "), "{err}");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn formats() {
+    let args = ["decode", "--format", "json", STL_DIR, "StrictTypes.TypeName"];
+    assert_eq!(stdout(&run_with_input(&args, b"\x04Name")), "\"Name\"\n");
+    let args = ["encode", "--format", "yaml", STL_DIR, "Std.Bool"];
+    assert_eq!(stdout(&run_with_input(&args, b"true")).as_bytes(), &[1]);
+}
+
+#[test]
+fn stl() {
+    let dir = tmp_dir("stl");
+    stdout(&run(&["stl", path_str(&dir)]));
+    for file in ["Std@0.1.0.stl", "Std@0.1.0.sty", "StrictTypes@0.2.0.stl", "TypeLib.vesper"] {
+        assert_eq!(
+            fs::read(dir.join(file)).unwrap(),
+            fs::read(Path::new(STL_DIR).join(file)).unwrap(),
+            "{file}"
+        );
+    }
+}
+
+#[test]
+fn rustgen() {
+    let out = stdout(&run(&["rustgen", "--extern", "Std=strict_encoding::stl", ST_LIB]));
+    assert!(out.starts_with("// Rust types for the `StrictTypes` strict type library."));
+    assert!(out.contains("pub struct TypeLib {"));
+    assert!(!out.contains("pub enum Bool {"));
+
+    let dir = tmp_dir("rustgen");
+    let sts = dir.join("types.sts");
+    let rs = dir.join("types.rs");
+    stdout(&run(&["link", STL_DIR, path_str(&sts)]));
+    stdout(&run(&["rustgen", path_str(&sts), path_str(&rs)]));
+    let code = fs::read_to_string(&rs).unwrap();
+    assert!(code.contains("pub mod std_ {"), "{code}");
+    assert!(code.contains("pub mod strict_types {"));
+}
+
+#[test]
+fn usage_errors() {
+    for (args, msg) in [
+        (&[][..], "no command given"),
+        (&["unknown"], "unknown command `unknown`"),
+        (&["inspect"], "invalid number of arguments for `inspect`"),
+        (&["decode", STL_DIR], "invalid number of arguments for `decode`"),
+        (&["inspect", ST_LIB, "--verbose"], "unknown option `--verbose`"),
+        (
+            &["inspect", ST_LIB, "--format", "ston"],
+            "command `inspect` doesn't accept `--format` option",
+        ),
+        (&["decode", STL_DIR, "Std.Bool", "--format", "xml"], "unsupported value format `xml`"),
+        (
+            &["link", STL_DIR, "--extern", "Std=std"],
+            "command `link` doesn't accept `--extern` option",
+        ),
+        (&["rustgen", ST_LIB, "--extern", "Std"], "invalid `--extern` value `Std`"),
+        (&["decode", STL_DIR, "Std..Bool"], "invalid type `Std..Bool`"),
+        (&["inspect", "Std"], "file name `Std` has no extension specifying library format"),
+    ] {
+        let err = failure(&run(args), 2);
+        assert!(err.starts_with(&format!("Error: {msg}")), "{err}");
+        assert!(err.contains("\n\nUsage: strict-types <COMMAND> [ARGS]\n"));
+    }
+}
+
+#[test]
+fn io_errors() {
+    let missing = tmp_dir("io").join("Missing.stl");
+    let err = failure(&run(&["inspect", path_str(&missing)]), 1);
+    assert!(err.starts_with(&format!("Error: unable to load library `{}`", missing.display())));
+    assert!(!err.contains("invalid binary library data"), "{err}");
+    let io_err = fs::read(&missing).unwrap_err();
+    assert!(err.trim_end().ends_with(&io_err.to_string()), "{err}");
+
+    let missing = missing.with_extension("sts");
+    let err = failure(&run(&["link", path_str(&missing)]), 1);
+    assert!(err.starts_with(&format!("Error: unable to load type system `{}`", missing.display())));
+    assert!(err.trim_end().ends_with(&io_err.to_string()), "{err}");
+
+    let err = failure(&run(&["decode", STL_DIR, "Std.Bool", path_str(&missing)]), 1);
+    assert!(err.starts_with("Error: unable to read input data. Details: "), "{err}");
+}
@@ -0,0 +1,1984 @@
+// Rust types for the strict type system sts:7doJJwVV-gnts3pu-OPFp5t3-MekHgFX-Uq7Kek1-q3GFmcg#analog-quiet-stretch.
+//
+// Generated by the strict types Rust code generator; do not edit manually.
+//
+// The code requires `strict_encoding` macros to be imported into the crate root with
+// `#[macro_use] extern crate strict_encoding;`. Dumb value expressions in field attributes
+// trigger false `unused_braces` warnings, which should be allowed.
+
+pub mod codegen_test {
+    use amplify::confinement::{Confined, NonEmptyOrdMap, SmallOrdSet, TinyString, U8};
+    use amplify::num::{u24, u256};
+    use amplify::num::apfloat::ieee;
+    use strict_encoding::{StrictDecode, StrictDumb, StrictEncode, StrictType};
+
+    pub const LIB_NAME_CODEGEN_TEST: &str = "CodegenTest";
+
+    #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_CODEGEN_TEST)]
+    pub struct Account {
+        pub id: [u8; 32],
+        pub name: Name,
+        pub alias: Option<Name>,
+        pub balance: u64,
+        pub flags: Flags,
+        #[strict_type(dumb = { Confined::try_from_iter((0..1).map(|_| (strict_dumb!(), strict_dumb!()))).expect("dumb value") })]
+        pub limits: NonEmptyOrdMap<Purpose, u256, U8>,
+        pub keys: SmallOrdSet<Flags>,
+        pub active: super::std_::Bool,
+        pub kind: Kind,
+        pub memo: TinyString,
+    }
+
+    #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_CODEGEN_TEST)]
+    pub struct Flags(pub [u16; 4]);
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_CODEGEN_TEST, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum Kind {
+        #[strict_type(dumb)]
+        Unknown = 0,
+        Personal = 1,
+        Business = 2,
+        Other = 255,
+    }
+
+    #[derive(Clone, PartialEq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_CODEGEN_TEST)]
+    pub struct Measure {
+        pub value: ieee::Double,
+        pub scale: Option<ieee::Single>,
+        #[strict_type(dumb = { Confined::try_from_iter((0..2).map(|_| strict_dumb!())).expect("dumb value") })]
+        pub history: Confined<Vec<(i8, u128)>, 2, 10>,
+    }
+
+    #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_CODEGEN_TEST)]
+    pub struct Name(pub super::std_::AsciiPrintable, pub Confined<Vec<super::std_::AsciiPrintable>, 0, 63>);
+
+    #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_CODEGEN_TEST)]
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+        pub z: i32,
+    }
+
+    #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_CODEGEN_TEST, tags = custom)]
+    pub enum Purpose {
+        #[strict_type(tag = 0, dumb)]
+        Payment,
+        #[strict_type(tag = 10)]
+        Withdrawal,
+        #[strict_type(tag = 11)]
+        Transfer(u16, u24),
+        #[strict_type(tag = 12)]
+        Custom {
+            name: Name,
+            value: u8,
+        },
+    }
+
+    /// Compiles `CodegenTest` type library from the types defined in this module.
+    pub fn codegen_test_stl() -> strict_types::TypeLib {
+        strict_types::LibBuilder::new(LIB_NAME_CODEGEN_TEST, [
+            strict_types::Dependency::with("stl:yiweb4OZ-3TAMPm!-eUS$XRw-iMgF32K-DbZZJX5-xmwCVCc#ralph-blue-lucky".parse().expect("valid library id"), "Std".into()),
+        ])
+        .transpile::<Account>()
+        .transpile::<Flags>()
+        .transpile::<Kind>()
+        .transpile::<Measure>()
+        .transpile::<Name>()
+        .transpile::<Point>()
+        .transpile::<Purpose>()
+        .compile()
+        .expect("invalid `CodegenTest` type library")
+    }
+}
+
+pub mod std_ {
+    use strict_encoding::{StrictDecode, StrictDumb, StrictEncode, StrictType};
+
+    pub const LIB_NAME_STD: &str = "Std";
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum Alpha {
+        #[strict_type(dumb, rename = "_A")]
+        A = 65,
+        #[strict_type(rename = "_B")]
+        B = 66,
+        #[strict_type(rename = "_C")]
+        C = 67,
+        #[strict_type(rename = "_D")]
+        D = 68,
+        #[strict_type(rename = "_E")]
+        E = 69,
+        #[strict_type(rename = "_F")]
+        F = 70,
+        #[strict_type(rename = "_G")]
+        G = 71,
+        #[strict_type(rename = "_H")]
+        H = 72,
+        #[strict_type(rename = "_I")]
+        I = 73,
+        #[strict_type(rename = "_J")]
+        J = 74,
+        #[strict_type(rename = "_K")]
+        K = 75,
+        #[strict_type(rename = "_L")]
+        L = 76,
+        #[strict_type(rename = "_M")]
+        M = 77,
+        #[strict_type(rename = "_N")]
+        N = 78,
+        #[strict_type(rename = "_O")]
+        O = 79,
+        #[strict_type(rename = "_P")]
+        P = 80,
+        #[strict_type(rename = "_Q")]
+        Q = 81,
+        #[strict_type(rename = "_R")]
+        R = 82,
+        #[strict_type(rename = "_S")]
+        S = 83,
+        #[strict_type(rename = "_T")]
+        T = 84,
+        #[strict_type(rename = "_U")]
+        U = 85,
+        #[strict_type(rename = "_V")]
+        V = 86,
+        #[strict_type(rename = "_W")]
+        W = 87,
+        #[strict_type(rename = "_X")]
+        X = 88,
+        #[strict_type(rename = "_Y")]
+        Y = 89,
+        #[strict_type(rename = "_Z")]
+        Z = 90,
+        #[strict_type(rename = "a")]
+        A97 = 97,
+        #[strict_type(rename = "b")]
+        B98 = 98,
+        #[strict_type(rename = "c")]
+        C99 = 99,
+        #[strict_type(rename = "d")]
+        D100 = 100,
+        #[strict_type(rename = "e")]
+        E101 = 101,
+        #[strict_type(rename = "f")]
+        F102 = 102,
+        #[strict_type(rename = "g")]
+        G103 = 103,
+        #[strict_type(rename = "h")]
+        H104 = 104,
+        #[strict_type(rename = "i")]
+        I105 = 105,
+        #[strict_type(rename = "j")]
+        J106 = 106,
+        #[strict_type(rename = "k")]
+        K107 = 107,
+        #[strict_type(rename = "l")]
+        L108 = 108,
+        #[strict_type(rename = "m")]
+        M109 = 109,
+        #[strict_type(rename = "n")]
+        N110 = 110,
+        #[strict_type(rename = "o")]
+        O111 = 111,
+        #[strict_type(rename = "p")]
+        P112 = 112,
+        #[strict_type(rename = "q")]
+        Q113 = 113,
+        #[strict_type(rename = "r")]
+        R114 = 114,
+        #[strict_type(rename = "s")]
+        S115 = 115,
+        #[strict_type(rename = "t")]
+        T116 = 116,
+        #[strict_type(rename = "u")]
+        U117 = 117,
+        #[strict_type(rename = "v")]
+        V118 = 118,
+        #[strict_type(rename = "w")]
+        W119 = 119,
+        #[strict_type(rename = "x")]
+        X120 = 120,
+        #[strict_type(rename = "y")]
+        Y121 = 121,
+        #[strict_type(rename = "z")]
+        Z122 = 122,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum AlphaCaps {
+        #[strict_type(dumb, rename = "_A")]
+        A = 65,
+        #[strict_type(rename = "_B")]
+        B = 66,
+        #[strict_type(rename = "_C")]
+        C = 67,
+        #[strict_type(rename = "_D")]
+        D = 68,
+        #[strict_type(rename = "_E")]
+        E = 69,
+        #[strict_type(rename = "_F")]
+        F = 70,
+        #[strict_type(rename = "_G")]
+        G = 71,
+        #[strict_type(rename = "_H")]
+        H = 72,
+        #[strict_type(rename = "_I")]
+        I = 73,
+        #[strict_type(rename = "_J")]
+        J = 74,
+        #[strict_type(rename = "_K")]
+        K = 75,
+        #[strict_type(rename = "_L")]
+        L = 76,
+        #[strict_type(rename = "_M")]
+        M = 77,
+        #[strict_type(rename = "_N")]
+        N = 78,
+        #[strict_type(rename = "_O")]
+        O = 79,
+        #[strict_type(rename = "_P")]
+        P = 80,
+        #[strict_type(rename = "_Q")]
+        Q = 81,
+        #[strict_type(rename = "_R")]
+        R = 82,
+        #[strict_type(rename = "_S")]
+        S = 83,
+        #[strict_type(rename = "_T")]
+        T = 84,
+        #[strict_type(rename = "_U")]
+        U = 85,
+        #[strict_type(rename = "_V")]
+        V = 86,
+        #[strict_type(rename = "_W")]
+        W = 87,
+        #[strict_type(rename = "_X")]
+        X = 88,
+        #[strict_type(rename = "_Y")]
+        Y = 89,
+        #[strict_type(rename = "_Z")]
+        Z = 90,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum AlphaCapsLodash {
+        #[strict_type(dumb, rename = "_A")]
+        A = 65,
+        #[strict_type(rename = "_B")]
+        B = 66,
+        #[strict_type(rename = "_C")]
+        C = 67,
+        #[strict_type(rename = "_D")]
+        D = 68,
+        #[strict_type(rename = "_E")]
+        E = 69,
+        #[strict_type(rename = "_F")]
+        F = 70,
+        #[strict_type(rename = "_G")]
+        G = 71,
+        #[strict_type(rename = "_H")]
+        H = 72,
+        #[strict_type(rename = "_I")]
+        I = 73,
+        #[strict_type(rename = "_J")]
+        J = 74,
+        #[strict_type(rename = "_K")]
+        K = 75,
+        #[strict_type(rename = "_L")]
+        L = 76,
+        #[strict_type(rename = "_M")]
+        M = 77,
+        #[strict_type(rename = "_N")]
+        N = 78,
+        #[strict_type(rename = "_O")]
+        O = 79,
+        #[strict_type(rename = "_P")]
+        P = 80,
+        #[strict_type(rename = "_Q")]
+        Q = 81,
+        #[strict_type(rename = "_R")]
+        R = 82,
+        #[strict_type(rename = "_S")]
+        S = 83,
+        #[strict_type(rename = "_T")]
+        T = 84,
+        #[strict_type(rename = "_U")]
+        U = 85,
+        #[strict_type(rename = "_V")]
+        V = 86,
+        #[strict_type(rename = "_W")]
+        W = 87,
+        #[strict_type(rename = "_X")]
+        X = 88,
+        #[strict_type(rename = "_Y")]
+        Y = 89,
+        #[strict_type(rename = "_Z")]
+        Z = 90,
+        Lodash = 95,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum AlphaCapsNum {
+        #[strict_type(dumb)]
+        Zero = 48,
+        One = 49,
+        Two = 50,
+        Three = 51,
+        Four = 52,
+        Five = 53,
+        Six = 54,
+        Seven = 55,
+        Eight = 56,
+        Nine = 57,
+        #[strict_type(rename = "_A")]
+        A = 65,
+        #[strict_type(rename = "_B")]
+        B = 66,
+        #[strict_type(rename = "_C")]
+        C = 67,
+        #[strict_type(rename = "_D")]
+        D = 68,
+        #[strict_type(rename = "_E")]
+        E = 69,
+        #[strict_type(rename = "_F")]
+        F = 70,
+        #[strict_type(rename = "_G")]
+        G = 71,
+        #[strict_type(rename = "_H")]
+        H = 72,
+        #[strict_type(rename = "_I")]
+        I = 73,
+        #[strict_type(rename = "_J")]
+        J = 74,
+        #[strict_type(rename = "_K")]
+        K = 75,
+        #[strict_type(rename = "_L")]
+        L = 76,
+        #[strict_type(rename = "_M")]
+        M = 77,
+        #[strict_type(rename = "_N")]
+        N = 78,
+        #[strict_type(rename = "_O")]
+        O = 79,
+        #[strict_type(rename = "_P")]
+        P = 80,
+        #[strict_type(rename = "_Q")]
+        Q = 81,
+        #[strict_type(rename = "_R")]
+        R = 82,
+        #[strict_type(rename = "_S")]
+        S = 83,
+        #[strict_type(rename = "_T")]
+        T = 84,
+        #[strict_type(rename = "_U")]
+        U = 85,
+        #[strict_type(rename = "_V")]
+        V = 86,
+        #[strict_type(rename = "_W")]
+        W = 87,
+        #[strict_type(rename = "_X")]
+        X = 88,
+        #[strict_type(rename = "_Y")]
+        Y = 89,
+        #[strict_type(rename = "_Z")]
+        Z = 90,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum AlphaLodash {
+        #[strict_type(dumb, rename = "_A")]
+        A = 65,
+        #[strict_type(rename = "_B")]
+        B = 66,
+        #[strict_type(rename = "_C")]
+        C = 67,
+        #[strict_type(rename = "_D")]
+        D = 68,
+        #[strict_type(rename = "_E")]
+        E = 69,
+        #[strict_type(rename = "_F")]
+        F = 70,
+        #[strict_type(rename = "_G")]
+        G = 71,
+        #[strict_type(rename = "_H")]
+        H = 72,
+        #[strict_type(rename = "_I")]
+        I = 73,
+        #[strict_type(rename = "_J")]
+        J = 74,
+        #[strict_type(rename = "_K")]
+        K = 75,
+        #[strict_type(rename = "_L")]
+        L = 76,
+        #[strict_type(rename = "_M")]
+        M = 77,
+        #[strict_type(rename = "_N")]
+        N = 78,
+        #[strict_type(rename = "_O")]
+        O = 79,
+        #[strict_type(rename = "_P")]
+        P = 80,
+        #[strict_type(rename = "_Q")]
+        Q = 81,
+        #[strict_type(rename = "_R")]
+        R = 82,
+        #[strict_type(rename = "_S")]
+        S = 83,
+        #[strict_type(rename = "_T")]
+        T = 84,
+        #[strict_type(rename = "_U")]
+        U = 85,
+        #[strict_type(rename = "_V")]
+        V = 86,
+        #[strict_type(rename = "_W")]
+        W = 87,
+        #[strict_type(rename = "_X")]
+        X = 88,
+        #[strict_type(rename = "_Y")]
+        Y = 89,
+        #[strict_type(rename = "_Z")]
+        Z = 90,
+        Lodash = 95,
+        #[strict_type(rename = "a")]
+        A97 = 97,
+        #[strict_type(rename = "b")]
+        B98 = 98,
+        #[strict_type(rename = "c")]
+        C99 = 99,
+        #[strict_type(rename = "d")]
+        D100 = 100,
+        #[strict_type(rename = "e")]
+        E101 = 101,
+        #[strict_type(rename = "f")]
+        F102 = 102,
+        #[strict_type(rename = "g")]
+        G103 = 103,
+        #[strict_type(rename = "h")]
+        H104 = 104,
+        #[strict_type(rename = "i")]
+        I105 = 105,
+        #[strict_type(rename = "j")]
+        J106 = 106,
+        #[strict_type(rename = "k")]
+        K107 = 107,
+        #[strict_type(rename = "l")]
+        L108 = 108,
+        #[strict_type(rename = "m")]
+        M109 = 109,
+        #[strict_type(rename = "n")]
+        N110 = 110,
+        #[strict_type(rename = "o")]
+        O111 = 111,
+        #[strict_type(rename = "p")]
+        P112 = 112,
+        #[strict_type(rename = "q")]
+        Q113 = 113,
+        #[strict_type(rename = "r")]
+        R114 = 114,
+        #[strict_type(rename = "s")]
+        S115 = 115,
+        #[strict_type(rename = "t")]
+        T116 = 116,
+        #[strict_type(rename = "u")]
+        U117 = 117,
+        #[strict_type(rename = "v")]
+        V118 = 118,
+        #[strict_type(rename = "w")]
+        W119 = 119,
+        #[strict_type(rename = "x")]
+        X120 = 120,
+        #[strict_type(rename = "y")]
+        Y121 = 121,
+        #[strict_type(rename = "z")]
+        Z122 = 122,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum AlphaNum {
+        #[strict_type(dumb)]
+        Zero = 48,
+        One = 49,
+        Two = 50,
+        Three = 51,
+        Four = 52,
+        Five = 53,
+        Six = 54,
+        Seven = 55,
+        Eight = 56,
+        Nine = 57,
+        #[strict_type(rename = "_A")]
+        A = 65,
+        #[strict_type(rename = "_B")]
+        B = 66,
+        #[strict_type(rename = "_C")]
+        C = 67,
+        #[strict_type(rename = "_D")]
+        D = 68,
+        #[strict_type(rename = "_E")]
+        E = 69,
+        #[strict_type(rename = "_F")]
+        F = 70,
+        #[strict_type(rename = "_G")]
+        G = 71,
+        #[strict_type(rename = "_H")]
+        H = 72,
+        #[strict_type(rename = "_I")]
+        I = 73,
+        #[strict_type(rename = "_J")]
+        J = 74,
+        #[strict_type(rename = "_K")]
+        K = 75,
+        #[strict_type(rename = "_L")]
+        L = 76,
+        #[strict_type(rename = "_M")]
+        M = 77,
+        #[strict_type(rename = "_N")]
+        N = 78,
+        #[strict_type(rename = "_O")]
+        O = 79,
+        #[strict_type(rename = "_P")]
+        P = 80,
+        #[strict_type(rename = "_Q")]
+        Q = 81,
+        #[strict_type(rename = "_R")]
+        R = 82,
+        #[strict_type(rename = "_S")]
+        S = 83,
+        #[strict_type(rename = "_T")]
+        T = 84,
+        #[strict_type(rename = "_U")]
+        U = 85,
+        #[strict_type(rename = "_V")]
+        V = 86,
+        #[strict_type(rename = "_W")]
+        W = 87,
+        #[strict_type(rename = "_X")]
+        X = 88,
+        #[strict_type(rename = "_Y")]
+        Y = 89,
+        #[strict_type(rename = "_Z")]
+        Z = 90,
+        #[strict_type(rename = "a")]
+        A97 = 97,
+        #[strict_type(rename = "b")]
+        B98 = 98,
+        #[strict_type(rename = "c")]
+        C99 = 99,
+        #[strict_type(rename = "d")]
+        D100 = 100,
+        #[strict_type(rename = "e")]
+        E101 = 101,
+        #[strict_type(rename = "f")]
+        F102 = 102,
+        #[strict_type(rename = "g")]
+        G103 = 103,
+        #[strict_type(rename = "h")]
+        H104 = 104,
+        #[strict_type(rename = "i")]
+        I105 = 105,
+        #[strict_type(rename = "j")]
+        J106 = 106,
+        #[strict_type(rename = "k")]
+        K107 = 107,
+        #[strict_type(rename = "l")]
+        L108 = 108,
+        #[strict_type(rename = "m")]
+        M109 = 109,
+        #[strict_type(rename = "n")]
+        N110 = 110,
+        #[strict_type(rename = "o")]
+        O111 = 111,
+        #[strict_type(rename = "p")]
+        P112 = 112,
+        #[strict_type(rename = "q")]
+        Q113 = 113,
+        #[strict_type(rename = "r")]
+        R114 = 114,
+        #[strict_type(rename = "s")]
+        S115 = 115,
+        #[strict_type(rename = "t")]
+        T116 = 116,
+        #[strict_type(rename = "u")]
+        U117 = 117,
+        #[strict_type(rename = "v")]
+        V118 = 118,
+        #[strict_type(rename = "w")]
+        W119 = 119,
+        #[strict_type(rename = "x")]
+        X120 = 120,
+        #[strict_type(rename = "y")]
+        Y121 = 121,
+        #[strict_type(rename = "z")]
+        Z122 = 122,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum AlphaNumDash {
+        #[strict_type(dumb)]
+        Dash = 45,
+        Zero = 48,
+        One = 49,
+        Two = 50,
+        Three = 51,
+        Four = 52,
+        Five = 53,
+        Six = 54,
+        Seven = 55,
+        Eight = 56,
+        Nine = 57,
+        #[strict_type(rename = "_A")]
+        A = 65,
+        #[strict_type(rename = "_B")]
+        B = 66,
+        #[strict_type(rename = "_C")]
+        C = 67,
+        #[strict_type(rename = "_D")]
+        D = 68,
+        #[strict_type(rename = "_E")]
+        E = 69,
+        #[strict_type(rename = "_F")]
+        F = 70,
+        #[strict_type(rename = "_G")]
+        G = 71,
+        #[strict_type(rename = "_H")]
+        H = 72,
+        #[strict_type(rename = "_I")]
+        I = 73,
+        #[strict_type(rename = "_J")]
+        J = 74,
+        #[strict_type(rename = "_K")]
+        K = 75,
+        #[strict_type(rename = "_L")]
+        L = 76,
+        #[strict_type(rename = "_M")]
+        M = 77,
+        #[strict_type(rename = "_N")]
+        N = 78,
+        #[strict_type(rename = "_O")]
+        O = 79,
+        #[strict_type(rename = "_P")]
+        P = 80,
+        #[strict_type(rename = "_Q")]
+        Q = 81,
+        #[strict_type(rename = "_R")]
+        R = 82,
+        #[strict_type(rename = "_S")]
+        S = 83,
+        #[strict_type(rename = "_T")]
+        T = 84,
+        #[strict_type(rename = "_U")]
+        U = 85,
+        #[strict_type(rename = "_V")]
+        V = 86,
+        #[strict_type(rename = "_W")]
+        W = 87,
+        #[strict_type(rename = "_X")]
+        X = 88,
+        #[strict_type(rename = "_Y")]
+        Y = 89,
+        #[strict_type(rename = "_Z")]
+        Z = 90,
+        #[strict_type(rename = "a")]
+        A97 = 97,
+        #[strict_type(rename = "b")]
+        B98 = 98,
+        #[strict_type(rename = "c")]
+        C99 = 99,
+        #[strict_type(rename = "d")]
+        D100 = 100,
+        #[strict_type(rename = "e")]
+        E101 = 101,
+        #[strict_type(rename = "f")]
+        F102 = 102,
+        #[strict_type(rename = "g")]
+        G103 = 103,
+        #[strict_type(rename = "h")]
+        H104 = 104,
+        #[strict_type(rename = "i")]
+        I105 = 105,
+        #[strict_type(rename = "j")]
+        J106 = 106,
+        #[strict_type(rename = "k")]
+        K107 = 107,
+        #[strict_type(rename = "l")]
+        L108 = 108,
+        #[strict_type(rename = "m")]
+        M109 = 109,
+        #[strict_type(rename = "n")]
+        N110 = 110,
+        #[strict_type(rename = "o")]
+        O111 = 111,
+        #[strict_type(rename = "p")]
+        P112 = 112,
+        #[strict_type(rename = "q")]
+        Q113 = 113,
+        #[strict_type(rename = "r")]
+        R114 = 114,
+        #[strict_type(rename = "s")]
+        S115 = 115,
+        #[strict_type(rename = "t")]
+        T116 = 116,
+        #[strict_type(rename = "u")]
+        U117 = 117,
+        #[strict_type(rename = "v")]
+        V118 = 118,
+        #[strict_type(rename = "w")]
+        W119 = 119,
+        #[strict_type(rename = "x")]
+        X120 = 120,
+        #[strict_type(rename = "y")]
+        Y121 = 121,
+        #[strict_type(rename = "z")]
+        Z122 = 122,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum AlphaNumLodash {
+        #[strict_type(dumb)]
+        Zero = 48,
+        One = 49,
+        Two = 50,
+        Three = 51,
+        Four = 52,
+        Five = 53,
+        Six = 54,
+        Seven = 55,
+        Eight = 56,
+        Nine = 57,
+        #[strict_type(rename = "_A")]
+        A = 65,
+        #[strict_type(rename = "_B")]
+        B = 66,
+        #[strict_type(rename = "_C")]
+        C = 67,
+        #[strict_type(rename = "_D")]
+        D = 68,
+        #[strict_type(rename = "_E")]
+        E = 69,
+        #[strict_type(rename = "_F")]
+        F = 70,
+        #[strict_type(rename = "_G")]
+        G = 71,
+        #[strict_type(rename = "_H")]
+        H = 72,
+        #[strict_type(rename = "_I")]
+        I = 73,
+        #[strict_type(rename = "_J")]
+        J = 74,
+        #[strict_type(rename = "_K")]
+        K = 75,
+        #[strict_type(rename = "_L")]
+        L = 76,
+        #[strict_type(rename = "_M")]
+        M = 77,
+        #[strict_type(rename = "_N")]
+        N = 78,
+        #[strict_type(rename = "_O")]
+        O = 79,
+        #[strict_type(rename = "_P")]
+        P = 80,
+        #[strict_type(rename = "_Q")]
+        Q = 81,
+        #[strict_type(rename = "_R")]
+        R = 82,
+        #[strict_type(rename = "_S")]
+        S = 83,
+        #[strict_type(rename = "_T")]
+        T = 84,
+        #[strict_type(rename = "_U")]
+        U = 85,
+        #[strict_type(rename = "_V")]
+        V = 86,
+        #[strict_type(rename = "_W")]
+        W = 87,
+        #[strict_type(rename = "_X")]
+        X = 88,
+        #[strict_type(rename = "_Y")]
+        Y = 89,
+        #[strict_type(rename = "_Z")]
+        Z = 90,
+        Lodash = 95,
+        #[strict_type(rename = "a")]
+        A97 = 97,
+        #[strict_type(rename = "b")]
+        B98 = 98,
+        #[strict_type(rename = "c")]
+        C99 = 99,
+        #[strict_type(rename = "d")]
+        D100 = 100,
+        #[strict_type(rename = "e")]
+        E101 = 101,
+        #[strict_type(rename = "f")]
+        F102 = 102,
+        #[strict_type(rename = "g")]
+        G103 = 103,
+        #[strict_type(rename = "h")]
+        H104 = 104,
+        #[strict_type(rename = "i")]
+        I105 = 105,
+        #[strict_type(rename = "j")]
+        J106 = 106,
+        #[strict_type(rename = "k")]
+        K107 = 107,
+        #[strict_type(rename = "l")]
+        L108 = 108,
+        #[strict_type(rename = "m")]
+        M109 = 109,
+        #[strict_type(rename = "n")]
+        N110 = 110,
+        #[strict_type(rename = "o")]
+        O111 = 111,
+        #[strict_type(rename = "p")]
+        P112 = 112,
+        #[strict_type(rename = "q")]
+        Q113 = 113,
+        #[strict_type(rename = "r")]
+        R114 = 114,
+        #[strict_type(rename = "s")]
+        S115 = 115,
+        #[strict_type(rename = "t")]
+        T116 = 116,
+        #[strict_type(rename = "u")]
+        U117 = 117,
+        #[strict_type(rename = "v")]
+        V118 = 118,
+        #[strict_type(rename = "w")]
+        W119 = 119,
+        #[strict_type(rename = "x")]
+        X120 = 120,
+        #[strict_type(rename = "y")]
+        Y121 = 121,
+        #[strict_type(rename = "z")]
+        Z122 = 122,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum AlphaSmall {
+        #[strict_type(dumb)]
+        A = 97,
+        B = 98,
+        C = 99,
+        D = 100,
+        E = 101,
+        F = 102,
+        G = 103,
+        H = 104,
+        I = 105,
+        J = 106,
+        K = 107,
+        L = 108,
+        M = 109,
+        N = 110,
+        O = 111,
+        P = 112,
+        Q = 113,
+        R = 114,
+        S = 115,
+        T = 116,
+        U = 117,
+        V = 118,
+        W = 119,
+        X = 120,
+        Y = 121,
+        Z = 122,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum AlphaSmallLodash {
+        #[strict_type(dumb)]
+        Lodash = 95,
+        A = 97,
+        B = 98,
+        C = 99,
+        D = 100,
+        E = 101,
+        F = 102,
+        G = 103,
+        H = 104,
+        I = 105,
+        J = 106,
+        K = 107,
+        L = 108,
+        M = 109,
+        N = 110,
+        O = 111,
+        P = 112,
+        Q = 113,
+        R = 114,
+        S = 115,
+        T = 116,
+        U = 117,
+        V = 118,
+        W = 119,
+        X = 120,
+        Y = 121,
+        Z = 122,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum Ascii {
+        #[strict_type(dumb)]
+        Nul = 0,
+        Soh = 1,
+        Stx = 2,
+        Etx = 3,
+        Eot = 4,
+        Enq = 5,
+        Ack = 6,
+        Bel = 7,
+        Bs = 8,
+        Ht = 9,
+        Lf = 10,
+        Vt = 11,
+        Ff = 12,
+        Cr = 13,
+        So = 14,
+        Si = 15,
+        Dle = 16,
+        #[strict_type(rename = "dc1")]
+        Dc1 = 17,
+        #[strict_type(rename = "dc2")]
+        Dc2 = 18,
+        #[strict_type(rename = "dc3")]
+        Dc3 = 19,
+        #[strict_type(rename = "dc4")]
+        Dc4 = 20,
+        Nack = 21,
+        Syn = 22,
+        Etb = 23,
+        Can = 24,
+        Em = 25,
+        Sub = 26,
+        Esc = 27,
+        Fs = 28,
+        Gs = 29,
+        Rs = 30,
+        Us = 31,
+        Space = 32,
+        Excl = 33,
+        Quotes = 34,
+        Hash = 35,
+        Dollar = 36,
+        Percent = 37,
+        Ampersand = 38,
+        Apostrophe = 39,
+        #[strict_type(rename = "bracketL")]
+        BracketL = 40,
+        #[strict_type(rename = "bracketR")]
+        BracketR = 41,
+        Asterisk = 42,
+        Plus = 43,
+        Comma = 44,
+        Minus = 45,
+        Dot = 46,
+        Slash = 47,
+        Zero = 48,
+        One = 49,
+        Two = 50,
+        Three = 51,
+        Four = 52,
+        Five = 53,
+        Six = 54,
+        Seven = 55,
+        Eight = 56,
+        Nine = 57,
+        Colon = 58,
+        SemiColon = 59,
+        Less = 60,
+        Equal = 61,
+        Greater = 62,
+        Question = 63,
+        At = 64,
+        #[strict_type(rename = "_A")]
+        A = 65,
+        #[strict_type(rename = "_B")]
+        B = 66,
+        #[strict_type(rename = "_C")]
+        C = 67,
+        #[strict_type(rename = "_D")]
+        D = 68,
+        #[strict_type(rename = "_E")]
+        E = 69,
+        #[strict_type(rename = "_F")]
+        F = 70,
+        #[strict_type(rename = "_G")]
+        G = 71,
+        #[strict_type(rename = "_H")]
+        H = 72,
+        #[strict_type(rename = "_I")]
+        I = 73,
+        #[strict_type(rename = "_J")]
+        J = 74,
+        #[strict_type(rename = "_K")]
+        K = 75,
+        #[strict_type(rename = "_L")]
+        L = 76,
+        #[strict_type(rename = "_M")]
+        M = 77,
+        #[strict_type(rename = "_N")]
+        N = 78,
+        #[strict_type(rename = "_O")]
+        O = 79,
+        #[strict_type(rename = "_P")]
+        P = 80,
+        #[strict_type(rename = "_Q")]
+        Q = 81,
+        #[strict_type(rename = "_R")]
+        R = 82,
+        #[strict_type(rename = "_S")]
+        S = 83,
+        #[strict_type(rename = "_T")]
+        T = 84,
+        #[strict_type(rename = "_U")]
+        U = 85,
+        #[strict_type(rename = "_V")]
+        V = 86,
+        #[strict_type(rename = "_W")]
+        W = 87,
+        #[strict_type(rename = "_X")]
+        X = 88,
+        #[strict_type(rename = "_Y")]
+        Y = 89,
+        #[strict_type(rename = "_Z")]
+        Z = 90,
+        #[strict_type(rename = "sqBracketL")]
+        SqBracketL = 91,
+        BackSlash = 92,
+        #[strict_type(rename = "sqBracketR")]
+        SqBracketR = 93,
+        Caret = 94,
+        Lodash = 95,
+        Backtick = 96,
+        #[strict_type(rename = "a")]
+        A97 = 97,
+        #[strict_type(rename = "b")]
+        B98 = 98,
+        #[strict_type(rename = "c")]
+        C99 = 99,
+        #[strict_type(rename = "d")]
+        D100 = 100,
+        #[strict_type(rename = "e")]
+        E101 = 101,
+        #[strict_type(rename = "f")]
+        F102 = 102,
+        #[strict_type(rename = "g")]
+        G103 = 103,
+        #[strict_type(rename = "h")]
+        H104 = 104,
+        #[strict_type(rename = "i")]
+        I105 = 105,
+        #[strict_type(rename = "j")]
+        J106 = 106,
+        #[strict_type(rename = "k")]
+        K107 = 107,
+        #[strict_type(rename = "l")]
+        L108 = 108,
+        #[strict_type(rename = "m")]
+        M109 = 109,
+        #[strict_type(rename = "n")]
+        N110 = 110,
+        #[strict_type(rename = "o")]
+        O111 = 111,
+        #[strict_type(rename = "p")]
+        P112 = 112,
+        #[strict_type(rename = "q")]
+        Q113 = 113,
+        #[strict_type(rename = "r")]
+        R114 = 114,
+        #[strict_type(rename = "s")]
+        S115 = 115,
+        #[strict_type(rename = "t")]
+        T116 = 116,
+        #[strict_type(rename = "u")]
+        U117 = 117,
+        #[strict_type(rename = "v")]
+        V118 = 118,
+        #[strict_type(rename = "w")]
+        W119 = 119,
+        #[strict_type(rename = "x")]
+        X120 = 120,
+        #[strict_type(rename = "y")]
+        Y121 = 121,
+        #[strict_type(rename = "z")]
+        Z122 = 122,
+        #[strict_type(rename = "cBracketL")]
+        CBracketL = 123,
+        Pipe = 124,
+        #[strict_type(rename = "cBracketR")]
+        CBracketR = 125,
+        Tilde = 126,
+        Del = 127,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum AsciiPrintable {
+        #[strict_type(dumb)]
+        Space = 32,
+        Excl = 33,
+        Quotes = 34,
+        Hash = 35,
+        Dollar = 36,
+        Percent = 37,
+        Ampersand = 38,
+        Apostrophe = 39,
+        #[strict_type(rename = "bracketL")]
+        BracketL = 40,
+        #[strict_type(rename = "bracketR")]
+        BracketR = 41,
+        Asterisk = 42,
+        Plus = 43,
+        Comma = 44,
+        Minus = 45,
+        Dot = 46,
+        Slash = 47,
+        Zero = 48,
+        One = 49,
+        Two = 50,
+        Three = 51,
+        Four = 52,
+        Five = 53,
+        Six = 54,
+        Seven = 55,
+        Eight = 56,
+        Nine = 57,
+        Colon = 58,
+        SemiColon = 59,
+        Less = 60,
+        Equal = 61,
+        Greater = 62,
+        Question = 63,
+        At = 64,
+        #[strict_type(rename = "_A")]
+        A = 65,
+        #[strict_type(rename = "_B")]
+        B = 66,
+        #[strict_type(rename = "_C")]
+        C = 67,
+        #[strict_type(rename = "_D")]
+        D = 68,
+        #[strict_type(rename = "_E")]
+        E = 69,
+        #[strict_type(rename = "_F")]
+        F = 70,
+        #[strict_type(rename = "_G")]
+        G = 71,
+        #[strict_type(rename = "_H")]
+        H = 72,
+        #[strict_type(rename = "_I")]
+        I = 73,
+        #[strict_type(rename = "_J")]
+        J = 74,
+        #[strict_type(rename = "_K")]
+        K = 75,
+        #[strict_type(rename = "_L")]
+        L = 76,
+        #[strict_type(rename = "_M")]
+        M = 77,
+        #[strict_type(rename = "_N")]
+        N = 78,
+        #[strict_type(rename = "_O")]
+        O = 79,
+        #[strict_type(rename = "_P")]
+        P = 80,
+        #[strict_type(rename = "_Q")]
+        Q = 81,
+        #[strict_type(rename = "_R")]
+        R = 82,
+        #[strict_type(rename = "_S")]
+        S = 83,
+        #[strict_type(rename = "_T")]
+        T = 84,
+        #[strict_type(rename = "_U")]
+        U = 85,
+        #[strict_type(rename = "_V")]
+        V = 86,
+        #[strict_type(rename = "_W")]
+        W = 87,
+        #[strict_type(rename = "_X")]
+        X = 88,
+        #[strict_type(rename = "_Y")]
+        Y = 89,
+        #[strict_type(rename = "_Z")]
+        Z = 90,
+        #[strict_type(rename = "sqBracketL")]
+        SqBracketL = 91,
+        BackSlash = 92,
+        #[strict_type(rename = "sqBracketR")]
+        SqBracketR = 93,
+        Caret = 94,
+        Lodash = 95,
+        Backtick = 96,
+        #[strict_type(rename = "a")]
+        A97 = 97,
+        #[strict_type(rename = "b")]
+        B98 = 98,
+        #[strict_type(rename = "c")]
+        C99 = 99,
+        #[strict_type(rename = "d")]
+        D100 = 100,
+        #[strict_type(rename = "e")]
+        E101 = 101,
+        #[strict_type(rename = "f")]
+        F102 = 102,
+        #[strict_type(rename = "g")]
+        G103 = 103,
+        #[strict_type(rename = "h")]
+        H104 = 104,
+        #[strict_type(rename = "i")]
+        I105 = 105,
+        #[strict_type(rename = "j")]
+        J106 = 106,
+        #[strict_type(rename = "k")]
+        K107 = 107,
+        #[strict_type(rename = "l")]
+        L108 = 108,
+        #[strict_type(rename = "m")]
+        M109 = 109,
+        #[strict_type(rename = "n")]
+        N110 = 110,
+        #[strict_type(rename = "o")]
+        O111 = 111,
+        #[strict_type(rename = "p")]
+        P112 = 112,
+        #[strict_type(rename = "q")]
+        Q113 = 113,
+        #[strict_type(rename = "r")]
+        R114 = 114,
+        #[strict_type(rename = "s")]
+        S115 = 115,
+        #[strict_type(rename = "t")]
+        T116 = 116,
+        #[strict_type(rename = "u")]
+        U117 = 117,
+        #[strict_type(rename = "v")]
+        V118 = 118,
+        #[strict_type(rename = "w")]
+        W119 = 119,
+        #[strict_type(rename = "x")]
+        X120 = 120,
+        #[strict_type(rename = "y")]
+        Y121 = 121,
+        #[strict_type(rename = "z")]
+        Z122 = 122,
+        #[strict_type(rename = "cBracketL")]
+        CBracketL = 123,
+        Pipe = 124,
+        #[strict_type(rename = "cBracketR")]
+        CBracketR = 125,
+        Tilde = 126,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum Bool {
+        #[strict_type(dumb)]
+        False = 0,
+        True = 1,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum Dec {
+        #[strict_type(dumb)]
+        Zero = 48,
+        One = 49,
+        Two = 50,
+        Three = 51,
+        Four = 52,
+        Five = 53,
+        Six = 54,
+        Seven = 55,
+        Eight = 56,
+        Nine = 57,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum HexDecCaps {
+        #[strict_type(dumb)]
+        Zero = 48,
+        One = 49,
+        Two = 50,
+        Three = 51,
+        Four = 52,
+        Five = 53,
+        Six = 54,
+        Seven = 55,
+        Eight = 56,
+        Nine = 57,
+        Ten = 65,
+        Eleven = 66,
+        Twelve = 67,
+        Thirteen = 68,
+        Fourteen = 69,
+        Fifteen = 70,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum HexDecSmall {
+        #[strict_type(dumb)]
+        Zero = 48,
+        One = 49,
+        Two = 50,
+        Three = 51,
+        Four = 52,
+        Five = 53,
+        Six = 54,
+        Seven = 55,
+        Eight = 56,
+        Nine = 57,
+        Ten = 97,
+        Eleven = 98,
+        Twelve = 99,
+        Thirteen = 100,
+        Fourteen = 101,
+        Fifteen = 102,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum U1 {
+        #[strict_type(dumb, rename = "_0")]
+        V0 = 0,
+        #[strict_type(rename = "_1")]
+        V1 = 1,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum U2 {
+        #[strict_type(dumb, rename = "_0")]
+        V0 = 0,
+        #[strict_type(rename = "_1")]
+        V1 = 1,
+        #[strict_type(rename = "_2")]
+        V2 = 2,
+        #[strict_type(rename = "_3")]
+        V3 = 3,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum U3 {
+        #[strict_type(dumb, rename = "_0")]
+        V0 = 0,
+        #[strict_type(rename = "_1")]
+        V1 = 1,
+        #[strict_type(rename = "_2")]
+        V2 = 2,
+        #[strict_type(rename = "_3")]
+        V3 = 3,
+        #[strict_type(rename = "_4")]
+        V4 = 4,
+        #[strict_type(rename = "_5")]
+        V5 = 5,
+        #[strict_type(rename = "_6")]
+        V6 = 6,
+        #[strict_type(rename = "_7")]
+        V7 = 7,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum U4 {
+        #[strict_type(dumb, rename = "_0")]
+        V0 = 0,
+        #[strict_type(rename = "_1")]
+        V1 = 1,
+        #[strict_type(rename = "_2")]
+        V2 = 2,
+        #[strict_type(rename = "_3")]
+        V3 = 3,
+        #[strict_type(rename = "_4")]
+        V4 = 4,
+        #[strict_type(rename = "_5")]
+        V5 = 5,
+        #[strict_type(rename = "_6")]
+        V6 = 6,
+        #[strict_type(rename = "_7")]
+        V7 = 7,
+        #[strict_type(rename = "_8")]
+        V8 = 8,
+        #[strict_type(rename = "_9")]
+        V9 = 9,
+        #[strict_type(rename = "_10")]
+        V10 = 10,
+        #[strict_type(rename = "_11")]
+        V11 = 11,
+        #[strict_type(rename = "_12")]
+        V12 = 12,
+        #[strict_type(rename = "_13")]
+        V13 = 13,
+        #[strict_type(rename = "_14")]
+        V14 = 14,
+        #[strict_type(rename = "_15")]
+        V15 = 15,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum U5 {
+        #[strict_type(dumb, rename = "_0")]
+        V0 = 0,
+        #[strict_type(rename = "_1")]
+        V1 = 1,
+        #[strict_type(rename = "_2")]
+        V2 = 2,
+        #[strict_type(rename = "_3")]
+        V3 = 3,
+        #[strict_type(rename = "_4")]
+        V4 = 4,
+        #[strict_type(rename = "_5")]
+        V5 = 5,
+        #[strict_type(rename = "_6")]
+        V6 = 6,
+        #[strict_type(rename = "_7")]
+        V7 = 7,
+        #[strict_type(rename = "_8")]
+        V8 = 8,
+        #[strict_type(rename = "_9")]
+        V9 = 9,
+        #[strict_type(rename = "_10")]
+        V10 = 10,
+        #[strict_type(rename = "_11")]
+        V11 = 11,
+        #[strict_type(rename = "_12")]
+        V12 = 12,
+        #[strict_type(rename = "_13")]
+        V13 = 13,
+        #[strict_type(rename = "_14")]
+        V14 = 14,
+        #[strict_type(rename = "_15")]
+        V15 = 15,
+        #[strict_type(rename = "_16")]
+        V16 = 16,
+        #[strict_type(rename = "_17")]
+        V17 = 17,
+        #[strict_type(rename = "_18")]
+        V18 = 18,
+        #[strict_type(rename = "_19")]
+        V19 = 19,
+        #[strict_type(rename = "_20")]
+        V20 = 20,
+        #[strict_type(rename = "_21")]
+        V21 = 21,
+        #[strict_type(rename = "_22")]
+        V22 = 22,
+        #[strict_type(rename = "_23")]
+        V23 = 23,
+        #[strict_type(rename = "_24")]
+        V24 = 24,
+        #[strict_type(rename = "_25")]
+        V25 = 25,
+        #[strict_type(rename = "_26")]
+        V26 = 26,
+        #[strict_type(rename = "_27")]
+        V27 = 27,
+        #[strict_type(rename = "_28")]
+        V28 = 28,
+        #[strict_type(rename = "_29")]
+        V29 = 29,
+        #[strict_type(rename = "_30")]
+        V30 = 30,
+        #[strict_type(rename = "_31")]
+        V31 = 31,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum U6 {
+        #[strict_type(dumb, rename = "_0")]
+        V0 = 0,
+        #[strict_type(rename = "_1")]
+        V1 = 1,
+        #[strict_type(rename = "_2")]
+        V2 = 2,
+        #[strict_type(rename = "_3")]
+        V3 = 3,
+        #[strict_type(rename = "_4")]
+        V4 = 4,
+        #[strict_type(rename = "_5")]
+        V5 = 5,
+        #[strict_type(rename = "_6")]
+        V6 = 6,
+        #[strict_type(rename = "_7")]
+        V7 = 7,
+        #[strict_type(rename = "_8")]
+        V8 = 8,
+        #[strict_type(rename = "_9")]
+        V9 = 9,
+        #[strict_type(rename = "_10")]
+        V10 = 10,
+        #[strict_type(rename = "_11")]
+        V11 = 11,
+        #[strict_type(rename = "_12")]
+        V12 = 12,
+        #[strict_type(rename = "_13")]
+        V13 = 13,
+        #[strict_type(rename = "_14")]
+        V14 = 14,
+        #[strict_type(rename = "_15")]
+        V15 = 15,
+        #[strict_type(rename = "_16")]
+        V16 = 16,
+        #[strict_type(rename = "_17")]
+        V17 = 17,
+        #[strict_type(rename = "_18")]
+        V18 = 18,
+        #[strict_type(rename = "_19")]
+        V19 = 19,
+        #[strict_type(rename = "_20")]
+        V20 = 20,
+        #[strict_type(rename = "_21")]
+        V21 = 21,
+        #[strict_type(rename = "_22")]
+        V22 = 22,
+        #[strict_type(rename = "_23")]
+        V23 = 23,
+        #[strict_type(rename = "_24")]
+        V24 = 24,
+        #[strict_type(rename = "_25")]
+        V25 = 25,
+        #[strict_type(rename = "_26")]
+        V26 = 26,
+        #[strict_type(rename = "_27")]
+        V27 = 27,
+        #[strict_type(rename = "_28")]
+        V28 = 28,
+        #[strict_type(rename = "_29")]
+        V29 = 29,
+        #[strict_type(rename = "_30")]
+        V30 = 30,
+        #[strict_type(rename = "_31")]
+        V31 = 31,
+        #[strict_type(rename = "_32")]
+        V32 = 32,
+        #[strict_type(rename = "_33")]
+        V33 = 33,
+        #[strict_type(rename = "_34")]
+        V34 = 34,
+        #[strict_type(rename = "_35")]
+        V35 = 35,
+        #[strict_type(rename = "_36")]
+        V36 = 36,
+        #[strict_type(rename = "_37")]
+        V37 = 37,
+        #[strict_type(rename = "_38")]
+        V38 = 38,
+        #[strict_type(rename = "_39")]
+        V39 = 39,
+        #[strict_type(rename = "_40")]
+        V40 = 40,
+        #[strict_type(rename = "_41")]
+        V41 = 41,
+        #[strict_type(rename = "_42")]
+        V42 = 42,
+        #[strict_type(rename = "_43")]
+        V43 = 43,
+        #[strict_type(rename = "_44")]
+        V44 = 44,
+        #[strict_type(rename = "_45")]
+        V45 = 45,
+        #[strict_type(rename = "_46")]
+        V46 = 46,
+        #[strict_type(rename = "_47")]
+        V47 = 47,
+        #[strict_type(rename = "_48")]
+        V48 = 48,
+        #[strict_type(rename = "_49")]
+        V49 = 49,
+        #[strict_type(rename = "_50")]
+        V50 = 50,
+        #[strict_type(rename = "_51")]
+        V51 = 51,
+        #[strict_type(rename = "_52")]
+        V52 = 52,
+        #[strict_type(rename = "_53")]
+        V53 = 53,
+        #[strict_type(rename = "_54")]
+        V54 = 54,
+        #[strict_type(rename = "_55")]
+        V55 = 55,
+        #[strict_type(rename = "_56")]
+        V56 = 56,
+        #[strict_type(rename = "_57")]
+        V57 = 57,
+        #[strict_type(rename = "_58")]
+        V58 = 58,
+        #[strict_type(rename = "_59")]
+        V59 = 59,
+        #[strict_type(rename = "_60")]
+        V60 = 60,
+        #[strict_type(rename = "_61")]
+        V61 = 61,
+        #[strict_type(rename = "_62")]
+        V62 = 62,
+        #[strict_type(rename = "_63")]
+        V63 = 63,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    pub enum U7 {
+        #[strict_type(dumb, rename = "_0")]
+        V0 = 0,
+        #[strict_type(rename = "_1")]
+        V1 = 1,
+        #[strict_type(rename = "_2")]
+        V2 = 2,
+        #[strict_type(rename = "_3")]
+        V3 = 3,
+        #[strict_type(rename = "_4")]
+        V4 = 4,
+        #[strict_type(rename = "_5")]
+        V5 = 5,
+        #[strict_type(rename = "_6")]
+        V6 = 6,
+        #[strict_type(rename = "_7")]
+        V7 = 7,
+        #[strict_type(rename = "_8")]
+        V8 = 8,
+        #[strict_type(rename = "_9")]
+        V9 = 9,
+        #[strict_type(rename = "_10")]
+        V10 = 10,
+        #[strict_type(rename = "_11")]
+        V11 = 11,
+        #[strict_type(rename = "_12")]
+        V12 = 12,
+        #[strict_type(rename = "_13")]
+        V13 = 13,
+        #[strict_type(rename = "_14")]
+        V14 = 14,
+        #[strict_type(rename = "_15")]
+        V15 = 15,
+        #[strict_type(rename = "_16")]
+        V16 = 16,
+        #[strict_type(rename = "_17")]
+        V17 = 17,
+        #[strict_type(rename = "_18")]
+        V18 = 18,
+        #[strict_type(rename = "_19")]
+        V19 = 19,
+        #[strict_type(rename = "_20")]
+        V20 = 20,
+        #[strict_type(rename = "_21")]
+        V21 = 21,
+        #[strict_type(rename = "_22")]
+        V22 = 22,
+        #[strict_type(rename = "_23")]
+        V23 = 23,
+        #[strict_type(rename = "_24")]
+        V24 = 24,
+        #[strict_type(rename = "_25")]
+        V25 = 25,
+        #[strict_type(rename = "_26")]
+        V26 = 26,
+        #[strict_type(rename = "_27")]
+        V27 = 27,
+        #[strict_type(rename = "_28")]
+        V28 = 28,
+        #[strict_type(rename = "_29")]
+        V29 = 29,
+        #[strict_type(rename = "_30")]
+        V30 = 30,
+        #[strict_type(rename = "_31")]
+        V31 = 31,
+        #[strict_type(rename = "_32")]
+        V32 = 32,
+        #[strict_type(rename = "_33")]
+        V33 = 33,
+        #[strict_type(rename = "_34")]
+        V34 = 34,
+        #[strict_type(rename = "_35")]
+        V35 = 35,
+        #[strict_type(rename = "_36")]
+        V36 = 36,
+        #[strict_type(rename = "_37")]
+        V37 = 37,
+        #[strict_type(rename = "_38")]
+        V38 = 38,
+        #[strict_type(rename = "_39")]
+        V39 = 39,
+        #[strict_type(rename = "_40")]
+        V40 = 40,
+        #[strict_type(rename = "_41")]
+        V41 = 41,
+        #[strict_type(rename = "_42")]
+        V42 = 42,
+        #[strict_type(rename = "_43")]
+        V43 = 43,
+        #[strict_type(rename = "_44")]
+        V44 = 44,
+        #[strict_type(rename = "_45")]
+        V45 = 45,
+        #[strict_type(rename = "_46")]
+        V46 = 46,
+        #[strict_type(rename = "_47")]
+        V47 = 47,
+        #[strict_type(rename = "_48")]
+        V48 = 48,
+        #[strict_type(rename = "_49")]
+        V49 = 49,
+        #[strict_type(rename = "_50")]
+        V50 = 50,
+        #[strict_type(rename = "_51")]
+        V51 = 51,
+        #[strict_type(rename = "_52")]
+        V52 = 52,
+        #[strict_type(rename = "_53")]
+        V53 = 53,
+        #[strict_type(rename = "_54")]
+        V54 = 54,
+        #[strict_type(rename = "_55")]
+        V55 = 55,
+        #[strict_type(rename = "_56")]
+        V56 = 56,
+        #[strict_type(rename = "_57")]
+        V57 = 57,
+        #[strict_type(rename = "_58")]
+        V58 = 58,
+        #[strict_type(rename = "_59")]
+        V59 = 59,
+        #[strict_type(rename = "_60")]
+        V60 = 60,
+        #[strict_type(rename = "_61")]
+        V61 = 61,
+        #[strict_type(rename = "_62")]
+        V62 = 62,
+        #[strict_type(rename = "_63")]
+        V63 = 63,
+        #[strict_type(rename = "_64")]
+        V64 = 64,
+        #[strict_type(rename = "_65")]
+        V65 = 65,
+        #[strict_type(rename = "_66")]
+        V66 = 66,
+        #[strict_type(rename = "_67")]
+        V67 = 67,
+        #[strict_type(rename = "_68")]
+        V68 = 68,
+        #[strict_type(rename = "_69")]
+        V69 = 69,
+        #[strict_type(rename = "_70")]
+        V70 = 70,
+        #[strict_type(rename = "_71")]
+        V71 = 71,
+        #[strict_type(rename = "_72")]
+        V72 = 72,
+        #[strict_type(rename = "_73")]
+        V73 = 73,
+        #[strict_type(rename = "_74")]
+        V74 = 74,
+        #[strict_type(rename = "_75")]
+        V75 = 75,
+        #[strict_type(rename = "_76")]
+        V76 = 76,
+        #[strict_type(rename = "_77")]
+        V77 = 77,
+        #[strict_type(rename = "_78")]
+        V78 = 78,
+        #[strict_type(rename = "_79")]
+        V79 = 79,
+        #[strict_type(rename = "_80")]
+        V80 = 80,
+        #[strict_type(rename = "_81")]
+        V81 = 81,
+        #[strict_type(rename = "_82")]
+        V82 = 82,
+        #[strict_type(rename = "_83")]
+        V83 = 83,
+        #[strict_type(rename = "_84")]
+        V84 = 84,
+        #[strict_type(rename = "_85")]
+        V85 = 85,
+        #[strict_type(rename = "_86")]
+        V86 = 86,
+        #[strict_type(rename = "_87")]
+        V87 = 87,
+        #[strict_type(rename = "_88")]
+        V88 = 88,
+        #[strict_type(rename = "_89")]
+        V89 = 89,
+        #[strict_type(rename = "_90")]
+        V90 = 90,
+        #[strict_type(rename = "_91")]
+        V91 = 91,
+        #[strict_type(rename = "_92")]
+        V92 = 92,
+        #[strict_type(rename = "_93")]
+        V93 = 93,
+        #[strict_type(rename = "_94")]
+        V94 = 94,
+        #[strict_type(rename = "_95")]
+        V95 = 95,
+        #[strict_type(rename = "_96")]
+        V96 = 96,
+        #[strict_type(rename = "_97")]
+        V97 = 97,
+        #[strict_type(rename = "_98")]
+        V98 = 98,
+        #[strict_type(rename = "_99")]
+        V99 = 99,
+        #[strict_type(rename = "_100")]
+        V100 = 100,
+        #[strict_type(rename = "_101")]
+        V101 = 101,
+        #[strict_type(rename = "_102")]
+        V102 = 102,
+        #[strict_type(rename = "_103")]
+        V103 = 103,
+        #[strict_type(rename = "_104")]
+        V104 = 104,
+        #[strict_type(rename = "_105")]
+        V105 = 105,
+        #[strict_type(rename = "_106")]
+        V106 = 106,
+        #[strict_type(rename = "_107")]
+        V107 = 107,
+        #[strict_type(rename = "_108")]
+        V108 = 108,
+        #[strict_type(rename = "_109")]
+        V109 = 109,
+        #[strict_type(rename = "_110")]
+        V110 = 110,
+        #[strict_type(rename = "_111")]
+        V111 = 111,
+        #[strict_type(rename = "_112")]
+        V112 = 112,
+        #[strict_type(rename = "_113")]
+        V113 = 113,
+        #[strict_type(rename = "_114")]
+        V114 = 114,
+        #[strict_type(rename = "_115")]
+        V115 = 115,
+        #[strict_type(rename = "_116")]
+        V116 = 116,
+        #[strict_type(rename = "_117")]
+        V117 = 117,
+        #[strict_type(rename = "_118")]
+        V118 = 118,
+        #[strict_type(rename = "_119")]
+        V119 = 119,
+        #[strict_type(rename = "_120")]
+        V120 = 120,
+        #[strict_type(rename = "_121")]
+        V121 = 121,
+        #[strict_type(rename = "_122")]
+        V122 = 122,
+        #[strict_type(rename = "_123")]
+        V123 = 123,
+        #[strict_type(rename = "_124")]
+        V124 = 124,
+        #[strict_type(rename = "_125")]
+        V125 = 125,
+        #[strict_type(rename = "_126")]
+        V126 = 126,
+        #[strict_type(rename = "_127")]
+        V127 = 127,
+    }
+
+    /// Compiles `Std` type library from the types defined in this module.
+    pub fn std_stl() -> strict_types::TypeLib {
+        strict_types::LibBuilder::new(LIB_NAME_STD, None)
+        .transpile::<Alpha>()
+        .transpile::<AlphaCaps>()
+        .transpile::<AlphaCapsLodash>()
+        .transpile::<AlphaCapsNum>()
+        .transpile::<AlphaLodash>()
+        .transpile::<AlphaNum>()
+        .transpile::<AlphaNumDash>()
+        .transpile::<AlphaNumLodash>()
+        .transpile::<AlphaSmall>()
+        .transpile::<AlphaSmallLodash>()
+        .transpile::<Ascii>()
+        .transpile::<AsciiPrintable>()
+        .transpile::<Bool>()
+        .transpile::<Dec>()
+        .transpile::<HexDecCaps>()
+        .transpile::<HexDecSmall>()
+        .transpile::<U1>()
+        .transpile::<U2>()
+        .transpile::<U3>()
+        .transpile::<U4>()
+        .transpile::<U5>()
+        .transpile::<U6>()
+        .transpile::<U7>()
+        .compile()
+        .expect("invalid `Std` type library")
+    }
+}
@@ -0,0 +1,645 @@
+// Rust types for the `StrictTypes` strict type library.
+//
+// Library id: stl:6huILnaL-cgjhCxW-Ldwyeid-2fTx$TF-FYVoOht-6UgogfM#opus-william-arsenal
+//
+// Generated by the strict types Rust code generator; do not edit manually.
+//
+// The code requires `strict_encoding` macros to be imported into the crate root with
+// `#[macro_use] extern crate strict_encoding;`. Dumb value expressions in field attributes
+// trigger false `unused_braces` warnings, which should be allowed.
+
+use amplify::confinement::{Confined, LargeVec, MediumOrdMap, MediumOrdSet, NonEmptyOrdMap, NonEmptyOrdSet, NonEmptyVec, SmallOrdMap, SmallOrdSet, TinyOrdMap, TinyOrdSet, TinyVec, U16, U8};
+use strict_encoding::{RString, StrictDecode, StrictDumb, StrictEncode, StrictType};
+
+pub const LIB_NAME_STRICT_TYPES: &str = "StrictTypes";
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct Dependency {
+    pub id: TypeLibId,
+    pub name: LibName,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct EnumVariants(
+    #[strict_type(dumb = { Confined::try_from_iter((0..1).map(|_| strict_dumb!())).expect("dumb value") })]
+    pub NonEmptyOrdSet<Variant, U8>,
+);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct ExternRef {
+    pub lib_id: TypeLibId,
+    pub sem_id: SemId,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct FieldInlineRef {
+    pub name: FieldName,
+    pub ty: InlineRef,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct FieldInlineRef1 {
+    pub name: FieldName,
+    pub ty: InlineRef1,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct FieldInlineRef2 {
+    pub name: FieldName,
+    pub ty: InlineRef2,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct FieldLibRef {
+    pub name: FieldName,
+    pub ty: LibRef,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct FieldName(pub RString<strict_encoding::stl::AlphaSmallLodash, strict_encoding::stl::AlphaNumLodash, 1, 100>);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct FieldSemId {
+    pub name: FieldName,
+    pub ty: SemId,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct Ident(pub RString<strict_encoding::stl::AlphaLodash, strict_encoding::stl::AlphaNumLodash, 1, 100>);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, dumb = Self::Inline(strict_dumb!()), tags = custom)]
+pub enum InlineRef {
+    #[strict_type(tag = 0)]
+    Inline(TyInlineRef1),
+    #[strict_type(tag = 1)]
+    Named(SemId),
+    #[strict_type(tag = 2)]
+    Extern(ExternRef),
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, dumb = Self::Inline(strict_dumb!()), tags = custom)]
+pub enum InlineRef1 {
+    #[strict_type(tag = 0)]
+    Inline(TyInlineRef2),
+    #[strict_type(tag = 1)]
+    Named(SemId),
+    #[strict_type(tag = 2)]
+    Extern(ExternRef),
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, dumb = Self::Named(strict_dumb!()), tags = custom)]
+pub enum InlineRef2 {
+    #[strict_type(tag = 0)]
+    Named(SemId),
+    #[strict_type(tag = 1)]
+    Extern(ExternRef),
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, tags = custom)]
+pub enum ItemCase {
+    #[strict_type(tag = 0)]
+    UnnamedField(u8),
+    #[strict_type(tag = 1)]
+    NamedField(u8, FieldName),
+    #[strict_type(tag = 2)]
+    UnionVariant(u8, VariantName),
+    #[strict_type(tag = 16, dumb)]
+    ArrayItem,
+    #[strict_type(tag = 17)]
+    ListItem,
+    #[strict_type(tag = 18)]
+    SetItem,
+    #[strict_type(tag = 19)]
+    MapKey,
+    #[strict_type(tag = 20)]
+    MapValue,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct LibName(pub RString<strict_encoding::stl::AlphaCapsLodash, strict_encoding::stl::AlphaNumLodash, 1, 100>);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, dumb = Self::Inline(strict_dumb!()), tags = custom)]
+pub enum LibRef {
+    #[strict_type(tag = 0)]
+    Inline(TyInlineRef),
+    #[strict_type(tag = 1)]
+    Named(SemId),
+    #[strict_type(tag = 2)]
+    Extern(ExternRef),
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct MemoryLayout {
+    pub items: LargeVec<TypeInfo>,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct NamedFieldsInlineRef(
+    #[strict_type(dumb = { Confined::try_from_iter((0..1).map(|_| strict_dumb!())).expect("dumb value") })]
+    pub NonEmptyVec<FieldInlineRef, U8>,
+);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct NamedFieldsInlineRef1(
+    #[strict_type(dumb = { Confined::try_from_iter((0..1).map(|_| strict_dumb!())).expect("dumb value") })]
+    pub NonEmptyVec<FieldInlineRef1, U8>,
+);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct NamedFieldsInlineRef2(
+    #[strict_type(dumb = { Confined::try_from_iter((0..1).map(|_| strict_dumb!())).expect("dumb value") })]
+    pub NonEmptyVec<FieldInlineRef2, U8>,
+);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct NamedFieldsLibRef(
+    #[strict_type(dumb = { Confined::try_from_iter((0..1).map(|_| strict_dumb!())).expect("dumb value") })]
+    pub NonEmptyVec<FieldLibRef, U8>,
+);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct NamedFieldsSemId(
+    #[strict_type(dumb = { Confined::try_from_iter((0..1).map(|_| strict_dumb!())).expect("dumb value") })]
+    pub NonEmptyVec<FieldSemId, U8>,
+);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, tags = custom)]
+pub enum NestedCase {
+    #[strict_type(tag = 0)]
+    NewType(Option<TypeFqn>),
+    #[strict_type(tag = 1, dumb)]
+    Option,
+    #[strict_type(tag = 16)]
+    ByteStr,
+    #[strict_type(tag = 17)]
+    AsciiStr(Option<TypeFqn>),
+    #[strict_type(tag = 18)]
+    UniStr,
+    #[strict_type(tag = 19)]
+    RStr(Option<TypeFqn>, Option<TypeFqn>, Sizing),
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct Primitive(pub u8);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct SemId(pub [u8; 32]);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct Sizing {
+    pub min: u64,
+    pub max: u64,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct SymbolRef {
+    pub lib_name: LibName,
+    pub ty_name: TypeName,
+    pub lib_id: TypeLibId,
+    pub sem_id: SemId,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct SymbolicSys {
+    pub symbols: Symbols,
+    pub types: TypeSystem,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct Symbols {
+    pub libs: SmallOrdSet<Dependency>,
+    pub symbols: MediumOrdSet<TypeSymbol>,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, tags = custom)]
+pub enum TyInlineRef {
+    #[strict_type(tag = 0)]
+    Primitive(Primitive),
+    #[strict_type(tag = 1, dumb)]
+    Unicode,
+    #[strict_type(tag = 3)]
+    Enum(EnumVariants),
+    #[strict_type(tag = 4)]
+    Union(UnionVariantsInlineRef),
+    #[strict_type(tag = 5)]
+    Tuple(UnnamedFieldsInlineRef),
+    #[strict_type(tag = 6)]
+    Struct(NamedFieldsInlineRef),
+    #[strict_type(tag = 7)]
+    Array(InlineRef, u16),
+    #[strict_type(tag = 8)]
+    List(InlineRef, Sizing),
+    #[strict_type(tag = 9)]
+    Set(InlineRef, Sizing),
+    #[strict_type(tag = 10)]
+    Map(InlineRef, InlineRef, Sizing),
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, tags = custom)]
+pub enum TyInlineRef1 {
+    #[strict_type(tag = 0)]
+    Primitive(Primitive),
+    #[strict_type(tag = 1, dumb)]
+    Unicode,
+    #[strict_type(tag = 3)]
+    Enum(EnumVariants),
+    #[strict_type(tag = 4)]
+    Union(UnionVariantsInlineRef1),
+    #[strict_type(tag = 5)]
+    Tuple(UnnamedFieldsInlineRef1),
+    #[strict_type(tag = 6)]
+    Struct(NamedFieldsInlineRef1),
+    #[strict_type(tag = 7)]
+    Array(InlineRef1, u16),
+    #[strict_type(tag = 8)]
+    List(InlineRef1, Sizing),
+    #[strict_type(tag = 9)]
+    Set(InlineRef1, Sizing),
+    #[strict_type(tag = 10)]
+    Map(InlineRef1, InlineRef1, Sizing),
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, tags = custom)]
+pub enum TyInlineRef2 {
+    #[strict_type(tag = 0)]
+    Primitive(Primitive),
+    #[strict_type(tag = 1, dumb)]
+    Unicode,
+    #[strict_type(tag = 3)]
+    Enum(EnumVariants),
+    #[strict_type(tag = 4)]
+    Union(UnionVariantsInlineRef2),
+    #[strict_type(tag = 5)]
+    Tuple(UnnamedFieldsInlineRef2),
+    #[strict_type(tag = 6)]
+    Struct(NamedFieldsInlineRef2),
+    #[strict_type(tag = 7)]
+    Array(InlineRef2, u16),
+    #[strict_type(tag = 8)]
+    List(InlineRef2, Sizing),
+    #[strict_type(tag = 9)]
+    Set(InlineRef2, Sizing),
+    #[strict_type(tag = 10)]
+    Map(InlineRef2, InlineRef2, Sizing),
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, tags = custom)]
+pub enum TyLibRef {
+    #[strict_type(tag = 0)]
+    Primitive(Primitive),
+    #[strict_type(tag = 1, dumb)]
+    Unicode,
+    #[strict_type(tag = 3)]
+    Enum(EnumVariants),
+    #[strict_type(tag = 4)]
+    Union(UnionVariantsLibRef),
+    #[strict_type(tag = 5)]
+    Tuple(UnnamedFieldsLibRef),
+    #[strict_type(tag = 6)]
+    Struct(NamedFieldsLibRef),
+    #[strict_type(tag = 7)]
+    Array(LibRef, u16),
+    #[strict_type(tag = 8)]
+    List(LibRef, Sizing),
+    #[strict_type(tag = 9)]
+    Set(LibRef, Sizing),
+    #[strict_type(tag = 10)]
+    Map(LibRef, LibRef, Sizing),
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, tags = custom)]
+pub enum TySemId {
+    #[strict_type(tag = 0)]
+    Primitive(Primitive),
+    #[strict_type(tag = 1, dumb)]
+    Unicode,
+    #[strict_type(tag = 3)]
+    Enum(EnumVariants),
+    #[strict_type(tag = 4)]
+    Union(UnionVariantsSemId),
+    #[strict_type(tag = 5)]
+    Tuple(UnnamedFieldsSemId),
+    #[strict_type(tag = 6)]
+    Struct(NamedFieldsSemId),
+    #[strict_type(tag = 7)]
+    Array(SemId, u16),
+    #[strict_type(tag = 8)]
+    List(SemId, Sizing),
+    #[strict_type(tag = 9)]
+    Set(SemId, Sizing),
+    #[strict_type(tag = 10)]
+    Map(SemId, SemId, Sizing),
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct TypeFqn {
+    pub lib: LibName,
+    pub name: TypeName,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct TypeInfo {
+    pub depth: u32,
+    pub ty: TySemId,
+    pub fqn: Option<TypeFqn>,
+    pub item: Option<ItemCase>,
+    pub nested: TinyVec<NestedCase>,
+    pub back_ref: strict_encoding::stl::Bool,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct TypeLib {
+    pub name: LibName,
+    pub dependencies: TinyOrdSet<Dependency>,
+    pub extern_types: TinyOrdMap<LibName, SmallOrdMap<SemId, TypeName>>,
+    #[strict_type(dumb = { Confined::try_from_iter((0..1).map(|_| (strict_dumb!(), strict_dumb!()))).expect("dumb value") })]
+    pub types: NonEmptyOrdMap<TypeName, TyLibRef, U16>,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct TypeLibId(pub [u8; 32]);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct TypeName(pub RString<strict_encoding::stl::AlphaCapsLodash, strict_encoding::stl::AlphaNumLodash, 1, 100>);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct TypeSymbol {
+    pub id: SemId,
+    pub fqn: Option<TypeFqn>,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct TypeSysId(pub [u8; 32]);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct TypeSystem(pub MediumOrdMap<SemId, TySemId>);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct UnionVariantsInlineRef(pub TinyOrdMap<u8, VariantInfoInlineRef>);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct UnionVariantsInlineRef1(pub TinyOrdMap<u8, VariantInfoInlineRef1>);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct UnionVariantsInlineRef2(pub TinyOrdMap<u8, VariantInfoInlineRef2>);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct UnionVariantsLibRef(pub TinyOrdMap<u8, VariantInfoLibRef>);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct UnionVariantsSemId(pub TinyOrdMap<u8, VariantInfoSemId>);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct UnnamedFieldsInlineRef(
+    #[strict_type(dumb = { Confined::try_from_iter((0..1).map(|_| strict_dumb!())).expect("dumb value") })]
+    pub NonEmptyVec<InlineRef, U8>,
+);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct UnnamedFieldsInlineRef1(
+    #[strict_type(dumb = { Confined::try_from_iter((0..1).map(|_| strict_dumb!())).expect("dumb value") })]
+    pub NonEmptyVec<InlineRef1, U8>,
+);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct UnnamedFieldsInlineRef2(
+    #[strict_type(dumb = { Confined::try_from_iter((0..1).map(|_| strict_dumb!())).expect("dumb value") })]
+    pub NonEmptyVec<InlineRef2, U8>,
+);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct UnnamedFieldsLibRef(
+    #[strict_type(dumb = { Confined::try_from_iter((0..1).map(|_| strict_dumb!())).expect("dumb value") })]
+    pub NonEmptyVec<LibRef, U8>,
+);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct UnnamedFieldsSemId(
+    #[strict_type(dumb = { Confined::try_from_iter((0..1).map(|_| strict_dumb!())).expect("dumb value") })]
+    pub NonEmptyVec<SemId, U8>,
+);
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct Variant {
+    pub name: VariantName,
+    pub tag: u8,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct VariantInfoInlineRef {
+    pub name: VariantName,
+    pub ty: InlineRef,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct VariantInfoInlineRef1 {
+    pub name: VariantName,
+    pub ty: InlineRef1,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct VariantInfoInlineRef2 {
+    pub name: VariantName,
+    pub ty: InlineRef2,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct VariantInfoLibRef {
+    pub name: VariantName,
+    pub ty: LibRef,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct VariantInfoSemId {
+    pub name: VariantName,
+    pub ty: SemId,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct VariantName(pub RString<strict_encoding::stl::AlphaSmallLodash, strict_encoding::stl::AlphaNumLodash, 1, 100>);
+
+/// Compiles `StrictTypes` type library from the types defined in this module.
+pub fn strict_types_stl() -> strict_types::TypeLib {
+    strict_types::LibBuilder::new(LIB_NAME_STRICT_TYPES, [
+        strict_types::Dependency::with("stl:yiweb4OZ-3TAMPm!-eUS$XRw-iMgF32K-DbZZJX5-xmwCVCc#ralph-blue-lucky".parse().expect("valid library id"), "Std".into()),
+    ])
+    .transpile::<Dependency>()
+    .transpile::<EnumVariants>()
+    .transpile::<ExternRef>()
+    .transpile::<FieldInlineRef>()
+    .transpile::<FieldInlineRef1>()
+    .transpile::<FieldInlineRef2>()
+    .transpile::<FieldLibRef>()
+    .transpile::<FieldName>()
+    .transpile::<FieldSemId>()
+    .transpile::<Ident>()
+    .transpile::<InlineRef>()
+    .transpile::<InlineRef1>()
+    .transpile::<InlineRef2>()
+    .transpile::<ItemCase>()
+    .transpile::<LibName>()
+    .transpile::<LibRef>()
+    .transpile::<MemoryLayout>()
+    .transpile::<NamedFieldsInlineRef>()
+    .transpile::<NamedFieldsInlineRef1>()
+    .transpile::<NamedFieldsInlineRef2>()
+    .transpile::<NamedFieldsLibRef>()
+    .transpile::<NamedFieldsSemId>()
+    .transpile::<NestedCase>()
+    .transpile::<Primitive>()
+    .transpile::<SemId>()
+    .transpile::<Sizing>()
+    .transpile::<SymbolRef>()
+    .transpile::<SymbolicSys>()
+    .transpile::<Symbols>()
+    .transpile::<TyInlineRef>()
+    .transpile::<TyInlineRef1>()
+    .transpile::<TyInlineRef2>()
+    .transpile::<TyLibRef>()
+    .transpile::<TySemId>()
+    .transpile::<TypeFqn>()
+    .transpile::<TypeInfo>()
+    .transpile::<TypeLib>()
+    .transpile::<TypeLibId>()
+    .transpile::<TypeName>()
+    .transpile::<TypeSymbol>()
+    .transpile::<TypeSysId>()
+    .transpile::<TypeSystem>()
+    .transpile::<UnionVariantsInlineRef>()
+    .transpile::<UnionVariantsInlineRef1>()
+    .transpile::<UnionVariantsInlineRef2>()
+    .transpile::<UnionVariantsLibRef>()
+    .transpile::<UnionVariantsSemId>()
+    .transpile::<UnnamedFieldsInlineRef>()
+    .transpile::<UnnamedFieldsInlineRef1>()
+    .transpile::<UnnamedFieldsInlineRef2>()
+    .transpile::<UnnamedFieldsLibRef>()
+    .transpile::<UnnamedFieldsSemId>()
+    .transpile::<Variant>()
+    .transpile::<VariantInfoInlineRef>()
+    .transpile::<VariantInfoInlineRef1>()
+    .transpile::<VariantInfoInlineRef2>()
+    .transpile::<VariantInfoLibRef>()
+    .transpile::<VariantInfoSemId>()
+    .transpile::<VariantName>()
+    .compile()
+    .expect("invalid `StrictTypes` type library")
+}
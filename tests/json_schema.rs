@@ -0,0 +1,68 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "serde")]
+
+use serde_json::json;
+use strict_types::codegen::{BytesEncoding, JsonSchemaGen};
+use strict_types::stl::std_stl;
+use strict_types::{SymbolicLib, SymbolicSys, SystemBuilder};
+
+fn codegen_test_sys() -> SymbolicSys {
+    let src = include_str!("data/CodegenTest.sty");
+    let lib = SymbolicLib::from_source(src, [&std_stl()]).unwrap().compile().unwrap();
+    SystemBuilder::new().import(std_stl()).unwrap().import(lib).unwrap().finalize().unwrap()
+}
+
+#[test]
+fn account_schema() {
+    let sys = codegen_test_sys();
+    let schema = sys.type_tree("CodegenTest.Account").unwrap().to_json_schema();
+    let expected: serde_json::Value =
+        serde_json::from_str(include_str!("data/account.schema.json")).unwrap();
+    assert_eq!(schema, expected);
+}
+
+#[test]
+fn inline_schema() {
+    let src = "typelib Test\ndata Kind : a | b\ndata Data : bytes [Byte ^ 1..0xff], map {Kind -> \
+               ^ ..0xff I8}\n";
+    let lib = SymbolicLib::from_source(src, None).unwrap().compile().unwrap();
+    let sys = SystemBuilder::new().import(lib).unwrap().finalize().unwrap();
+    let tree = sys.type_tree("Test.Data").unwrap();
+    let schema = JsonSchemaGen::new().bytes_encoding(BytesEncoding::Base64).generate(&tree);
+
+    let data = &schema["$defs"]["Test.Data"]["properties"];
+    assert_eq!(data["bytes"]["contentEncoding"], json!("base64"));
+    assert_eq!(data["bytes"]["minLength"], json!(4));
+    assert_eq!(data["bytes"]["maxLength"], json!(340));
+    assert_eq!(
+        data["map"],
+        json!({
+            "type": "object",
+            "propertyNames": { "$ref": "#/$defs/Test.Kind" },
+            "additionalProperties": { "type": "integer", "minimum": -128, "maximum": 127 },
+            "maxProperties": 255
+        })
+    );
+    assert_eq!(schema["$defs"]["Test.Kind"], json!({ "type": "string", "enum": ["a", "b"] }));
+}
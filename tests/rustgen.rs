@@ -0,0 +1,79 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[macro_use]
+extern crate strict_encoding;
+
+use strict_types::codegen::RustGen;
+use strict_types::stl::{std_stl, strict_types_stl, strict_types_sym};
+use strict_types::{SymbolicLib, SymbolicSys, SystemBuilder, TypeLib};
+
+#[allow(clippy::all, dead_code, unused_braces)]
+mod strict_types_rs {
+    include!("data/strict_types.rs");
+}
+
+#[allow(clippy::all, dead_code, unused_braces)]
+mod codegen_test_rs {
+    include!("data/codegen_test.rs");
+}
+
+fn codegen_test_lib() -> TypeLib {
+    let src = include_str!("data/CodegenTest.sty");
+    SymbolicLib::from_source(src, [&std_stl()]).unwrap_or_else(|e| panic!("{e}")).compile().unwrap()
+}
+
+fn codegen_test_sys() -> SymbolicSys {
+    SystemBuilder::new()
+        .import(std_stl())
+        .unwrap()
+        .import(codegen_test_lib())
+        .unwrap()
+        .finalize()
+        .unwrap()
+}
+
+#[test]
+fn lib_codegen() {
+    let code = RustGen::new()
+        .extern_lib(libname!("Std"), "strict_encoding::stl")
+        .generate_lib(&strict_types_sym())
+        .unwrap();
+    assert_eq!(code, include_str!("data/strict_types.rs"));
+}
+
+#[test]
+fn sys_codegen() {
+    let code = RustGen::new().generate_sys(&codegen_test_sys()).unwrap();
+    assert_eq!(code, include_str!("data/codegen_test.rs"));
+}
+
+#[test]
+fn lib_roundtrip() {
+    assert_eq!(strict_types_rs::strict_types_stl().id(), strict_types_stl().id());
+}
+
+#[test]
+fn sys_roundtrip() {
+    assert_eq!(codegen_test_rs::std_::std_stl().id(), std_stl().id());
+    assert_eq!(codegen_test_rs::codegen_test::codegen_test_stl().id(), codegen_test_lib().id());
+}